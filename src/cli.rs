@@ -1,7 +1,8 @@
 // ABOUTME: Command-line interface definition using clap derive macros.
 // ABOUTME: Defines all subcommands and their arguments.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "peleka")]
@@ -50,6 +51,19 @@ pub enum Commands {
         /// Break existing deploy lock if held
         #[arg(long)]
         force: bool,
+
+        /// Start and health check the new container without moving traffic
+        /// or touching existing containers, then remove it
+        #[arg(long)]
+        shadow: bool,
+
+        /// Override an env var for this deploy (repeatable)
+        #[arg(long = "env", value_name = "KEY=VALUE")]
+        env: Vec<String>,
+
+        /// Read env overrides from a KEY=VALUE file (repeatable, applied before --env)
+        #[arg(long = "env-file", value_name = "PATH")]
+        env_file: Vec<PathBuf>,
     },
 
     /// Rollback to the previous deployment
@@ -59,6 +73,20 @@ pub enum Commands {
         destination: Option<String>,
     },
 
+    /// Check running containers against config without changing anything
+    Verify {
+        /// Target destination (defined in config)
+        #[arg(short, long)]
+        destination: Option<String>,
+    },
+
+    /// Poll container health and restart unhealthy containers (health.auto_restart)
+    Watch {
+        /// Target destination (defined in config)
+        #[arg(short, long)]
+        destination: Option<String>,
+    },
+
     /// Execute a command in the service container
     Exec {
         /// Target destination (defined in config)
@@ -69,4 +97,92 @@ pub enum Commands {
         #[arg(required = true, trailing_var_arg = true)]
         command: Vec<String>,
     },
+
+    /// Show logs from the service container
+    Logs {
+        /// Target destination (defined in config)
+        #[arg(short, long)]
+        destination: Option<String>,
+
+        /// Use the previous (stopped) release container instead of the active one
+        #[arg(long)]
+        previous: bool,
+
+        /// Follow log output
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Number of lines to show from the end of the logs
+        #[arg(short = 'n', long)]
+        tail: Option<u64>,
+    },
+
+    /// Check connectivity to each server and report per-step latency
+    Ping {
+        /// Target destination (defined in config)
+        #[arg(short, long)]
+        destination: Option<String>,
+    },
+
+    /// Show details of the service container
+    Inspect {
+        /// Target destination (defined in config)
+        #[arg(short, long)]
+        destination: Option<String>,
+
+        /// Use the previous (stopped) release container instead of the active one
+        #[arg(long)]
+        previous: bool,
+    },
+
+    /// List files added, changed or deleted inside the service container since it was created
+    #[command(name = "diff-fs")]
+    DiffFs {
+        /// Target destination (defined in config)
+        #[arg(short, long)]
+        destination: Option<String>,
+
+        /// Use the previous (stopped) release container instead of the active one
+        #[arg(long)]
+        previous: bool,
+
+        /// Leave out changes under this path (repeatable), e.g. --ignore /tmp
+        #[arg(long, value_name = "PATH")]
+        ignore: Vec<String>,
+    },
+
+    /// Print a shell completion script (completes destinations from peleka.yml)
+    Completions {
+        /// Shell to generate the script for
+        shell: CompletionShell,
+    },
+
+    /// Print the peleka version
+    Version {
+        /// Check for a newer release and the config's min_peleka_version
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Print completion candidates (used by completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// What to complete
+        target: CompletionTarget,
+    },
+}
+
+/// Shells with completion scripts.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Values that completion scripts ask for.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CompletionTarget {
+    /// Destination names from peleka.yml in the current directory
+    Destinations,
 }
@@ -0,0 +1,79 @@
+// ABOUTME: Shell completion scripts and the hidden `__complete` helper they call.
+// ABOUTME: Destination names are read from peleka.yml at completion time, never baked in.
+
+use crate::cli::{Cli, CompletionShell};
+use clap::CommandFactory;
+use peleka::config::Config;
+use peleka::output::{Output, Stream};
+use std::path::Path;
+
+/// Print a completion script for `shell`.
+///
+/// Subcommands are taken from the CLI definition; `-d/--destination` values
+/// are completed by calling `peleka __complete destinations`.
+pub fn completions(shell: CompletionShell, output: Output) {
+    let subcommands = Cli::command()
+        .get_subcommands()
+        .filter(|c| !c.is_hide_set())
+        .map(|c| c.get_name().to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let script = match shell {
+        CompletionShell::Bash => BASH_SCRIPT,
+        CompletionShell::Zsh => ZSH_SCRIPT,
+        CompletionShell::Fish => FISH_SCRIPT,
+    };
+    output.write(
+        Stream::Stdout,
+        &script.replace("@SUBCOMMANDS@", &subcommands),
+    );
+}
+
+/// Print destination names from the config in `dir`, one per line.
+///
+/// Called by completion scripts on every keypress, so a missing or invalid
+/// config prints nothing instead of an error.
+pub fn complete_destinations(dir: &Path, output: Output) {
+    let Ok(config) = Config::discover(dir) else {
+        return;
+    };
+    let mut names: Vec<_> = config.destinations.keys().collect();
+    names.sort();
+    for name in names {
+        output.line(name);
+    }
+}
+
+const BASH_SCRIPT: &str = r#"_peleka() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        -d|--destination)
+            COMPREPLY=($(compgen -W "$(peleka __complete destinations 2>/dev/null)" -- "$cur"))
+            return
+            ;;
+    esac
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "@SUBCOMMANDS@" -- "$cur"))
+    fi
+}
+complete -o default -F _peleka peleka
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef peleka
+_peleka() {
+    if (( CURRENT == 2 )); then
+        compadd -- @SUBCOMMANDS@
+    elif [[ ${words[CURRENT-1]} == (-d|--destination) ]]; then
+        compadd -- ${(f)"$(peleka __complete destinations 2>/dev/null)"}
+    else
+        _files
+    fi
+}
+compdef _peleka peleka
+"#;
+
+const FISH_SCRIPT: &str = r#"complete -c peleka -f -n '__fish_use_subcommand' -a '@SUBCOMMANDS@'
+complete -c peleka -s d -l destination -x -a '(peleka __complete destinations 2>/dev/null)'
+"#;
@@ -2,65 +2,159 @@
 // ABOUTME: Handles deployment orchestration, hooks, and state machine execution.
 
 use super::runtime_connection::connect_to_runtime;
-use peleka::config::{Config, ServerConfig};
+use chrono::SecondsFormat;
+use peleka::config::{Config, LowMemoryAction, ServerConfig};
 use peleka::deploy::{
-    ContainerErrorExt, DeployError, DeployLock, DeployStrategy, Deployment, Initialized,
-    cleanup_orphans, detect_orphans,
+    ContainerErrorExt, DeployError, DeployLock, DeployMetadata, DeployStrategy, Deployment,
+    Initialized, LocalLock, MemoryCheck, cleanup_ephemeral, cleanup_orphans, detect_orphans,
+    host_available_memory, local_lock_dir,
 };
 use peleka::diagnostics::{Diagnostics, Warning};
+use peleka::discovery::{self, DiscoveryTarget};
 use peleka::error::{Error, Result};
-use peleka::hooks::{HookContext, HookPoint, HookRunner};
-use peleka::output::Output;
-use peleka::runtime::{BollardRuntime, ContainerFilters, ContainerOps};
-use peleka::ssh::Session;
+use peleka::hooks::{HookContext, HookPoint, HookRunner, PhaseResult};
+use peleka::output::{Output, PhaseStatus, Stream};
+use peleka::runtime::{
+    CachedRuntime, ContainerFilters, ContainerOps, ContainerSummary, ExecOps, ImageOps, NetworkOps,
+    RuntimeType,
+};
+use peleka::ssh::{CLOCK_SKEW_THRESHOLD, Session};
+use peleka::types::ContainerId;
 use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 
 /// Deploy to all configured servers.
-pub async fn deploy(config: Config, force: bool, mut output: Output) -> Result<()> {
+///
+/// Holds a local lock for the service and `destination` for the whole run,
+/// so a second deploy of the same target from this machine fails fast.
+///
+/// With `shadow`, the new container is pulled, started and health checked
+/// but never receives traffic; existing containers and hooks are skipped.
+pub async fn deploy(
+    config: Config,
+    destination: Option<&str>,
+    force: bool,
+    shadow: bool,
+    mut output: Output,
+) -> Result<()> {
     if config.servers.is_empty() {
         return Err(Error::NoServers);
     }
 
+    // Fail fast if this machine is already deploying the same target,
+    // before touching any server lock. Released when dropped.
+    let _local_lock = LocalLock::acquire(&local_lock_dir(), &config.service, destination)?;
+
     output.start_timer();
+    let started = Instant::now();
     let cwd = env::current_dir()?;
     let hook_runner = HookRunner::new(&cwd);
-    let mut diag = Diagnostics::default();
+    let mut phases: Vec<PhaseResult> = Vec::new();
+    // Shared by every server so one deploy ID identifies the whole run
+    let metadata = DeployMetadata::new();
 
     output.progress(&format!(
-        "Deploying {} ({}) to {} server(s)",
+        "{} {} ({}) to {} server(s)",
+        if shadow {
+            "Shadow deploying"
+        } else {
+            "Deploying"
+        },
         config.service,
         config.image,
         config.servers.len()
     ));
 
+    // Preflight: writes that would fail on a read-only root filesystem
+    for warning in config.read_only_warnings() {
+        output.warning(&warning);
+    }
+    for warning in config.auto_update_warnings() {
+        output.warning(&warning);
+    }
+    if let Some(summary) = config.env_override_summary() {
+        output.progress(&format!("Env overrides: {}", summary));
+    }
+
+    if shadow {
+        return shadow_deploy(&config, force, &metadata, output).await;
+    }
+
     // Run pre-deploy hook for each server
     for server in &config.servers {
-        let hook_context = HookContext::new(&config, server);
+        let hook_context =
+            HookContext::new(&config, server).with_phases(&phases, started.elapsed());
 
         if let Some(result) = hook_runner.run(HookPoint::PreDeploy, &hook_context).await
             && !result.success
         {
-            eprintln!("Pre-deploy hook failed for {}", server.host);
+            output.write(
+                Stream::Stderr,
+                &format!("Pre-deploy hook failed for {}\n", server.host),
+            );
             if !result.stderr.is_empty() {
-                eprintln!("{}", result.stderr);
+                output.write(Stream::Stderr, &format!("{}\n", result.stderr));
             }
             return Err(Error::Hook("pre-deploy hook failed".to_string()));
         }
     }
 
-    // Deploy to each server
+    // Deploy to each server. Sessions stay open until the post-deploy hooks
+    // have run, so hooks can reach each runtime through PELEKA_DOCKER_HOST.
+    // Each server keeps its own prefixed output and warnings.
+    let mut sessions = Vec::new();
     let mut deploy_error = None;
     for server in &config.servers {
-        if let Err(e) = deploy_to_server(&config, server, force, &output, &mut diag).await {
-            eprintln!("Failed to deploy to {}: {}", server.host, e);
+        let phase_start = Instant::now();
+        let output = output.for_server(&server.host);
+        let mut diag = Diagnostics::default();
+        let (result, socket) = match connect_server(server, &output, &mut diag).await {
+            Ok(mut session) => {
+                let result = deploy_to_server(
+                    &config,
+                    server,
+                    &mut session,
+                    force,
+                    false,
+                    &metadata,
+                    &output,
+                )
+                .await;
+                let socket = session.forwarded_socket();
+                sessions.push((session, output.clone(), diag));
+                (result, socket)
+            }
+            Err(e) => {
+                emit_warnings(&output, &diag);
+                (Err(e), None)
+            }
+        };
+        phases.push(PhaseResult {
+            phase: "deploy".to_string(),
+            server: Some(server.host.clone()),
+            success: result.is_ok(),
+            duration_secs: phase_start.elapsed().as_secs_f64(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+
+        if let Err(e) = result {
+            output.phase(
+                &server.host,
+                "deploy",
+                PhaseStatus::Failed,
+                &format!("Deploy failed: {}", e),
+            );
 
             // Run on-error hook
-            let hook_context = HookContext::new(&config, server);
+            let hook_context = HookContext::new(&config, server)
+                .with_phases(&phases, started.elapsed())
+                .with_forwarded_socket(socket);
 
             if let Some(result) = hook_runner.run(HookPoint::OnError, &hook_context).await
                 && !result.success
             {
-                eprintln!("Warning: on-error hook failed");
+                output.warning("on-error hook failed");
             }
 
             deploy_error = Some(e);
@@ -69,57 +163,201 @@ pub async fn deploy(config: Config, force: bool, mut output: Output) -> Result<(
     }
 
     if let Some(e) = deploy_error {
+        for (server, (session, output, mut diag)) in config.servers.iter().zip(sessions) {
+            disconnect_server(session, server, &mut diag).await;
+            emit_warnings(&output, &diag);
+        }
         return Err(e);
     }
 
     // Run post-deploy hook for each server
-    for server in &config.servers {
-        let hook_context = HookContext::new(&config, server);
+    for (server, (session, output, _)) in config.servers.iter().zip(&sessions) {
+        let hook_context = HookContext::new(&config, server)
+            .with_phases(&phases, started.elapsed())
+            .with_forwarded_socket(session.forwarded_socket());
 
         if let Some(result) = hook_runner.run(HookPoint::PostDeploy, &hook_context).await
             && !result.success
         {
-            eprintln!("Warning: post-deploy hook failed for {}", server.host);
+            output.warning("post-deploy hook failed");
         }
     }
 
-    // Emit collected warnings
-    for warning in diag.warnings() {
-        output.warning(&warning.message);
+    for (server, (session, output, mut diag)) in config.servers.iter().zip(sessions) {
+        disconnect_server(session, server, &mut diag).await;
+        emit_warnings(&output, &diag);
     }
 
-    output.success("Deployment complete!");
+    output.progress(&format!(
+        "Deploy ID: {} (deployed at {})",
+        metadata.id,
+        metadata
+            .deployed_at
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+    ));
+
+    let total = config.servers.len();
+    output.finish(
+        "Deployment complete!",
+        &format!("deployed {} to {}/{} servers", config.image, total, total),
+    );
     Ok(())
 }
 
-/// Deploy to a single server.
-async fn deploy_to_server(
+/// Shadow deploy to all configured servers, skipping hooks.
+async fn shadow_deploy(
     config: &Config,
-    server: &ServerConfig,
     force: bool,
+    metadata: &DeployMetadata,
+    output: Output,
+) -> Result<()> {
+    for server in &config.servers {
+        let server_output = output.for_server(&server.host);
+        let mut diag = Diagnostics::default();
+        let mut session = connect_server(server, &server_output, &mut diag).await?;
+        let result = deploy_to_server(
+            config,
+            server,
+            &mut session,
+            force,
+            true,
+            metadata,
+            &server_output,
+        )
+        .await;
+        disconnect_server(session, server, &mut diag).await;
+        emit_warnings(&server_output, &diag);
+        if let Err(e) = result {
+            output.write(
+                Stream::Stderr,
+                &format!("Shadow deploy failed on {}: {}\n", server.host, e),
+            );
+            return Err(e);
+        }
+    }
+
+    let total = config.servers.len();
+    output.finish(
+        "Shadow deployment passed; traffic was not moved",
+        &format!(
+            "shadow-deployed {} on {}/{} servers",
+            config.image, total, total
+        ),
+    );
+    Ok(())
+}
+
+/// Open an SSH session to a server and check its clock.
+async fn connect_server(
+    server: &ServerConfig,
     output: &Output,
     diag: &mut Diagnostics,
-) -> Result<()> {
-    output.progress(&format!("  → Connecting to {}...", server.host));
+) -> Result<Session> {
+    output.phase(
+        &server.host,
+        "connect",
+        PhaseStatus::Started,
+        &format!("Connecting to {}...", server.host),
+    );
 
     let session = Session::connect(server.ssh_session_config()).await?;
+    check_clock_skew(&session, server, diag).await;
+    Ok(session)
+}
 
-    // Run deployment with lock, ensuring cleanup on error or panic
-    output.progress("  → Acquiring deploy lock...");
-    let result = DeployLock::with_lock(&session, &config.service, force, async {
-        deploy_to_server_inner(config, server, &session, output).await
-    })
-    .await;
-
-    // Disconnect SSH session (non-fatal if it fails)
+/// Disconnect SSH session (non-fatal if it fails).
+async fn disconnect_server(session: Session, server: &ServerConfig, diag: &mut Diagnostics) {
     if let Err(e) = session.disconnect().await {
         diag.warn(Warning::ssh_disconnect(format!(
             "SSH disconnect failed for {}: {}",
             server.host, e
         )));
     }
+}
 
-    result
+/// Emit a server's collected warnings on its prefixed output.
+fn emit_warnings(output: &Output, diag: &Diagnostics) {
+    for warning in diag.warnings() {
+        output.warning(&warning.message);
+    }
+}
+
+/// Deploy to a single server over an open session.
+///
+/// If the session drops before any container has been changed, it is
+/// reopened (replacing `session`) and the pipeline resumes: the network,
+/// pull and lookup phases are safe to repeat. Once containers have been
+/// stopped or created, a dropped session fails the deploy. Shadow deploys
+/// always resume, since a rerun removes the stale shadow container first.
+async fn deploy_to_server(
+    config: &Config,
+    server: &ServerConfig,
+    session: &mut Session,
+    force: bool,
+    shadow: bool,
+    metadata: &DeployMetadata,
+    output: &Output,
+) -> Result<()> {
+    let mut reconnects = 0;
+    loop {
+        let committed = AtomicBool::new(false);
+
+        // Run deployment with lock, ensuring cleanup on error or panic
+        output.phase(
+            &server.host,
+            "lock",
+            PhaseStatus::Started,
+            "Acquiring deploy lock...",
+        );
+        let current: &Session = session;
+        let result = DeployLock::with_lock(current, &config.service, force, async {
+            deploy_to_server_inner(
+                config, server, current, shadow, &committed, metadata, output,
+            )
+            .await
+        })
+        .await;
+
+        let e = match result {
+            Err(e)
+                if !committed.load(Ordering::SeqCst)
+                    && current.is_closed()
+                    && reconnects < current.config().reconnect_attempts =>
+            {
+                e
+            }
+            other => return other,
+        };
+
+        reconnects += 1;
+        output.phase(
+            &server.host,
+            "connect",
+            PhaseStatus::Started,
+            &format!(
+                "Connection to {} lost ({}); reconnecting...",
+                server.host, e
+            ),
+        );
+        *session = session.reconnect().await?;
+    }
+}
+
+/// Warn when the server clock disagrees with ours.
+///
+/// Lock timestamps are already translated to the server clock; the warning
+/// is for everything else that compares times, like container timestamps.
+async fn check_clock_skew(session: &Session, server: &ServerConfig, diag: &mut Diagnostics) {
+    match session.clock_skew().await {
+        Ok(skew) if skew.exceeds(CLOCK_SKEW_THRESHOLD) => {
+            diag.warn(Warning::clock_skew(format!(
+                "Clock on {} is {}; check NTP on both machines",
+                server.host, skew
+            )));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("Could not measure clock skew on {}: {}", server.host, e),
+    }
 }
 
 /// Inner deployment logic (runs while holding lock).
@@ -127,12 +365,82 @@ async fn deploy_to_server_inner(
     config: &Config,
     server: &ServerConfig,
     session: &Session,
+    shadow: bool,
+    committed: &AtomicBool,
+    metadata: &DeployMetadata,
     output: &Output,
 ) -> Result<()> {
-    let runtime = connect_to_runtime(session, server, output).await?;
+    // Cache listings/inspections for this run so repeated queries across
+    // phases don't each cost an SSH round-trip.
+    let runtime = CachedRuntime::new(connect_to_runtime(session, server, output).await?);
+
+    // Checker containers of a killed run would otherwise linger
+    match cleanup_ephemeral(&runtime, &config.service, &metadata.id).await {
+        Ok(removed) => {
+            for id in removed {
+                output.progress(&format!(
+                    "  → Removed leftover ephemeral container {} from an interrupted run",
+                    id
+                ));
+            }
+        }
+        Err(e) => tracing::debug!(
+            "Could not list ephemeral containers on {}: {}",
+            server.host,
+            e
+        ),
+    }
+
+    // Auto-update is a Podman feature; elsewhere peleka keeps pulling
+    let without_auto_update;
+    let runtime_type = runtime.inner().runtime_type();
+    let config = if config.auto_update_policy().is_some() && runtime_type != RuntimeType::Podman {
+        output.warning(&format!(
+            "podman_auto_update is ignored on {}: it needs Podman, found {}",
+            server.host, runtime_type
+        ));
+        without_auto_update = Config {
+            podman_auto_update: None,
+            ..config.clone()
+        };
+        &without_auto_update
+    } else {
+        config
+    };
 
+    let result = if shadow {
+        run_shadow_deployment(&runtime, config, metadata, output).await
+    } else {
+        deploy_with_runtime(
+            config, server, session, &runtime, committed, metadata, output,
+        )
+        .await
+    };
+
+    let stats = runtime.stats();
+    tracing::debug!(
+        "Runtime cache for {}: {} round-trip(s) made, {} saved, {} invalidation(s)",
+        server.host,
+        stats.round_trips_made(),
+        stats.round_trips_saved(),
+        stats.invalidations
+    );
+
+    result
+}
+
+/// Strategy selection and state machine execution against a connected runtime.
+async fn deploy_with_runtime<R: ContainerOps + ImageOps + NetworkOps + ExecOps>(
+    config: &Config,
+    server: &ServerConfig,
+    session: &Session,
+    runtime: &R,
+    committed: &AtomicBool,
+    metadata: &DeployMetadata,
+    output: &Output,
+) -> Result<()> {
     // Determine deployment strategy
-    let (strategy, reason) = DeployStrategy::for_config(config);
+    let (mut strategy, reason) = DeployStrategy::for_config(config);
     if let Some(reason) = reason {
         output.warning(&format!(
             "Using recreate strategy (brief downtime): {}",
@@ -141,7 +449,7 @@ async fn deploy_to_server_inner(
     }
 
     // Find existing container for this service
-    let old_container = find_existing_container(&runtime, &config.service).await?;
+    let old_container = find_existing_container(runtime, &config.service).await?;
 
     if let Some(ref id) = old_container {
         output.progress(&format!("  → Found existing container: {}", id));
@@ -149,9 +457,15 @@ async fn deploy_to_server_inner(
         output.progress("  → No existing container (first deploy)");
     }
 
+    // Blue-green runs both containers at once; make sure the host can take it
+    if strategy == DeployStrategy::BlueGreen && old_container.is_some() {
+        strategy = check_host_memory(config, server, session, output).await?;
+    }
+
     // Handle strategy-specific pre-deployment and create deployment state machine.
     let deployment: Deployment<Initialized> = match (strategy, old_container) {
         (DeployStrategy::Recreate, Some(old_id)) => {
+            committed.store(true, Ordering::SeqCst);
             output.progress("  → Stopping old container (recreate strategy)...");
             let stop_timeout = config.stop_timeout();
             runtime
@@ -166,22 +480,75 @@ async fn deploy_to_server_inner(
                 .context_container_remove()?;
             Deployment::new(config.clone())
         }
-        (DeployStrategy::BlueGreen, Some(old_id)) => {
-            // Give ownership to deployment for blue-green cutover
+        (DeployStrategy::BlueGreen | DeployStrategy::Replace, Some(old_id)) => {
+            // Give ownership to deployment for blue-green cutover; the replace
+            // strategy stops it after the pull, keeping it for rollback
             Deployment::new_update(config.clone(), old_id)
         }
         (_, None) => Deployment::new(config.clone()),
     };
 
     // Run deployment state machine
-    run_deployment(deployment, &runtime, config, output).await?;
+    let deployment = deployment.with_metadata(metadata.clone());
+
+    // A run killed between create and rollback leaves a container that
+    // would block the new one's name
+    for id in deployment.remove_leftovers(runtime).await? {
+        output.progress(&format!(
+            "  → Removed leftover container {} from an interrupted deploy",
+            id
+        ));
+    }
+    run_deployment(
+        deployment, strategy, runtime, server, session, committed, output,
+    )
+    .await?;
 
     Ok(())
 }
 
+/// Check the server can fit the new container next to the old one.
+///
+/// Returns the strategy to use: blue-green when it fits (or when memory
+/// can't be measured), replace when it doesn't and `on_low_memory` allows.
+async fn check_host_memory(
+    config: &Config,
+    server: &ServerConfig,
+    session: &Session,
+    output: &Output,
+) -> Result<DeployStrategy> {
+    let Some(resources) = &config.resources else {
+        return Ok(DeployStrategy::BlueGreen);
+    };
+
+    let available = match host_available_memory(session).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::debug!("Could not read available memory on {}: {}", server.host, e);
+            return Ok(DeployStrategy::BlueGreen);
+        }
+    };
+
+    match MemoryCheck::for_config(config, available) {
+        Some(check) if !check.fits() => match resources.on_low_memory {
+            LowMemoryAction::Replace => {
+                output.warning(&format!(
+                    "Not enough memory on {} for blue-green ({}); using replace strategy",
+                    server.host, check
+                ));
+                Ok(DeployStrategy::Replace)
+            }
+            LowMemoryAction::Fail => {
+                Err(DeployError::insufficient_memory(format!("{}: {}", server.host, check)).into())
+            }
+        },
+        _ => Ok(DeployStrategy::BlueGreen),
+    }
+}
+
 /// Find existing container for a service (running or stopped).
-pub async fn find_existing_container(
-    runtime: &BollardRuntime,
+pub async fn find_existing_container<R: ContainerOps>(
+    runtime: &R,
     service: &peleka::types::ServiceName,
 ) -> Result<Option<peleka::types::ContainerId>> {
     // Include stopped containers - recreate strategy needs to remove them
@@ -192,52 +559,198 @@ pub async fn find_existing_container(
         .await
         .map_err(|e| DeployError::config_error(format!("failed to list containers: {}", e)))?;
 
-    // Return the first running container found
-    Ok(containers.into_iter().next().map(|c| c.id))
+    // Return the first running container found, ignoring shadow containers
+    Ok(containers.into_iter().find(|c| !is_shadow(c)).map(|c| c.id))
+}
+
+/// Whether a container was created by `deploy --shadow`.
+fn is_shadow(container: &ContainerSummary) -> bool {
+    container.labels.get("peleka.shadow").map(String::as_str) == Some("true")
+}
+
+/// Run the pipeline up to the health check without cutting over.
+///
+/// The shadow container gets no service alias and no host ports, so it can
+/// run next to an existing (possibly non-peleka) deployment. It is removed
+/// once the health check has passed.
+async fn run_shadow_deployment<R: ContainerOps + ImageOps + NetworkOps>(
+    runtime: &R,
+    config: &Config,
+    metadata: &DeployMetadata,
+    output: &Output,
+) -> Result<()> {
+    // Remove leftovers from an interrupted shadow run so the name is free
+    let filters = ContainerFilters::for_service(&config.service, true);
+    let containers = runtime
+        .list_containers(&filters)
+        .await
+        .map_err(|e| DeployError::config_error(format!("failed to list containers: {}", e)))?;
+    for stale in containers.iter().filter(|c| is_shadow(c)) {
+        output.progress(&format!(
+            "  → Removing stale shadow container {}...",
+            stale.id
+        ));
+        runtime
+            .remove_container(&stale.id, true)
+            .await
+            .context_container_remove()?;
+    }
+
+    let deployment = Deployment::new_shadow(config.clone()).with_metadata(metadata.clone());
+
+    output.progress("  → Ensuring network exists...");
+    // The container joins it at creation; cutover never runs, so no alias
+    let _ = deployment.ensure_network(runtime).await?;
+
+    output.progress("  → Pulling image...");
+    let deployment = deployment.pull_image(runtime, None).await?;
+
+    output.progress("  → Starting shadow container...");
+    let deployment = deployment.start_container(runtime).await?;
+
+    output.progress("  → Waiting for health check...");
+    let health_timeout = deployment.config().health_timeout;
+    let deployment = match deployment.health_check(runtime, health_timeout).await {
+        Ok(d) => d,
+        Err((failed_deployment, e)) => {
+            output.write(Stream::Stderr, &format!("  ✗ Health check failed: {}\n", e));
+            output.progress("  → Removing shadow container...");
+            failed_deployment.rollback(runtime).await?;
+            return Err(e.into());
+        }
+    };
+
+    output.progress(&format!(
+        "  ✓ Shadow container {} passed health check",
+        deployment.new_container()
+    ));
+    output.progress("  → Removing shadow container...");
+    deployment.rollback(runtime).await?;
+
+    Ok(())
 }
 
 /// Run the deployment state machine.
-async fn run_deployment(
+async fn run_deployment<R: ContainerOps + ImageOps + NetworkOps + ExecOps>(
     deployment: Deployment<Initialized>,
-    runtime: &BollardRuntime,
-    config: &Config,
+    strategy: DeployStrategy,
+    runtime: &R,
+    server: &ServerConfig,
+    session: &Session,
+    committed: &AtomicBool,
     output: &Output,
 ) -> Result<()> {
     // Ensure network exists
-    output.progress("  → Ensuring network exists...");
+    output.phase(
+        &server.host,
+        "network",
+        PhaseStatus::Started,
+        "Ensuring network exists...",
+    );
     let network_id = deployment.ensure_network(runtime).await?;
 
     // Pull image
-    output.progress("  → Pulling image...");
-    let deployment = deployment.pull_image(runtime, None).await?;
+    output.phase(
+        &server.host,
+        "pull",
+        PhaseStatus::Started,
+        "Pulling image...",
+    );
+    let mut deployment = deployment.pull_image(runtime, None).await?;
+
+    // Replace strategy: never run two copies at once
+    if strategy == DeployStrategy::Replace && deployment.old_container().is_some() {
+        committed.store(true, Ordering::SeqCst);
+        output.phase(
+            &server.host,
+            "stop",
+            PhaseStatus::Started,
+            "Stopping old container (replace strategy)...",
+        );
+        deployment = deployment.stop_old_container(runtime).await?;
+    }
 
     // Start container
-    output.progress("  → Starting container...");
+    committed.store(true, Ordering::SeqCst);
+    output.phase(
+        &server.host,
+        "start",
+        PhaseStatus::Started,
+        "Starting container...",
+    );
     let deployment = deployment.start_container(runtime).await?;
 
     // Health check
-    output.progress("  → Waiting for health check...");
+    output.phase(
+        &server.host,
+        "health",
+        PhaseStatus::Started,
+        "Waiting for health check...",
+    );
     let health_timeout = deployment.config().health_timeout;
     let deployment = match deployment.health_check(runtime, health_timeout).await {
         Ok(d) => d,
         Err((failed_deployment, e)) => {
-            eprintln!("  ✗ Health check failed: {}", e);
-            output.progress("  → Rolling back...");
+            output.phase(
+                &server.host,
+                "health",
+                PhaseStatus::Failed,
+                &format!("Health check failed: {}", e),
+            );
+            output.phase(
+                &server.host,
+                "rollback",
+                PhaseStatus::Started,
+                "Rolling back...",
+            );
             failed_deployment.rollback(runtime).await?;
             return Err(e.into());
         }
     };
 
+    // A passing health check can hide a crash-restart during startup
+    check_stability(runtime, deployment.new_container(), server, output).await;
+
     // Cutover
-    output.progress("  → Cutting over traffic...");
+    output.phase(
+        &server.host,
+        "cutover",
+        PhaseStatus::Started,
+        "Cutting over traffic...",
+    );
     let deployment = deployment.cutover(runtime, &network_id).await?;
 
+    // Publish the new container to external service discovery
+    if !deployment.config().discovery.is_empty() {
+        output.phase(
+            &server.host,
+            "discovery",
+            PhaseStatus::Started,
+            "Updating service discovery...",
+        );
+        update_discovery(
+            runtime,
+            deployment.config(),
+            server,
+            session,
+            deployment.new_container(),
+            output,
+        )
+        .await;
+    }
+
     // Cleanup old container
-    output.progress("  → Cleaning up...");
+    output.phase(
+        &server.host,
+        "cleanup",
+        PhaseStatus::Started,
+        "Cleaning up...",
+    );
     let deployment = deployment.cleanup(runtime).await?;
 
     // Detect and cleanup orphaned containers
     let deployed_id = deployment.deployed_container().clone();
+    let image_digest = deployment.metadata().image_digest.clone();
     let old_id = deployment.config().service.clone();
     let deployment_config = deployment.finish();
 
@@ -247,7 +760,7 @@ async fn run_deployment(
         known_containers.push(old_container.clone());
     }
 
-    let orphans = detect_orphans(runtime, &config.service, &known_containers)
+    let orphans = detect_orphans(runtime, &deployment_config.service, &known_containers)
         .await
         .map_err(|e| DeployError::config_error(format!("failed to detect orphans: {}", e)))?;
 
@@ -271,7 +784,96 @@ async fn run_deployment(
         }
     }
 
-    output.progress(&format!("  ✓ Deployed container: {}", deployed_id));
+    output.phase(
+        &server.host,
+        "deploy",
+        PhaseStatus::Ok,
+        &match image_digest {
+            Some(digest) => format!("Deployed container: {} ({})", deployed_id, digest),
+            None => format!("Deployed container: {}", deployed_id),
+        },
+    );
 
     Ok(())
 }
+
+/// Warn if the new container restarted or was OOM-killed before it turned healthy.
+async fn check_stability<R: ContainerOps>(
+    runtime: &R,
+    container_id: &ContainerId,
+    server: &ServerConfig,
+    output: &Output,
+) {
+    match runtime.inspect_container(container_id).await {
+        Ok(info) if info.has_crashed() => output.warning(&format!(
+            "New container {} passed its health check but has {}",
+            info.name,
+            info.crash_summary()
+        )),
+        Ok(_) => {}
+        Err(e) => tracing::debug!(
+            "Could not inspect {} on {} for restarts: {}",
+            container_id,
+            server.host,
+            e
+        ),
+    }
+}
+
+/// Register the new container with each configured discovery provider.
+///
+/// Traffic has already moved to the new container, so failures are reported
+/// as warnings rather than failing the deployment.
+async fn update_discovery<R: ContainerOps>(
+    runtime: &R,
+    config: &Config,
+    server: &ServerConfig,
+    session: &Session,
+    container_id: &ContainerId,
+    output: &Output,
+) {
+    let info = match runtime.inspect_container(container_id).await {
+        Ok(info) => info,
+        Err(e) => {
+            output.warning(&format!(
+                "Skipping service discovery on {}: failed to inspect container: {}",
+                server.host, e
+            ));
+            return;
+        }
+    };
+
+    // Prefer the address on the service network, fall back to any network
+    let networks = &info.network_settings.networks;
+    let address = networks
+        .get(&config.network_name())
+        .into_iter()
+        .chain(networks.values())
+        .map(|n| n.ip_address.clone())
+        .find(|ip| !ip.is_empty());
+    let Some(address) = address else {
+        output.warning(&format!(
+            "Skipping service discovery on {}: container has no network address",
+            server.host
+        ));
+        return;
+    };
+
+    let target = DiscoveryTarget {
+        service: config.service.to_string(),
+        server: server.host.clone(),
+        container: container_id.to_string(),
+        address,
+        port: config.primary_container_port(),
+    };
+
+    for provider in &config.discovery {
+        match discovery::register(session, provider, &target).await {
+            Ok(()) => output.progress(&format!("  ✓ Registered with {}", provider.kind())),
+            Err(e) => output.warning(&format!(
+                "Service discovery update failed on {}: {}",
+                server.host, e
+            )),
+        }
+    }
+}
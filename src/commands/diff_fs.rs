@@ -0,0 +1,112 @@
+// ABOUTME: diff-fs command implementation.
+// ABOUTME: Lists files added, changed or deleted inside a release container since it was created.
+
+use super::runtime_connection::connect_to_runtime;
+use peleka::config::{Config, ServerConfig};
+use peleka::deploy::{DeployError, Release, find_release};
+use peleka::diagnostics::{Diagnostics, Warning};
+use peleka::error::{Error, Result};
+use peleka::output::Output;
+use peleka::runtime::{ChangeKind, ContainerOps, FilesystemChange};
+use peleka::ssh::Session;
+
+/// Show filesystem changes in the service container on the first server.
+///
+/// Paths under any of `ignore` are left out, so expected writes (e.g. `/tmp`)
+/// don't hide unexpected ones.
+pub async fn diff_fs(
+    config: Config,
+    release: Release,
+    ignore: Vec<String>,
+    output: Output,
+) -> Result<()> {
+    if config.servers.is_empty() {
+        return Err(Error::NoServers);
+    }
+
+    let mut diag = Diagnostics::default();
+
+    let server = &config.servers[0];
+    let result = changes_on_server(&config, server, release, &output, &mut diag).await;
+
+    for warning in diag.warnings() {
+        output.warning(&warning.message);
+    }
+
+    let mut changes: Vec<_> = result?
+        .into_iter()
+        .filter(|change| !ignore.iter().any(|prefix| is_under(&change.path, prefix)))
+        .collect();
+    changes.sort();
+
+    for change in &changes {
+        output.line(&format!("{} {}", change.kind.marker(), change.path));
+    }
+    output.progress(&summary(&changes));
+    Ok(())
+}
+
+/// Read the filesystem diff of the release container on a single server.
+async fn changes_on_server(
+    config: &Config,
+    server: &ServerConfig,
+    release: Release,
+    output: &Output,
+    diag: &mut Diagnostics,
+) -> Result<Vec<FilesystemChange>> {
+    output.progress(&format!("  → Connecting to {}...", server.host));
+
+    let session = Session::connect(server.ssh_session_config()).await?;
+    let runtime = connect_to_runtime(&session, server, output).await?;
+
+    let result = async {
+        let container = find_release(&runtime, &config.service, release)
+            .await?
+            .ok_or_else(|| {
+                DeployError::config_error(format!("no {} container found for service", release))
+            })?;
+
+        output.progress(&format!("  → Reading changes in {}", container.name));
+        runtime.container_changes(&container.id).await.map_err(|e| {
+            Error::from(DeployError::config_error(format!(
+                "failed to read container changes: {}",
+                e
+            )))
+        })
+    }
+    .await;
+
+    // Disconnect SSH session (non-fatal if it fails)
+    if let Err(e) = session.disconnect().await {
+        diag.warn(Warning::ssh_disconnect(format!(
+            "SSH disconnect failed for {}: {}",
+            server.host, e
+        )));
+    }
+
+    result
+}
+
+/// Whether `path` is `prefix` itself or lies beneath it.
+fn is_under(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// One-line count of changes by kind.
+fn summary(changes: &[FilesystemChange]) -> String {
+    if changes.is_empty() {
+        return "No filesystem changes since the container was created".to_string();
+    }
+    let count = |kind| changes.iter().filter(|c| c.kind == kind).count();
+    format!(
+        "{} change(s): {} added, {} changed, {} deleted",
+        changes.len(),
+        count(ChangeKind::Added),
+        count(ChangeKind::Changed),
+        count(ChangeKind::Deleted)
+    )
+}
@@ -4,13 +4,19 @@
 use super::deploy::find_existing_container;
 use super::runtime_connection::connect_to_runtime;
 use peleka::config::{Config, ServerConfig};
-use peleka::deploy::DeployError;
+use peleka::deploy::{DeployError, new_run_id};
 use peleka::diagnostics::{Diagnostics, Warning};
 use peleka::error::{Error, Result};
-use peleka::output::Output;
+use peleka::output::{Output, PhaseStatus, Stream};
 use peleka::runtime::{ExecConfig, ExecOps};
 use peleka::ssh::Session;
 
+/// Set on every command run through `peleka exec`.
+const EXEC_ENV: &str = "PELEKA_EXEC";
+
+/// ID of the `peleka exec` run, for matching audit logs.
+const RUN_ID_ENV: &str = "PELEKA_RUN_ID";
+
 /// Execute a command in the service container.
 pub async fn exec_command(config: Config, command: Vec<String>, output: Output) -> Result<()> {
     if config.servers.is_empty() {
@@ -39,7 +45,12 @@ async fn exec_on_server(
     output: &Output,
     diag: &mut Diagnostics,
 ) -> Result<()> {
-    output.progress(&format!("  → Connecting to {}...", server.host));
+    output.phase(
+        &server.host,
+        "connect",
+        PhaseStatus::Started,
+        &format!("Connecting to {}...", server.host),
+    );
 
     let session = Session::connect(server.ssh_session_config()).await?;
     let runtime = connect_to_runtime(&session, server, output).await?;
@@ -49,12 +60,25 @@ async fn exec_on_server(
         .await?
         .ok_or_else(|| DeployError::config_error("no running container found for service"))?;
 
-    output.progress(&format!("  → Executing in container {}...", container_id));
+    // Mark the command so it can be told apart from the app's own processes
+    let run_id = new_run_id();
+    output.phase(
+        &server.host,
+        "exec",
+        PhaseStatus::Started,
+        &format!(
+            "Executing in container {} (run {})...",
+            container_id, run_id
+        ),
+    );
 
     // Build exec config
     let exec_config = ExecConfig {
         cmd: command.to_vec(),
-        env: vec![],
+        env: vec![
+            format!("{}=true", EXEC_ENV),
+            format!("{}={}", RUN_ID_ENV, run_id),
+        ],
         working_dir: None,
         user: None,
         attach_stdin: false,
@@ -74,21 +98,25 @@ async fn exec_on_server(
     // Print output
     if !result.stdout.is_empty() {
         let stdout = String::from_utf8_lossy(&result.stdout);
-        print!("{}", stdout);
+        output.command_output(&server.host, Stream::Stdout, &stdout);
     }
     if !result.stderr.is_empty() {
         let stderr = String::from_utf8_lossy(&result.stderr);
-        eprint!("{}", stderr);
+        output.command_output(&server.host, Stream::Stderr, &stderr);
     }
 
     // Check exit code
     if result.exit_code != 0 {
-        return Err(DeployError::config_error(format!(
-            "command exited with code {}",
-            result.exit_code
-        ))
-        .into());
+        let message = format!("command exited with code {}", result.exit_code);
+        output.phase(&server.host, "exec", PhaseStatus::Failed, &message);
+        return Err(DeployError::config_error(message).into());
     }
+    output.phase(
+        &server.host,
+        "exec",
+        PhaseStatus::Ok,
+        "command exited with code 0",
+    );
 
     // Disconnect SSH session (non-fatal if it fails)
     if let Err(e) = session.disconnect().await {
@@ -0,0 +1,119 @@
+// ABOUTME: Inspect command implementation.
+// ABOUTME: Prints details of the active or previous release container.
+
+use super::runtime_connection::connect_to_runtime;
+use peleka::config::{AUTO_UPDATE_LABEL, Config, ServerConfig};
+use peleka::deploy::{DeployError, Release, find_release};
+use peleka::diagnostics::{Diagnostics, Warning};
+use peleka::error::{Error, Result};
+use peleka::output::Output;
+use peleka::runtime::{ContainerInfo, ContainerOps};
+use peleka::ssh::Session;
+
+/// Show details of the service container on the first server.
+pub async fn inspect(config: Config, release: Release, output: Output) -> Result<()> {
+    if config.servers.is_empty() {
+        return Err(Error::NoServers);
+    }
+
+    let mut diag = Diagnostics::default();
+
+    let server = &config.servers[0];
+    let result = inspect_on_server(&config, server, release, &output, &mut diag).await;
+
+    for warning in diag.warnings() {
+        output.warning(&warning.message);
+    }
+
+    let info = result?;
+    print_info(&output, &info, release);
+    Ok(())
+}
+
+/// Inspect the release container on a single server.
+async fn inspect_on_server(
+    config: &Config,
+    server: &ServerConfig,
+    release: Release,
+    output: &Output,
+    diag: &mut Diagnostics,
+) -> Result<ContainerInfo> {
+    output.progress(&format!("  → Connecting to {}...", server.host));
+
+    let session = Session::connect(server.ssh_session_config()).await?;
+    let runtime = connect_to_runtime(&session, server, output).await?;
+
+    let result = async {
+        let container = find_release(&runtime, &config.service, release)
+            .await?
+            .ok_or_else(|| {
+                DeployError::config_error(format!("no {} container found for service", release))
+            })?;
+
+        runtime.inspect_container(&container.id).await.map_err(|e| {
+            Error::from(DeployError::config_error(format!(
+                "failed to inspect container: {}",
+                e
+            )))
+        })
+    }
+    .await;
+
+    // Disconnect SSH session (non-fatal if it fails)
+    if let Err(e) = session.disconnect().await {
+        diag.warn(Warning::ssh_disconnect(format!(
+            "SSH disconnect failed for {}: {}",
+            server.host, e
+        )));
+    }
+
+    result
+}
+
+/// Print container details as aligned key/value lines.
+fn print_info(output: &Output, info: &ContainerInfo, release: Release) {
+    output.line(&format!("Release:   {}", release));
+    output.line(&format!("Name:      {}", info.name));
+    output.line(&format!("ID:        {}", info.id));
+    output.line(&format!("Image:     {}", info.image));
+    output.line(&format!("Image ID:  {}", info.image_id));
+    output.line(&format!("State:     {:?}", info.state));
+    if let Some(health) = info.health {
+        output.line(&format!("Health:    {:?}", health));
+    }
+    output.line(&format!("Created:   {}", info.created));
+    if let Some(policy) = &info.restart_policy {
+        output.line(&format!("Restart:   {}", policy));
+    }
+    output.line(&format!("Restarts:  {}", info.restart_count));
+    if let Some(policy) = info.labels.get(AUTO_UPDATE_LABEL) {
+        output.line(&format!("Updates:   podman auto-update ({})", policy));
+    }
+    if info.oom_killed {
+        output.line("OOM:       killed by the OOM killer");
+    }
+    if let Some(code) = info.exit_code.filter(|c| *c != 0) {
+        output.line(&format!("Exit code: {}", code));
+    }
+
+    let mut networks: Vec<_> = info.network_settings.networks.iter().collect();
+    networks.sort_by_key(|(name, _)| name.as_str());
+    for (name, net) in networks {
+        output.line(&format!(
+            "Network:   {} ({}) aliases: {}",
+            name,
+            net.ip_address,
+            net.aliases.join(", ")
+        ));
+    }
+
+    let mut labels: Vec<_> = info
+        .labels
+        .iter()
+        .filter(|(k, _)| k.starts_with("peleka."))
+        .collect();
+    labels.sort();
+    for (key, value) in labels {
+        output.line(&format!("Label:     {}={}", key, value));
+    }
+}
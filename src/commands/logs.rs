@@ -0,0 +1,103 @@
+// ABOUTME: Logs command implementation.
+// ABOUTME: Streams logs from the active or previous release container.
+
+use super::runtime_connection::connect_to_runtime;
+use futures::StreamExt;
+use peleka::config::{Config, ServerConfig};
+use peleka::deploy::{DeployError, Release, find_release};
+use peleka::diagnostics::{Diagnostics, Warning};
+use peleka::error::{Error, Result};
+use peleka::output::{Output, Stream};
+use peleka::runtime::{LogOps, LogOptions, LogStream};
+use peleka::ssh::Session;
+
+/// Options for the logs command.
+pub struct LogsArgs {
+    pub release: Release,
+    pub follow: bool,
+    pub tail: Option<u64>,
+}
+
+/// Show logs from the service container on the first server.
+pub async fn logs(config: Config, args: LogsArgs, output: Output) -> Result<()> {
+    if config.servers.is_empty() {
+        return Err(Error::NoServers);
+    }
+
+    let mut diag = Diagnostics::default();
+
+    let server = &config.servers[0];
+    let result = logs_on_server(&config, server, &args, &output, &mut diag).await;
+
+    for warning in diag.warnings() {
+        output.warning(&warning.message);
+    }
+
+    result
+}
+
+/// Stream logs on a single server.
+async fn logs_on_server(
+    config: &Config,
+    server: &ServerConfig,
+    args: &LogsArgs,
+    output: &Output,
+    diag: &mut Diagnostics,
+) -> Result<()> {
+    output.progress(&format!("  → Connecting to {}...", server.host));
+
+    let session = Session::connect(server.ssh_session_config()).await?;
+    let runtime = connect_to_runtime(&session, server, output).await?;
+
+    let result = async {
+        let container = find_release(&runtime, &config.service, args.release)
+            .await?
+            .ok_or_else(|| {
+                DeployError::config_error(format!(
+                    "no {} container found for service",
+                    args.release
+                ))
+            })?;
+
+        output.progress(&format!(
+            "  → Showing logs for {} ({})",
+            container.name, args.release
+        ));
+
+        let opts = LogOptions {
+            stdout: true,
+            stderr: true,
+            follow: args.follow,
+            tail: args.tail,
+            ..Default::default()
+        };
+
+        let mut stream = runtime
+            .container_logs(&container.id, &opts)
+            .await
+            .map_err(|e| DeployError::config_error(format!("failed to read logs: {}", e)))?;
+
+        while let Some(line) = stream.next().await {
+            let line =
+                line.map_err(|e| DeployError::config_error(format!("log stream failed: {}", e)))?;
+            let stream = match line.stream {
+                LogStream::Stdout => Stream::Stdout,
+                LogStream::Stderr => Stream::Stderr,
+            };
+            output.write(stream, &line.content);
+        }
+
+        Ok::<_, Error>(())
+    }
+    .await;
+
+    // Disconnect SSH session (non-fatal if it fails)
+    if let Err(e) = session.disconnect().await {
+        diag.warn(Warning::ssh_disconnect(format!(
+            "SSH disconnect failed for {}: {}",
+            server.host, e
+        )));
+    }
+
+    result
+}
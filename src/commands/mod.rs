@@ -1,11 +1,27 @@
 // ABOUTME: Command module aggregator for the peleka CLI.
-// ABOUTME: Re-exports deploy, rollback, and exec command handlers.
+// ABOUTME: Re-exports deploy, rollback, verify, watch, exec, logs, inspect, diff-fs, ping, version, and completion handlers.
 
+mod completions;
 mod deploy;
+mod diff_fs;
 mod exec;
+mod inspect;
+mod logs;
+mod ping;
 mod rollback;
 mod runtime_connection;
+mod verify;
+mod version;
+mod watch;
 
+pub use completions::{complete_destinations, completions};
 pub use deploy::deploy;
+pub use diff_fs::diff_fs;
 pub use exec::exec_command;
+pub use inspect::inspect;
+pub use logs::{LogsArgs, logs};
+pub use ping::ping;
 pub use rollback::rollback;
+pub use verify::verify;
+pub use version::version;
+pub use watch::watch;
@@ -0,0 +1,177 @@
+// ABOUTME: Ping command implementation for connection diagnostics.
+// ABOUTME: Times SSH handshake, auth, runtime detection, socket forwarding, daemon ping and admin login per server.
+
+use super::runtime_connection::detect_server_runtime;
+use futures::future::join_all;
+use peleka::config::{Config, ServerConfig, SshRole};
+use peleka::diagnostics::{Diagnostics, Warning};
+use peleka::error::{Error, Result};
+use peleka::output::{Output, PhaseStatus};
+use peleka::runtime::{RuntimeError, RuntimeInfoTrait, connect_to_socket};
+use peleka::ssh::{self, Session};
+use std::time::{Duration, Instant};
+
+/// Connect to every server and report per-step latency.
+///
+/// Nothing is changed on the servers. Servers are checked concurrently; the
+/// first failure is returned so the exit code reflects it.
+pub async fn ping(config: Config, mut output: Output) -> Result<()> {
+    if config.servers.is_empty() {
+        return Err(Error::NoServers);
+    }
+
+    output.start_timer();
+
+    let total = config.servers.len();
+    output.progress(&format!("Pinging {} server(s)", total));
+
+    let reports = join_all(config.servers.iter().map(ping_server)).await;
+
+    let mut first_error = None;
+    for (server, report) in config.servers.iter().zip(reports) {
+        let output = output.for_server(&server.host);
+        for (step, elapsed) in &report.steps {
+            output.phase(
+                &server.host,
+                step,
+                PhaseStatus::Ok,
+                &format!("{:<8} {}", step, format_latency(*elapsed)),
+            );
+        }
+        if let Some((step, e)) = report.failure {
+            output.phase(
+                &server.host,
+                step,
+                PhaseStatus::Failed,
+                &format!("{:<8} failed: {}", step, e),
+            );
+            first_error.get_or_insert(e);
+        }
+        for warning in report.diag.warnings() {
+            output.warning(&warning.message);
+        }
+    }
+
+    if let Some(e) = first_error {
+        return Err(e);
+    }
+
+    output.finish(
+        &format!("All {} server(s) reachable", total),
+        &format!("reached {}/{} servers", total, total),
+    );
+    Ok(())
+}
+
+/// Outcome of pinging one server.
+#[derive(Default)]
+struct PingReport {
+    /// Completed steps and how long each took.
+    steps: Vec<(&'static str, Duration)>,
+    /// The step that failed, if any. Later steps are not attempted.
+    failure: Option<(&'static str, Error)>,
+    diag: Diagnostics,
+}
+
+impl PingReport {
+    fn fail(mut self, step: &'static str, e: impl Into<Error>) -> Self {
+        self.failure = Some((step, e.into()));
+        self
+    }
+}
+
+/// Run each connection step against a single server, stopping at the first failure.
+async fn ping_server(server: &ServerConfig) -> PingReport {
+    let mut report = PingReport::default();
+
+    let session = match Session::connect(server.ssh_session_config()).await {
+        Ok(session) => session,
+        Err(e) => {
+            let step = match &e {
+                ssh::Error::AuthenticationFailed
+                | ssh::Error::AgentUnavailable(_)
+                | ssh::Error::KeyLoadFailed { .. } => "auth",
+                _ => "connect",
+            };
+            return report.fail(step, e);
+        }
+    };
+    let timings = session.connect_timings();
+    report.steps.push(("connect", timings.handshake));
+    report.steps.push(("auth", timings.auth));
+
+    let mut report = ping_runtime(&session, server, report).await;
+
+    // Disconnect SSH session (non-fatal if it fails)
+    if let Err(e) = session.disconnect().await {
+        report.diag.warn(Warning::ssh_disconnect(format!(
+            "SSH disconnect failed for {}: {}",
+            server.host, e
+        )));
+    }
+
+    if report.failure.is_none() && server.has_separate_admin() {
+        report = ping_admin(server, report).await;
+    }
+
+    report
+}
+
+/// Check that the admin identity can log in when it differs from the deploy user.
+async fn ping_admin(server: &ServerConfig, mut report: PingReport) -> PingReport {
+    let session = match Session::connect(server.ssh_session_config_for(SshRole::Admin)).await {
+        Ok(session) => session,
+        Err(e) => return report.fail("admin", e),
+    };
+    let timings = session.connect_timings();
+    report
+        .steps
+        .push(("admin", timings.handshake + timings.auth));
+
+    if let Err(e) = session.disconnect().await {
+        report.diag.warn(Warning::ssh_disconnect(format!(
+            "SSH disconnect failed for {}: {}",
+            server.host, e
+        )));
+    }
+
+    report
+}
+
+/// Detect, forward and ping the runtime over an established session.
+async fn ping_runtime(
+    session: &Session,
+    server: &ServerConfig,
+    mut report: PingReport,
+) -> PingReport {
+    let started = Instant::now();
+    let info = match detect_server_runtime(session, server).await {
+        Ok(info) => info,
+        Err(e) => return report.fail("detect", e),
+    };
+    report.steps.push(("detect", started.elapsed()));
+
+    let started = Instant::now();
+    let runtime = match connect_to_socket(session, info.runtime_type, &info.socket_path).await {
+        Ok(runtime) => runtime,
+        Err(e) => return report.fail("forward", RuntimeError::from(e)),
+    };
+    report.steps.push(("forward", started.elapsed()));
+
+    let started = Instant::now();
+    if let Err(e) = runtime.ping().await {
+        return report.fail("ping", RuntimeError::from(e));
+    }
+    report.steps.push(("ping", started.elapsed()));
+
+    report
+}
+
+/// Format a latency for display, e.g. `42ms` or `1.30s`.
+fn format_latency(elapsed: Duration) -> String {
+    if elapsed < Duration::from_secs(1) {
+        format!("{}ms", elapsed.as_millis())
+    } else {
+        format!("{:.2}s", elapsed.as_secs_f64())
+    }
+}
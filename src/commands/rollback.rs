@@ -1,45 +1,102 @@
 // ABOUTME: Rollback command implementation.
-// ABOUTME: Handles reverting deployments to previous container versions.
+// ABOUTME: Reverts all servers concurrently and reports per-host results.
 
 use super::runtime_connection::connect_to_runtime;
+use futures::future::join_all;
 use peleka::config::{Config, ServerConfig};
 use peleka::deploy::manual_rollback;
 use peleka::diagnostics::{Diagnostics, Warning};
 use peleka::error::{Error, Result};
-use peleka::output::Output;
+use peleka::output::{Output, PhaseStatus};
 use peleka::ssh::Session;
 
 /// Rollback to previous deployment on all configured servers.
+///
+/// Servers are rolled back concurrently. A failure on one server does not
+/// stop the others; a per-host summary and a result line are printed at the
+/// end and the first failure is returned so the exit code reflects it.
 pub async fn rollback(config: Config, mut output: Output) -> Result<()> {
     if config.servers.is_empty() {
         return Err(Error::NoServers);
     }
 
     output.start_timer();
-    let mut diag = Diagnostics::default();
 
+    let total = config.servers.len();
     output.progress(&format!(
         "Rolling back {} on {} server(s)",
-        config.service,
-        config.servers.len()
+        config.service, total
     ));
 
-    for server in &config.servers {
-        if let Err(e) = rollback_on_server(&config, server, &output, &mut diag).await {
-            eprintln!("Failed to rollback on {}: {}", server.host, e);
-            return Err(e);
+    // Each server reports its final phase as soon as its result is known
+    let results = join_all(config.servers.iter().map(|server| {
+        let config = &config;
+        let output = output.for_server(&server.host);
+        async move {
+            let mut diag = Diagnostics::default();
+            let result = rollback_on_server(config, server, &output, &mut diag).await;
+            if let Err(e) = &result {
+                output.phase(
+                    &server.host,
+                    "rollback",
+                    PhaseStatus::Failed,
+                    &format!("Rollback failed: {}", e),
+                );
+            }
+            for warning in diag.warnings() {
+                output.warning(&warning.message);
+            }
+            (server, result)
         }
-    }
+    }))
+    .await;
+
+    print_summary(&output, &results);
+
+    let succeeded = results.iter().filter(|(_, result)| result.is_ok()).count();
+    let result_line = format!(
+        "rolled back {} on {}/{} servers",
+        config.service, succeeded, total
+    );
 
-    // Emit collected warnings
-    for warning in diag.warnings() {
-        output.warning(&warning.message);
+    if let Some(e) = results.into_iter().find_map(|(_, result)| result.err()) {
+        output.finish_partial(
+            &format!(
+                "Rollback failed on {}/{} server(s)",
+                total - succeeded,
+                total
+            ),
+            &result_line,
+        );
+        return Err(e);
     }
 
-    output.success("Rollback complete!");
+    output.finish(
+        &format!("Rollback complete on {}/{} server(s)!", total, total),
+        &result_line,
+    );
     Ok(())
 }
 
+/// Print a per-host success/failure table.
+fn print_summary(output: &Output, results: &[(&ServerConfig, Result<()>)]) {
+    let width = results
+        .iter()
+        .map(|(server, _)| server.host.len())
+        .max()
+        .unwrap_or(0);
+
+    output.progress("");
+    output.progress("Rollback summary:");
+    for (server, result) in results {
+        let status = match result {
+            Ok(()) => "ok".to_string(),
+            Err(e) => format!("FAILED ({})", e),
+        };
+        output.progress(&format!("  {:<width$}  {}", server.host, status));
+    }
+}
+
 /// Rollback on a single server.
 async fn rollback_on_server(
     config: &Config,
@@ -47,25 +104,42 @@ async fn rollback_on_server(
     output: &Output,
     diag: &mut Diagnostics,
 ) -> Result<()> {
-    output.progress(&format!("  → Connecting to {}...", server.host));
+    output.phase(
+        &server.host,
+        "connect",
+        PhaseStatus::Started,
+        &format!("Connecting to {}...", server.host),
+    );
 
     let session = Session::connect(server.ssh_session_config()).await?;
     let runtime = connect_to_runtime(&session, server, output).await?;
 
     // Get network ID
-    let network_id = peleka::types::NetworkId::new(config.network_name().to_string());
+    let network_id = peleka::types::NetworkId::new(config.network_name());
 
     // Perform rollback
-    output.progress("  → Swapping containers...");
-    manual_rollback(
+    output.phase(
+        &server.host,
+        "rollback",
+        PhaseStatus::Started,
+        "Swapping containers...",
+    );
+    let result = manual_rollback(
         &runtime,
         &config.service,
         &network_id,
         config.stop_timeout(),
     )
-    .await?;
+    .await;
 
-    output.progress("  ✓ Rollback successful");
+    if result.is_ok() {
+        output.phase(
+            &server.host,
+            "rollback",
+            PhaseStatus::Ok,
+            "Rollback successful",
+        );
+    }
 
     // Disconnect SSH session (non-fatal if it fails)
     if let Err(e) = session.disconnect().await {
@@ -75,5 +149,5 @@ async fn rollback_on_server(
         )));
     }
 
-    Ok(())
+    result.map_err(Error::from)
 }
@@ -1,10 +1,13 @@
 // ABOUTME: Shared helper for connecting to container runtimes via SSH.
 // ABOUTME: Eliminates duplication across deploy, rollback, and exec commands.
 
-use peleka::config::ServerConfig;
+use peleka::config::{ServerConfig, SshRole};
 use peleka::error::Result;
 use peleka::output::Output;
-use peleka::runtime::{BollardRuntime, RuntimeError, connect_via_session, detect_runtime};
+use peleka::runtime::{
+    BollardRuntime, DetectionError, Prerequisites, RuntimeError, RuntimeInfo, connect_to_socket,
+    detect_runtime,
+};
 use peleka::ssh::Session;
 
 /// Connect to the container runtime on a server via SSH.
@@ -19,18 +22,64 @@ pub async fn connect_to_runtime(
     output: &Output,
 ) -> Result<BollardRuntime> {
     output.progress("  → Detecting runtime...");
-    let runtime_info = detect_runtime(session, Some(&server.runtime_config()))
-        .await
-        .map_err(RuntimeError::from)?;
+    let runtime_info = detect_server_runtime(session, server).await?;
 
     output.progress(&format!(
         "  → Found {} at {}",
         runtime_info.runtime_type, runtime_info.socket_path
     ));
 
-    let runtime = connect_via_session(session, runtime_info.runtime_type)
-        .await
-        .map_err(RuntimeError::from)?;
+    // Reuse the detected socket rather than probing the server again
+    let runtime = connect_to_socket(
+        session,
+        runtime_info.runtime_type,
+        &runtime_info.socket_path,
+    )
+    .await
+    .map_err(RuntimeError::from)?;
 
     Ok(runtime)
 }
+
+/// Detect the runtime on a server over its deploy session.
+///
+/// When nothing is found, the system socket units are checked as the admin
+/// user so the error can explain what to start.
+pub async fn detect_server_runtime(
+    session: &Session,
+    server: &ServerConfig,
+) -> std::result::Result<RuntimeInfo, RuntimeError> {
+    match detect_runtime(session, Some(&server.runtime_config())).await {
+        Err(DetectionError::NoRuntimeFound(mut prerequisites)) => {
+            check_system_units(session, server, &mut prerequisites).await;
+            Err(DetectionError::NoRuntimeFound(prerequisites).into())
+        }
+        result => result.map_err(RuntimeError::from),
+    }
+}
+
+/// Check system socket units, logging in as the admin user if it differs.
+async fn check_system_units(
+    session: &Session,
+    server: &ServerConfig,
+    prerequisites: &mut Prerequisites,
+) {
+    if !server.has_separate_admin() {
+        prerequisites.check_system_units(session).await;
+        return;
+    }
+
+    match Session::connect(server.ssh_session_config_for(SshRole::Admin)).await {
+        Ok(admin) => {
+            prerequisites.check_system_units(&admin).await;
+            if let Err(e) = admin.disconnect().await {
+                tracing::debug!("admin SSH disconnect failed for {}: {}", server.host, e);
+            }
+        }
+        Err(e) => tracing::debug!(
+            "could not connect to {} as admin to check socket units: {}",
+            server.host,
+            e
+        ),
+    }
+}
@@ -0,0 +1,104 @@
+// ABOUTME: Verify command implementation for read-only drift detection.
+// ABOUTME: Compares running containers to peleka.yml and fails if any server deviates.
+
+use super::runtime_connection::connect_to_runtime;
+use peleka::config::{Config, ServerConfig};
+use peleka::deploy::{VerifyReport, verify_service};
+use peleka::diagnostics::{Diagnostics, Warning};
+use peleka::error::{Error, Result};
+use peleka::output::Output;
+use peleka::ssh::Session;
+
+/// Verify that every configured server runs the service as configured.
+///
+/// Nothing is pulled, created, or removed. Returns `Error::DriftDetected`
+/// if any server deviates, so the command can run on a CI schedule. A server
+/// that cannot be checked is reported and skipped; if no drift is found
+/// elsewhere, `Error::VerifyIncomplete` is returned.
+pub async fn verify(config: Config, mut output: Output) -> Result<()> {
+    if config.servers.is_empty() {
+        return Err(Error::NoServers);
+    }
+
+    output.start_timer();
+
+    output.progress(&format!(
+        "Verifying {} on {} server(s)",
+        config.service,
+        config.servers.len()
+    ));
+
+    let mut drifted = 0;
+    let mut unverified = 0;
+    for server in &config.servers {
+        let output = output.for_server(&server.host);
+        let mut diag = Diagnostics::default();
+        let result = verify_on_server(&config, server, &output, &mut diag).await;
+        for warning in diag.warnings() {
+            output.warning(&warning.message);
+        }
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                unverified += 1;
+                output.error(&format!("verification failed: {}", e));
+                continue;
+            }
+        };
+        if report.is_clean() {
+            output.progress(&format!(
+                "  ✓ {} container(s) match config",
+                report.containers.len()
+            ));
+            continue;
+        }
+
+        drifted += 1;
+        for (container, drift) in &report.drifts {
+            match container {
+                Some(name) => output.error(&format!("{}: {}", name, drift)),
+                None => output.error(&drift.to_string()),
+            }
+        }
+    }
+
+    // Drift is the stronger signal; report it even if some servers were unreachable
+    if drifted > 0 {
+        return Err(Error::DriftDetected(drifted));
+    }
+    if unverified > 0 {
+        return Err(Error::VerifyIncomplete(unverified));
+    }
+
+    let total = config.servers.len();
+    output.finish(
+        "No drift detected",
+        &format!("verified {} on {}/{} servers", config.service, total, total),
+    );
+    Ok(())
+}
+
+/// Verify a single server.
+async fn verify_on_server(
+    config: &Config,
+    server: &ServerConfig,
+    output: &Output,
+    diag: &mut Diagnostics,
+) -> Result<VerifyReport> {
+    output.progress(&format!("  → Connecting to {}...", server.host));
+
+    let session = Session::connect(server.ssh_session_config()).await?;
+    let runtime = connect_to_runtime(&session, server, output).await?;
+
+    let result = verify_service(&runtime, config).await;
+
+    // Disconnect SSH session (non-fatal if it fails)
+    if let Err(e) = session.disconnect().await {
+        diag.warn(Warning::ssh_disconnect(format!(
+            "SSH disconnect failed for {}: {}",
+            server.host, e
+        )));
+    }
+
+    result.map_err(Error::from)
+}
@@ -0,0 +1,39 @@
+// ABOUTME: Version command: prints the binary version and, with --check,
+// ABOUTME: compares it against the latest release and the config's minimum.
+
+use peleka::config::Config;
+use peleka::error::Result;
+use peleka::output::Output;
+use peleka::version::{self, RELEASES_URL, VERSION};
+use std::path::Path;
+
+/// Print the binary version, optionally checking it is current and new
+/// enough for the config in `dir`.
+///
+/// Problems found by the check are warnings; the command only fails on
+/// errors unrelated to the check itself.
+pub async fn version(check: bool, dir: &Path, output: Output) -> Result<()> {
+    if check {
+        match version::latest_release().await {
+            Ok(latest) if version::is_newer(&latest, VERSION) => output.warning(&format!(
+                "peleka {} is available ({})",
+                latest, RELEASES_URL
+            )),
+            Ok(_) => output.progress("peleka is up to date"),
+            Err(e) => output.warning(&format!("Could not check for the latest release: {}", e)),
+        }
+
+        // Only a readable config can declare a minimum version
+        if let Ok(config) = Config::discover(dir)
+            && let Some(required) = config.requires_newer_peleka(VERSION)
+        {
+            output.warning(&format!(
+                "This config requires peleka {} or newer (min_peleka_version), but this is {}",
+                required, VERSION
+            ));
+        }
+    }
+
+    output.finish(&format!("peleka {}", VERSION), VERSION);
+    Ok(())
+}
@@ -0,0 +1,169 @@
+// ABOUTME: Watch command implementation for basic self-healing.
+// ABOUTME: Polls container health and restarts unhealthy containers when enabled.
+
+use super::runtime_connection::connect_to_runtime;
+use peleka::config::{Config, HealthConfig, ServerConfig};
+use peleka::deploy::{HealthTracker, Release, RestartLimiter, find_release, restart_container};
+use peleka::error::{Error, Result};
+use peleka::hooks::{HookContext, HookPoint, HookRunner};
+use peleka::output::Output;
+use peleka::runtime::{BollardRuntime, ContainerOps, HealthState};
+use peleka::ssh::Session;
+use peleka::types::ContainerId;
+use std::env;
+use std::time::Instant;
+
+/// Per-server supervision state.
+struct Watched<'a> {
+    server: &'a ServerConfig,
+    session: Session,
+    runtime: BollardRuntime,
+    container: Option<ContainerId>,
+    tracker: HealthTracker,
+    limiter: RestartLimiter,
+}
+
+/// Watch the service on all servers until interrupted.
+pub async fn watch(config: Config, output: Output) -> Result<()> {
+    if config.servers.is_empty() {
+        return Err(Error::NoServers);
+    }
+
+    let policy = config.health.clone().unwrap_or_default();
+    if !policy.auto_restart {
+        output.warning(
+            "health.auto_restart is not enabled; unhealthy containers will only be reported",
+        );
+    }
+
+    let cwd = env::current_dir()?;
+    let hook_runner = HookRunner::new(&cwd);
+
+    let mut watched = Vec::new();
+    for server in &config.servers {
+        output.progress(&format!("  → Connecting to {}...", server.host));
+        let session = Session::connect(server.ssh_session_config()).await?;
+        let runtime = connect_to_runtime(&session, server, &output).await?;
+        watched.push(Watched {
+            server,
+            session,
+            runtime,
+            container: None,
+            tracker: HealthTracker::default(),
+            limiter: RestartLimiter::new(policy.max_restarts, policy.restart_window),
+        });
+    }
+
+    output.progress(&format!(
+        "Watching {} on {} server(s) every {}s (Ctrl-C to stop)",
+        config.service,
+        watched.len(),
+        policy.poll_interval.as_secs()
+    ));
+
+    let mut ticker = tokio::time::interval(policy.poll_interval);
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = ticker.tick() => {
+                for w in &mut watched {
+                    poll(&config, &policy, w, &hook_runner, &output).await;
+                }
+            }
+        }
+    }
+
+    for w in watched {
+        if let Err(e) = w.session.disconnect().await {
+            output.warning(&format!(
+                "SSH disconnect failed for {}: {}",
+                w.server.host, e
+            ));
+        }
+    }
+
+    output.finish(
+        "Stopped watching",
+        &format!("stopped watching {}", config.service),
+    );
+    Ok(())
+}
+
+/// Check one server and react to a transition into unhealthy.
+async fn poll(
+    config: &Config,
+    policy: &HealthConfig,
+    w: &mut Watched<'_>,
+    hook_runner: &HookRunner,
+    output: &Output,
+) {
+    let host = &w.server.host;
+
+    let container = match find_release(&w.runtime, &config.service, Release::Active).await {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            w.container = None;
+            w.tracker.reset();
+            return;
+        }
+        Err(e) => {
+            output.warning(&format!("[{}] health poll failed: {}", host, e));
+            return;
+        }
+    };
+
+    // A new deployment replaced the container; start tracking afresh
+    if w.container.as_ref() != Some(&container.id) {
+        w.container = Some(container.id.clone());
+        w.tracker.reset();
+    }
+
+    let health = match w.runtime.inspect_container(&container.id).await {
+        Ok(info) => info.health.unwrap_or(HealthState::None),
+        Err(e) => {
+            output.warning(&format!("[{}] health poll failed: {}", host, e));
+            return;
+        }
+    };
+
+    if !w.tracker.observe(health) {
+        return;
+    }
+
+    output.warning(&format!("[{}] {} is unhealthy", host, container.name));
+
+    let hook_context =
+        HookContext::new(config, w.server).with_forwarded_socket(w.session.forwarded_socket());
+    if let Some(result) = hook_runner.run(HookPoint::OnUnhealthy, &hook_context).await
+        && !result.success
+    {
+        output.warning("on-unhealthy hook failed");
+    }
+
+    if !policy.auto_restart {
+        return;
+    }
+
+    if !w.limiter.try_acquire(Instant::now()) {
+        output.error(&format!(
+            "[{}] restart limit reached ({} in {}s); not restarting {}",
+            host,
+            policy.max_restarts,
+            policy.restart_window.as_secs(),
+            container.name
+        ));
+        return;
+    }
+
+    output.progress(&format!("  → [{}] Restarting {}...", host, container.name));
+    match restart_container(&w.runtime, &container.id, config.stop_timeout()).await {
+        Ok(()) => {
+            w.tracker.reset();
+            output.progress(&format!("  ✓ [{}] Restarted {}", host, container.name));
+        }
+        Err(e) => output.error(&format!(
+            "[{}] failed to restart {}: {}",
+            host, container.name, e
+        )),
+    }
+}
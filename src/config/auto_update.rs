@@ -0,0 +1,135 @@
+// ABOUTME: Podman auto-update integration for containers deployed by peleka.
+// ABOUTME: Stamps the io.containers.autoupdate label and hands image refreshes to Podman.
+
+use serde::Deserialize;
+
+use super::{Config, PullPolicy};
+
+/// Label `podman auto-update` reads to decide how to refresh a container.
+pub const AUTO_UPDATE_LABEL: &str = "io.containers.autoupdate";
+
+/// Label naming the systemd unit `podman auto-update` restarts after a
+/// refresh. Containers without it are skipped by Podman.
+pub const SYSTEMD_UNIT_LABEL: &str = "PODMAN_SYSTEMD_UNIT";
+
+/// How `podman auto-update` refreshes the container's image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoUpdatePolicy {
+    /// Pull when the registry has a newer image for the same reference.
+    Registry,
+    /// Restart when a newer image was built or pulled on the host.
+    Local,
+}
+
+impl AutoUpdatePolicy {
+    /// Label value understood by Podman.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoUpdatePolicy::Registry => "registry",
+            AutoUpdatePolicy::Local => "local",
+        }
+    }
+}
+
+impl Config {
+    /// Auto-update policy that takes effect.
+    ///
+    /// `podman auto-update` only refreshes containers run by a systemd unit,
+    /// so the policy is ignored unless `labels` names that unit.
+    pub fn auto_update_policy(&self) -> Option<AutoUpdatePolicy> {
+        self.podman_auto_update.filter(|_| {
+            self.labels
+                .get(SYSTEMD_UNIT_LABEL)
+                .is_some_and(|unit| !unit.trim().is_empty())
+        })
+    }
+
+    /// Pull policy actually used by `deploy`.
+    ///
+    /// With auto-update in effect, Podman owns image refreshes, so peleka
+    /// only pulls an image that is missing on the host.
+    pub fn effective_pull_policy(&self) -> PullPolicy {
+        match self.auto_update_policy() {
+            Some(_) => PullPolicy::IfNotPresent,
+            None => self.pull_policy,
+        }
+    }
+
+    /// Problems with the auto-update setup that Podman would hit later.
+    pub fn auto_update_warnings(&self) -> Vec<String> {
+        let Some(policy) = self.podman_auto_update else {
+            return Vec::new();
+        };
+        if self.auto_update_policy().is_none() {
+            return vec![format!(
+                "podman_auto_update is ignored: podman auto-update only refreshes containers run by a systemd unit; set labels.{} to that unit",
+                SYSTEMD_UNIT_LABEL
+            )];
+        }
+
+        let mut warnings = Vec::new();
+        if policy == AutoUpdatePolicy::Registry && self.image.registry().is_none() {
+            warnings.push(format!(
+                "podman_auto_update: registry needs a fully qualified image (e.g. docker.io/{}), podman auto-update skips short names",
+                self.image
+            ));
+        }
+        if self.pull_policy != PullPolicy::default() {
+            warnings.push(
+                "pull_policy is ignored with podman_auto_update; images are only pulled when missing"
+                    .to_string(),
+            );
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(extra: &str) -> Config {
+        Config::from_yaml(&format!(
+            "service: app\nimage: nginx:1.27\nservers:\n  - host: example.com\n{}",
+            extra
+        ))
+        .unwrap()
+    }
+
+    const UNIT: &str = "labels:\n  PODMAN_SYSTEMD_UNIT: app.service\n";
+
+    #[test]
+    fn auto_update_only_pulls_missing_images() {
+        assert_eq!(config("").effective_pull_policy(), PullPolicy::Always);
+        assert_eq!(
+            config(&format!("podman_auto_update: local\n{}", UNIT)).effective_pull_policy(),
+            PullPolicy::IfNotPresent
+        );
+    }
+
+    #[test]
+    fn auto_update_without_systemd_unit_is_ignored() {
+        let config = config("podman_auto_update: local\n");
+        assert_eq!(config.auto_update_policy(), None);
+        assert_eq!(config.effective_pull_policy(), PullPolicy::Always);
+
+        let warnings = config.auto_update_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(SYSTEMD_UNIT_LABEL), "{}", warnings[0]);
+    }
+
+    #[test]
+    fn registry_policy_warns_on_short_image_name() {
+        let warnings =
+            config(&format!("podman_auto_update: registry\n{}", UNIT)).auto_update_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("fully qualified"), "{}", warnings[0]);
+
+        assert!(
+            config(&format!("podman_auto_update: local\n{}", UNIT))
+                .auto_update_warnings()
+                .is_empty()
+        );
+    }
+}
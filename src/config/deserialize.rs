@@ -1,10 +1,11 @@
 // ABOUTME: Custom serde deserializers for config types.
-// ABOUTME: Handles service names, image refs, and server lists.
+// ABOUTME: Handles service names, image refs, durations, healthchecks, and server lists.
 
 use nonempty::NonEmpty;
 use serde::Deserialize;
+use std::time::Duration;
 
-use super::ServerConfig;
+use super::{HealthcheckConfig, ServerConfig};
 use crate::types::{ImageRef, ServiceName};
 
 pub fn deserialize_service_name<'de, D>(deserializer: D) -> Result<ServiceName, D::Error>
@@ -23,6 +24,37 @@ where
     ImageRef::parse(&s).map_err(serde::de::Error::custom)
 }
 
+pub fn deserialize_nonzero_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let duration = humantime_serde::deserialize(deserializer)?;
+    if duration == Duration::ZERO {
+        return Err(serde::de::Error::custom(
+            "duration must be greater than zero",
+        ));
+    }
+    Ok(duration)
+}
+
+pub fn deserialize_healthcheck_option<'de, D>(
+    deserializer: D,
+) -> Result<Option<HealthcheckConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let healthcheck: Option<HealthcheckConfig> = Option::deserialize(deserializer)?;
+    if let Some(hc) = &healthcheck
+        && hc.cmd.trim().is_empty()
+        && hc.external.is_none()
+    {
+        return Err(serde::de::Error::custom(
+            "healthcheck needs either `cmd` or `external`",
+        ));
+    }
+    Ok(healthcheck)
+}
+
 pub fn deserialize_servers<'de, D>(deserializer: D) -> Result<NonEmpty<ServerConfig>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -0,0 +1,67 @@
+// ABOUTME: Service discovery provider configuration.
+// ABOUTME: Defines Consul, webhook, and Tailscale registration targets.
+
+use serde::Deserialize;
+
+/// External service discovery to update after cutover.
+///
+/// Each provider runs on the target server, so it can reach node-local
+/// agents (Consul, tailscaled) without extra tunnels.
+///
+/// # Examples
+///
+/// ```yaml
+/// discovery:
+///   - type: consul
+///     tags: [web]
+///   - type: webhook
+///     url: https://dns.internal/api/records
+///   - type: tailscale
+///     tags: ["tag:web"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum DiscoveryConfig {
+    /// Register the container with the local Consul agent.
+    Consul {
+        /// Consul agent HTTP address, as seen from the server.
+        #[serde(default = "default_consul_address")]
+        address: String,
+        /// Port to register (default: first container port in `ports`).
+        #[serde(default)]
+        port: Option<u16>,
+        /// Consul service tags.
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+    /// Send the container address to an HTTP endpoint as JSON.
+    Webhook {
+        url: String,
+        #[serde(default = "default_webhook_method")]
+        method: String,
+        /// Port to report (default: first container port in `ports`).
+        #[serde(default)]
+        port: Option<u16>,
+    },
+    /// Advertise Tailscale ACL tags on the server.
+    Tailscale { tags: Vec<String> },
+}
+
+impl DiscoveryConfig {
+    /// Short provider name for progress output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DiscoveryConfig::Consul { .. } => "consul",
+            DiscoveryConfig::Webhook { .. } => "webhook",
+            DiscoveryConfig::Tailscale { .. } => "tailscale",
+        }
+    }
+}
+
+fn default_consul_address() -> String {
+    "http://127.0.0.1:8500".to_string()
+}
+
+fn default_webhook_method() -> String {
+    "POST".to_string()
+}
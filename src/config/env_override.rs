@@ -0,0 +1,171 @@
+// ABOUTME: Deploy-time env overrides from `--env KEY=VALUE` and `--env-file`.
+// ABOUTME: Overlays the config's env map and summarizes which names were overridden.
+
+use super::{Config, EnvValue};
+use crate::error::{Error, Result};
+use std::path::Path;
+
+/// Label recording which env vars a deploy overrode, e.g. `API_TOKEN,FLAG`.
+pub const ENV_OVERRIDES_LABEL: &str = "peleka.env-overrides";
+
+/// Parse a `KEY=VALUE` assignment. The value may be empty or contain `=`.
+pub fn parse_env_assignment(s: &str) -> Result<(String, String)> {
+    split_assignment(s)
+        .ok_or_else(|| Error::InvalidConfig(format!("env override '{}' is not KEY=VALUE", s)))
+}
+
+/// Read `KEY=VALUE` lines from a dotenv-style file.
+///
+/// Blank lines and `#` comments are skipped, a leading `export ` is allowed,
+/// and one pair of matching quotes around the value is removed.
+pub fn read_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+    let mut vars = Vec::new();
+    for (n, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = split_assignment(line).ok_or_else(|| {
+            Error::InvalidConfig(format!("{}:{}: expected KEY=VALUE", path.display(), n + 1))
+        })?;
+        vars.push((key, unquote(value.trim()).to_string()));
+    }
+    Ok(vars)
+}
+
+fn split_assignment(s: &str) -> Option<(String, String)> {
+    let (key, value) = s.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() || key.chars().any(char::is_whitespace) {
+        return None;
+    }
+    Some((key.to_string(), value.to_string()))
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+impl Config {
+    /// Overlay `overrides` onto `env` for this invocation.
+    ///
+    /// Later entries win, so apply `--env-file` values before `--env` flags.
+    /// The overridden names are remembered for [`Config::env_override_summary`].
+    pub fn with_env_overrides(mut self, overrides: Vec<(String, String)>) -> Self {
+        for (key, value) in overrides {
+            if !self.env_overrides.contains(&key) {
+                self.env_overrides.push(key.clone());
+            }
+            self.env.insert(key, EnvValue::Literal(value));
+        }
+        self
+    }
+
+    /// Overridden var names as `KEY,...` in name order. `None` without
+    /// overrides.
+    ///
+    /// Values are never included: labels and logs are readable by anyone
+    /// with runtime access, and no name-based rule reliably spots secrets.
+    pub fn env_override_summary(&self) -> Option<String> {
+        if self.env_overrides.is_empty() {
+            return None;
+        }
+
+        let mut keys: Vec<&str> = self.env_overrides.iter().map(String::as_str).collect();
+        keys.sort();
+        Some(keys.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config::from_yaml(
+            "service: app\nimage: nginx:1.27\nservers:\n  - host: example.com\nenv:\n  FLAG: old\n  KEEP: kept\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn assignment_keeps_equals_in_value() {
+        assert_eq!(
+            parse_env_assignment("URL=postgres://db?a=b").unwrap(),
+            ("URL".to_string(), "postgres://db?a=b".to_string())
+        );
+        assert_eq!(
+            parse_env_assignment("EMPTY=").unwrap(),
+            ("EMPTY".to_string(), String::new())
+        );
+        assert!(parse_env_assignment("NOVALUE").is_err());
+        assert!(parse_env_assignment("=value").is_err());
+    }
+
+    #[test]
+    fn env_file_skips_comments_and_strips_quotes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("override.env");
+        std::fs::write(
+            &path,
+            "# flags\n\nFLAG=on\nexport NAME=\"two words\"\nQUOTED='x'\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_env_file(&path).unwrap(),
+            vec![
+                ("FLAG".to_string(), "on".to_string()),
+                ("NAME".to_string(), "two words".to_string()),
+                ("QUOTED".to_string(), "x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn env_file_reports_line_of_bad_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.env");
+        std::fs::write(&path, "FLAG=on\noops\n").unwrap();
+
+        let err = read_env_file(&path).unwrap_err().to_string();
+        assert!(err.contains("bad.env:2"), "{}", err);
+    }
+
+    #[test]
+    fn overrides_replace_config_env() {
+        let config = config().with_env_overrides(vec![
+            ("FLAG".to_string(), "on".to_string()),
+            ("NEW".to_string(), "1".to_string()),
+        ]);
+
+        assert_eq!(config.env["FLAG"], EnvValue::Literal("on".to_string()));
+        assert_eq!(config.env["NEW"], EnvValue::Literal("1".to_string()));
+        assert_eq!(config.env["KEEP"], EnvValue::Literal("kept".to_string()));
+    }
+
+    #[test]
+    fn summary_lists_names_without_values() {
+        let config = config().with_env_overrides(vec![
+            ("FLAG".to_string(), "on".to_string()),
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://u:hunter2@db".to_string(),
+            ),
+        ]);
+
+        assert_eq!(config.env_override_summary().unwrap(), "DATABASE_URL,FLAG");
+        assert!(self::config().env_override_summary().is_none());
+    }
+}
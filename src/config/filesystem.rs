@@ -0,0 +1,203 @@
+// ABOUTME: Read-only root filesystem support: tmpfs parsing and preflight write checks.
+// ABOUTME: Warns when the health check or common paths would write outside a mount.
+
+use std::collections::HashMap;
+
+use super::Config;
+
+/// Paths most processes write to at some point.
+const COMMON_WRITE_PATHS: &[&str] = &["/tmp"];
+
+impl Config {
+    /// tmpfs mounts as path -> mount options (empty when none given).
+    ///
+    /// Entries are `path` or `path:options`, e.g. `/run:size=16m,mode=1777`.
+    pub fn tmpfs_mounts(&self) -> HashMap<String, String> {
+        self.tmpfs
+            .iter()
+            .map(|spec| match spec.split_once(':') {
+                Some((path, options)) => (path.to_string(), options.to_string()),
+                None => (spec.clone(), String::new()),
+            })
+            .collect()
+    }
+
+    /// Preflight warnings for `read_only: true`.
+    ///
+    /// Returns one message per path the health check (or a commonly written
+    /// path like `/tmp`) would write to without a tmpfs or writable volume
+    /// covering it. Empty when the root filesystem is writable.
+    pub fn read_only_warnings(&self) -> Vec<String> {
+        if !self.read_only {
+            return Vec::new();
+        }
+
+        let writable = self.writable_paths();
+        let mut warnings = Vec::new();
+
+        for path in COMMON_WRITE_PATHS {
+            if !is_covered(path, &writable) {
+                warnings.push(format!(
+                    "read_only is set but {} is not a tmpfs or volume; add it to tmpfs if the app writes there",
+                    path
+                ));
+            }
+        }
+
+        if let Some(hc) = &self.healthcheck {
+            for path in write_targets(&hc.cmd) {
+                if !is_covered(&path, &writable) {
+                    warnings.push(format!(
+                        "read_only is set but the health check writes to {}, which is not covered by tmpfs or a volume",
+                        path
+                    ));
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Mount targets the container can write to.
+    fn writable_paths(&self) -> Vec<String> {
+        let volumes = self.volumes.iter().filter_map(|spec| {
+            let parts: Vec<&str> = spec.split(':').collect();
+            match parts.as_slice() {
+                [_, target] => Some(target.to_string()),
+                [_, target, mode] if *mode != "ro" => Some(target.to_string()),
+                _ => None,
+            }
+        });
+        self.tmpfs_mounts().into_keys().chain(volumes).collect()
+    }
+}
+
+/// Whether `path` is at or below one of the writable mount targets.
+fn is_covered(path: &str, writable: &[String]) -> bool {
+    writable.iter().any(|mount| {
+        let mount = mount.trim_end_matches('/');
+        path == mount
+            || path
+                .strip_prefix(mount)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Absolute paths a shell command writes to via redirection or
+/// `touch`/`mkdir`/`tee`. Best effort; only catches the common forms.
+fn write_targets(cmd: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut writer = false;
+
+    for token in cmd.split_whitespace() {
+        let token = token.trim_matches(|c| c == ';' || c == '&' || c == '|');
+        let redirect = token.trim_start_matches(['1', '2']).trim_start_matches('>');
+
+        if token.contains('>') && redirect.starts_with('/') {
+            targets.push(redirect.to_string());
+            writer = false;
+        } else if token.ends_with('>') || matches!(token, "touch" | "mkdir" | "tee") {
+            writer = true;
+        } else if writer && token.starts_with('/') {
+            targets.push(token.to_string());
+        } else if !token.starts_with('-') {
+            writer = false;
+        }
+    }
+
+    targets.retain(|t| t != "/dev/null" && !t.starts_with("/dev/std"));
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HealthcheckConfig;
+
+    fn read_only_config() -> Config {
+        let mut config = Config::template();
+        config.read_only = true;
+        config
+    }
+
+    fn healthcheck(cmd: &str) -> HealthcheckConfig {
+        serde_yaml::from_str(&format!("cmd: {:?}", cmd)).unwrap()
+    }
+
+    #[test]
+    fn tmpfs_mounts_parse_options() {
+        let mut config = Config::template();
+        config.tmpfs = vec!["/tmp".to_string(), "/run:size=16m".to_string()];
+
+        let mounts = config.tmpfs_mounts();
+        assert_eq!(mounts.get("/tmp").map(String::as_str), Some(""));
+        assert_eq!(mounts.get("/run").map(String::as_str), Some("size=16m"));
+    }
+
+    #[test]
+    fn no_warnings_when_writable() {
+        let config = Config::template();
+        assert!(config.read_only_warnings().is_empty());
+    }
+
+    #[test]
+    fn warns_when_tmp_not_mounted() {
+        let config = read_only_config();
+        let warnings = config.read_only_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("/tmp"));
+    }
+
+    #[test]
+    fn tmpfs_covers_tmp() {
+        let mut config = read_only_config();
+        config.tmpfs = vec!["/tmp".to_string()];
+        assert!(config.read_only_warnings().is_empty());
+    }
+
+    #[test]
+    fn warns_on_healthcheck_write_outside_mounts() {
+        let mut config = read_only_config();
+        config.tmpfs = vec!["/tmp".to_string()];
+        config.healthcheck = Some(healthcheck(
+            "curl -sf localhost/health > /var/lib/app/last-health",
+        ));
+
+        let warnings = config.read_only_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("/var/lib/app/last-health"));
+    }
+
+    #[test]
+    fn writable_volume_covers_healthcheck_write() {
+        let mut config = read_only_config();
+        config.tmpfs = vec!["/tmp".to_string()];
+        config.volumes = vec!["/srv/data:/data".to_string()];
+        config.healthcheck = Some(healthcheck("touch /data/alive"));
+        assert!(config.read_only_warnings().is_empty());
+    }
+
+    #[test]
+    fn read_only_volume_does_not_cover_write() {
+        let mut config = read_only_config();
+        config.tmpfs = vec!["/tmp".to_string()];
+        config.volumes = vec!["/srv/data:/data:ro".to_string()];
+        config.healthcheck = Some(healthcheck("touch /data/alive"));
+        assert_eq!(config.read_only_warnings().len(), 1);
+    }
+
+    #[test]
+    fn write_targets_ignores_dev_null() {
+        assert!(write_targets("curl -f localhost > /dev/null 2>&1").is_empty());
+        assert_eq!(write_targets("date >>/tmp/hc.log"), vec!["/tmp/hc.log"]);
+        assert_eq!(write_targets("mkdir -p /var/run/app"), vec!["/var/run/app"]);
+    }
+
+    #[test]
+    fn is_covered_requires_path_boundary() {
+        let writable = vec!["/tmp".to_string()];
+        assert!(is_covered("/tmp", &writable));
+        assert!(is_covered("/tmp/x", &writable));
+        assert!(!is_covered("/tmpfoo", &writable));
+    }
+}
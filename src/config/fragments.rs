@@ -0,0 +1,124 @@
+// ABOUTME: Merging of `.peleka/config.d/*.yml` fragments onto the base config.
+// ABOUTME: Mappings merge key by key; every other value in a fragment replaces the base.
+
+use crate::error::{Error, Result};
+use serde_yaml::Value;
+use std::path::{Path, PathBuf};
+
+/// YAML fragment files in `dir`, in lexical order.
+///
+/// A missing directory has no fragments. Files not ending in `.yml` or
+/// `.yaml` (editor backups, READMEs) are ignored.
+pub(crate) fn fragment_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml");
+        if is_yaml && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Parse `base` and merge each fragment onto it in order.
+pub(crate) fn load_merged(base: &Path, fragments: &[PathBuf]) -> Result<Value> {
+    let mut merged: Value = serde_yaml::from_str(&std::fs::read_to_string(base)?)?;
+    for path in fragments {
+        let fragment: Value = serde_yaml::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| Error::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        merge(&mut merged, fragment);
+    }
+    Ok(merged)
+}
+
+/// Merge `overlay` onto `base`.
+///
+/// Mappings are merged recursively, so a fragment can set `image` or add one
+/// `env` entry without repeating the rest. Sequences and scalars replace the
+/// base value whole (a fragment listing `servers` replaces all servers). An
+/// empty fragment changes nothing.
+pub(crate) fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (_, Value::Null) => {}
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(s: &str) -> Value {
+        serde_yaml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn mappings_merge_and_scalars_replace() {
+        let mut base = yaml("image: app:v1\nenv:\n  A: one\n  B: two\n");
+        merge(
+            &mut base,
+            yaml("image: app:v2\nenv:\n  B: three\n  C: four\n"),
+        );
+        assert_eq!(
+            base,
+            yaml("image: app:v2\nenv:\n  A: one\n  B: three\n  C: four\n")
+        );
+    }
+
+    #[test]
+    fn sequences_replace_whole() {
+        let mut base = yaml("ports: [\"80:80\", \"443:443\"]\n");
+        merge(&mut base, yaml("ports: [\"8080:80\"]\n"));
+        assert_eq!(base, yaml("ports: [\"8080:80\"]\n"));
+    }
+
+    #[test]
+    fn empty_fragment_changes_nothing() {
+        let mut base = yaml("image: app:v1\n");
+        merge(&mut base, yaml(""));
+        assert_eq!(base, yaml("image: app:v1\n"));
+    }
+
+    #[test]
+    fn fragments_are_listed_in_lexical_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["20-image.yml", "10-env.yaml", "README.md", "05-ci.yml~"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let names: Vec<_> = fragment_paths(dir.path())
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["10-env.yaml", "20-image.yml"]);
+    }
+
+    #[test]
+    fn missing_directory_has_no_fragments() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            fragment_paths(&dir.path().join("config.d"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
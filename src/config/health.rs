@@ -0,0 +1,61 @@
+// ABOUTME: Health supervision policy used by `peleka watch`.
+// ABOUTME: Controls restart-on-unhealthy and its rate limiting.
+
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Health supervision configuration.
+///
+/// # Examples
+///
+/// ```yaml
+/// health:
+///   auto_restart: true
+///   max_restarts: 3
+///   restart_window: 10m
+///   poll_interval: 10s
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthConfig {
+    /// Restart the container when it turns unhealthy (only under `peleka watch`).
+    #[serde(default)]
+    pub auto_restart: bool,
+
+    /// Maximum restarts allowed within `restart_window`.
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
+
+    /// Sliding window for `max_restarts`.
+    #[serde(default = "default_restart_window", with = "humantime_serde")]
+    pub restart_window: Duration,
+
+    /// How often `peleka watch` polls container health. Must be non-zero.
+    #[serde(
+        default = "default_poll_interval",
+        deserialize_with = "super::deserialize::deserialize_nonzero_duration"
+    )]
+    pub poll_interval: Duration,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            auto_restart: false,
+            max_restarts: default_max_restarts(),
+            restart_window: default_restart_window(),
+            poll_interval: default_poll_interval(),
+        }
+    }
+}
+
+fn default_max_restarts() -> u32 {
+    3
+}
+
+fn default_restart_window() -> Duration {
+    Duration::from_secs(600)
+}
+
+fn default_poll_interval() -> Duration {
+    Duration::from_secs(10)
+}
@@ -4,6 +4,9 @@
 use serde::Deserialize;
 use std::time::Duration;
 
+use super::TemplateVars;
+use crate::types::ImageRef;
+
 /// Health check configuration.
 ///
 /// The `cmd` field is a shell command that runs inside the container.
@@ -19,6 +22,9 @@ use std::time::Duration;
 ///   retries: 3
 /// ```
 ///
+/// With `warmup_requests: N`, the check must pass N more times, spaced by
+/// `warmup_interval` (default 500ms), after the first healthy response.
+///
 /// Common patterns:
 /// - HTTP with curl: `curl -f http://localhost:3000/health`
 /// - HTTP with wget: `wget -q --spider http://localhost:80/health`
@@ -26,10 +32,27 @@ use std::time::Duration;
 /// - Custom binary: `/app/healthcheck`
 /// - PostgreSQL: `pg_isready -U postgres`
 /// - Redis: `redis-cli ping`
+///
+/// Plain commands like these run directly, so images without a shell
+/// (distroless, scratch) can be checked. Commands using shell syntax
+/// (pipes, `&&`, variables, quotes) run through `sh -c` and need `sh`
+/// in the image.
+///
+/// Images that cannot run any check (e.g. a lone static binary) can be
+/// probed from a separate checker container instead:
+///
+/// ```yaml
+/// healthcheck:
+///   external:
+///     image: curlimages/curl:8.10.1
+///     cmd: "curl -f http://{{host}}:3000/health"
+/// ```
 #[derive(Debug, Clone, Deserialize)]
 pub struct HealthcheckConfig {
     /// Shell command to run inside the container.
     /// Exit code 0 = healthy, non-zero = unhealthy.
+    /// May be omitted when `external` is set.
+    #[serde(default)]
     pub cmd: String,
 
     #[serde(default = "default_interval", with = "humantime_serde")]
@@ -43,6 +66,97 @@ pub struct HealthcheckConfig {
 
     #[serde(default = "default_start_period", with = "humantime_serde")]
     pub start_period: Duration,
+
+    /// Extra checks to pass after the first healthy one before the
+    /// container counts as ready. Warms up JIT-compiled or cache-heavy apps.
+    #[serde(default)]
+    pub warmup_requests: u32,
+
+    /// Delay between warm-up checks.
+    #[serde(default = "default_warmup_interval", with = "humantime_serde")]
+    pub warmup_interval: Duration,
+
+    /// Run the check from a short-lived checker container instead of
+    /// inside the new container.
+    #[serde(default)]
+    pub external: Option<ExternalHealthcheck>,
+}
+
+/// Health check run from a separate container on the new container's network.
+///
+/// `{{host}}` in `cmd` (and `PELEKA_HEALTHCHECK_HOST` in its environment)
+/// is the new container's address on that network.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalHealthcheck {
+    /// Image of the checker container (pulled without registry auth).
+    #[serde(deserialize_with = "super::deserialize::deserialize_image_ref")]
+    pub image: ImageRef,
+
+    /// Command the checker runs. Exit code 0 = healthy.
+    pub cmd: String,
+}
+
+impl ExternalHealthcheck {
+    /// Arguments for the checker with templates expanded: the command
+    /// itself, or `sh -c cmd` if it needs a shell.
+    pub fn exec_args(&self, vars: &TemplateVars) -> Result<Vec<String>, String> {
+        Ok(exec_args(&vars.render(&self.cmd)?))
+    }
+}
+
+/// Characters that only mean something to a shell.
+const SHELL_SYNTAX: &[char] = &[
+    '|', '&', ';', '<', '>', '(', ')', '$', '`', '\\', '"', '\'', '*', '{', '}', '~', '#', '\n',
+];
+
+/// Shell builtins that have no standalone binary to exec.
+const SHELL_BUILTINS: &[&str] = &[
+    "exit", "cd", ".", "source", "export", "set", "unset", "command", "type", "eval", "exec",
+    "read", "return", "shift", "trap", "ulimit", "umask", "wait",
+];
+
+impl HealthcheckConfig {
+    /// The command this check runs, wherever it runs.
+    pub fn command(&self) -> &str {
+        match &self.external {
+            Some(external) => &external.cmd,
+            None => &self.cmd,
+        }
+    }
+
+    /// The command as exec-form arguments, or None if it needs a shell.
+    pub fn exec_form(&self) -> Option<Vec<String>> {
+        exec_form(&self.cmd)
+    }
+
+    /// Arguments to exec for one check: the command itself, or `sh -c cmd`.
+    pub fn exec_args(&self) -> Vec<String> {
+        exec_args(&self.cmd)
+    }
+
+    /// Healthcheck test for the container definition (`CMD` or `CMD-SHELL`).
+    pub fn container_test(&self) -> Vec<String> {
+        match self.exec_form() {
+            Some(words) => std::iter::once("CMD".to_string()).chain(words).collect(),
+            None => vec!["CMD-SHELL".to_string(), self.cmd.clone()],
+        }
+    }
+}
+
+/// Split a command into exec-form arguments, or None if it needs a shell.
+pub(crate) fn exec_form(cmd: &str) -> Option<Vec<String>> {
+    let words: Vec<String> = cmd.split_whitespace().map(str::to_string).collect();
+    let first = words.first()?;
+    if cmd.contains(SHELL_SYNTAX) || first.contains('=') || SHELL_BUILTINS.contains(&first.as_str())
+    {
+        return None;
+    }
+    Some(words)
+}
+
+/// Arguments to exec for a command: the command itself, or `sh -c cmd`.
+pub(crate) fn exec_args(cmd: &str) -> Vec<String> {
+    exec_form(cmd).unwrap_or_else(|| vec!["sh".to_string(), "-c".to_string(), cmd.to_string()])
 }
 
 fn default_interval() -> Duration {
@@ -60,3 +174,59 @@ fn default_retries() -> u32 {
 fn default_start_period() -> Duration {
     Duration::from_secs(30)
 }
+
+fn default_warmup_interval() -> Duration {
+    Duration::from_millis(500)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthcheck(cmd: &str) -> HealthcheckConfig {
+        serde_yaml::from_str(&format!("cmd: {:?}", cmd)).unwrap()
+    }
+
+    #[test]
+    fn plain_command_runs_without_shell() {
+        let hc = healthcheck("curl -f http://localhost:3000/health?full=1");
+        assert_eq!(
+            hc.exec_args(),
+            vec!["curl", "-f", "http://localhost:3000/health?full=1"]
+        );
+        assert_eq!(hc.container_test()[0], "CMD");
+    }
+
+    #[test]
+    fn shell_syntax_uses_sh() {
+        for cmd in [
+            "curl -f localhost || exit 1",
+            "test -f /tmp/ready && echo ok",
+            "wget -q http://localhost:$PORT/health",
+            "FOO=1 /app/check",
+            "exit 0",
+        ] {
+            let hc = healthcheck(cmd);
+            assert!(hc.exec_form().is_none(), "{cmd}");
+            assert_eq!(hc.exec_args(), vec!["sh", "-c", cmd]);
+            assert_eq!(hc.container_test(), vec!["CMD-SHELL", cmd]);
+        }
+    }
+
+    #[test]
+    fn external_command_expands_host_before_splitting() {
+        let hc: HealthcheckConfig = serde_yaml::from_str(
+            "external:\n  image: curlimages/curl\n  cmd: \"curl -f http://{{host}}:3000/health\"",
+        )
+        .unwrap();
+        let external = hc.external.as_ref().unwrap();
+        let mut vars = TemplateVars::default();
+        vars.set("host", "10.89.0.7");
+
+        assert_eq!(
+            external.exec_args(&vars).unwrap(),
+            vec!["curl", "-f", "http://10.89.0.7:3000/health"]
+        );
+        assert_eq!(hc.command(), "curl -f http://{{host}}:3000/health");
+    }
+}
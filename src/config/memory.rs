@@ -0,0 +1,94 @@
+// ABOUTME: Memory limit parsing and low-memory handling for resources config.
+// ABOUTME: Used to size containers and to check a host can run old and new side by side.
+
+use serde::Deserialize;
+
+use super::ResourcesConfig;
+
+/// Headroom kept free on the host when none is configured.
+const DEFAULT_MEMORY_HEADROOM: u64 = 64 * 1024 * 1024;
+
+/// What to do when the host cannot fit the new container next to the old one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LowMemoryAction {
+    /// Fall back to the replace strategy (stop old before starting new).
+    #[default]
+    Replace,
+    /// Abort the deployment before anything is started.
+    Fail,
+}
+
+impl ResourcesConfig {
+    /// Configured memory limit in bytes, if any.
+    pub fn memory_bytes(&self) -> Option<u64> {
+        self.memory.as_deref().and_then(parse_memory_string)
+    }
+
+    /// Memory to keep free on the host during a blue-green overlap.
+    pub fn memory_headroom_bytes(&self) -> u64 {
+        self.memory_headroom
+            .as_deref()
+            .and_then(parse_memory_string)
+            .unwrap_or(DEFAULT_MEMORY_HEADROOM)
+    }
+}
+
+/// Parse a memory string like "512m" or "1g" into bytes.
+pub(crate) fn parse_memory_string(spec: &str) -> Option<u64> {
+    let spec = spec.to_lowercase();
+    let (num_str, multiplier) = if spec.ends_with("g") {
+        (&spec[..spec.len() - 1], 1024 * 1024 * 1024)
+    } else if spec.ends_with("m") {
+        (&spec[..spec.len() - 1], 1024 * 1024)
+    } else if spec.ends_with("k") {
+        (&spec[..spec.len() - 1], 1024)
+    } else {
+        (spec.as_str(), 1)
+    };
+
+    // A size too large for u64 is as invalid as an unparseable one
+    num_str
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(yaml: &str) -> ResourcesConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn parses_memory_units() {
+        assert_eq!(parse_memory_string("512m"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_memory_string("1G"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_memory_string("64k"), Some(64 * 1024));
+        assert_eq!(parse_memory_string("1000"), Some(1000));
+        assert_eq!(parse_memory_string("lots"), None);
+    }
+
+    #[test]
+    fn rejects_memory_that_overflows() {
+        assert_eq!(parse_memory_string("20000000000g"), None);
+        assert_eq!(resources("memory: 20000000000g").memory_bytes(), None);
+    }
+
+    #[test]
+    fn headroom_defaults_when_unset() {
+        let r = resources("memory: 256m");
+        assert_eq!(r.memory_bytes(), Some(256 * 1024 * 1024));
+        assert_eq!(r.memory_headroom_bytes(), DEFAULT_MEMORY_HEADROOM);
+        assert_eq!(r.on_low_memory, LowMemoryAction::Replace);
+    }
+
+    #[test]
+    fn parses_headroom_and_action() {
+        let r = resources("memory: 256m\nmemory_headroom: 128m\non_low_memory: fail");
+        assert_eq!(r.memory_headroom_bytes(), 128 * 1024 * 1024);
+        assert_eq!(r.on_low_memory, LowMemoryAction::Fail);
+    }
+}
@@ -1,32 +1,48 @@
 // ABOUTME: Configuration types and parsing for peleka.yml.
 // ABOUTME: Handles YAML parsing, env var interpolation, and destination merging.
 
+mod auto_update;
 mod deserialize;
+mod discovery;
+mod env_override;
 mod env_value;
+mod filesystem;
+mod fragments;
+mod health;
 mod healthcheck;
 mod init;
+mod memory;
 mod restart_policy;
 mod server;
 mod stop;
+mod template;
 
+pub use auto_update::{AUTO_UPDATE_LABEL, AutoUpdatePolicy, SYSTEMD_UNIT_LABEL};
+pub use discovery::DiscoveryConfig;
+pub use env_override::{ENV_OVERRIDES_LABEL, parse_env_assignment, read_env_file};
 pub use env_value::{EnvValue, resolve_env_map};
-pub use healthcheck::HealthcheckConfig;
+pub use health::HealthConfig;
+pub use healthcheck::{ExternalHealthcheck, HealthcheckConfig};
 pub use init::init_config;
+pub use memory::LowMemoryAction;
 pub use restart_policy::RestartPolicy;
-pub use server::ServerConfig;
+pub use server::{ServerConfig, SshRole};
 pub use stop::StopConfig;
+pub use template::TemplateVars;
 
 use crate::error::{Error, Result};
 use crate::types::{ImageRef, ServiceName};
 use nonempty::NonEmpty;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 pub const CONFIG_FILENAME: &str = "peleka.yml";
 pub const CONFIG_FILENAME_ALT: &str = "peleka.yaml";
 pub const CONFIG_FILENAME_DIR: &str = ".peleka/config.yml";
+/// Directory of YAML fragments merged onto the base config in lexical order.
+pub const CONFIG_FRAGMENT_DIR: &str = ".peleka/config.d";
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -39,6 +55,10 @@ pub struct Config {
     #[serde(deserialize_with = "deserialize::deserialize_servers")]
     pub servers: NonEmpty<ServerConfig>,
 
+    /// Oldest peleka release this config is written for.
+    #[serde(default)]
+    pub min_peleka_version: Option<String>,
+
     #[serde(default)]
     pub ports: Vec<String>,
 
@@ -51,24 +71,58 @@ pub struct Config {
     #[serde(default)]
     pub labels: HashMap<String, String>,
 
+    /// Command to run. When `args` is also set, this replaces the image
+    /// ENTRYPOINT; otherwise it replaces the image CMD.
+    /// Supports `{{service}}`, `{{image}}` and `{{version}}` placeholders.
     #[serde(default)]
     pub command: Option<Vec<String>>,
 
+    /// Arguments passed to the entrypoint (replaces the image CMD).
+    /// Supports the same placeholders as `command`.
     #[serde(default)]
+    pub args: Option<Vec<String>>,
+
+    #[serde(
+        default,
+        deserialize_with = "deserialize::deserialize_healthcheck_option"
+    )]
     pub healthcheck: Option<HealthcheckConfig>,
 
     #[serde(default = "default_health_timeout", with = "humantime_serde")]
     pub health_timeout: Duration,
 
+    /// Health supervision for `peleka watch`.
+    #[serde(default)]
+    pub health: Option<HealthConfig>,
+
     #[serde(default, with = "humantime_serde::option")]
     pub image_pull_timeout: Option<Duration>,
 
     #[serde(default)]
     pub pull_policy: PullPolicy,
 
+    /// Let `podman auto-update` refresh the image: containers get the
+    /// `io.containers.autoupdate` label and peleka only pulls missing images.
+    /// Needs Podman and a `PODMAN_SYSTEMD_UNIT` label; ignored otherwise.
+    #[serde(default)]
+    pub podman_auto_update: Option<AutoUpdatePolicy>,
+
+    /// Local image tarball (`docker save`/OCI archive) to load instead of
+    /// pulling. Streamed to each server; the registry is never contacted.
+    #[serde(default)]
+    pub image_archive: Option<PathBuf>,
+
     #[serde(default)]
     pub resources: Option<ResourcesConfig>,
 
+    /// Mount the container's root filesystem read-only.
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// tmpfs mounts (`path` or `path:options`), typically paired with `read_only`.
+    #[serde(default)]
+    pub tmpfs: Vec<String>,
+
     #[serde(default)]
     pub network: Option<NetworkConfig>,
 
@@ -88,8 +142,16 @@ pub struct Config {
     #[serde(default)]
     pub strategy: Option<StrategyConfig>,
 
+    /// Service discovery providers to update after cutover.
+    #[serde(default)]
+    pub discovery: Vec<DiscoveryConfig>,
+
     #[serde(default)]
     pub destinations: HashMap<String, Destination>,
+
+    /// Env var names overridden for this invocation with `--env`/`--env-file`.
+    #[serde(skip)]
+    pub env_overrides: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -109,7 +171,10 @@ pub struct Destination {
     #[serde(default)]
     pub volumes: Option<Vec<String>>,
 
-    #[serde(default)]
+    #[serde(
+        default,
+        deserialize_with = "deserialize::deserialize_healthcheck_option"
+    )]
     pub healthcheck: Option<HealthcheckConfig>,
 }
 
@@ -117,19 +182,29 @@ pub struct Destination {
 pub struct ResourcesConfig {
     pub memory: Option<String>,
     pub cpus: Option<String>,
+    /// Memory to keep free when old and new containers overlap (default 64m).
+    #[serde(default)]
+    pub memory_headroom: Option<String>,
+    /// Action when the host cannot fit both containers during blue-green.
+    #[serde(default)]
+    pub on_low_memory: LowMemoryAction,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct NetworkConfig {
-    #[serde(default = "default_network_name")]
-    pub name: String,
+    /// Network name. Defaults to `peleka-<service>` so unrelated services
+    /// sharing a host don't see each other's aliases.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Use the single shared `peleka` network (pre-isolation behavior).
+    #[serde(default)]
+    pub shared: bool,
     #[serde(default)]
     pub aliases: Vec<String>,
 }
 
-fn default_network_name() -> String {
-    "peleka".to_string()
-}
+/// Name of the network shared by all services in compatibility mode.
+pub const SHARED_NETWORK_NAME: &str = "peleka";
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CleanupConfig {
@@ -165,6 +240,8 @@ pub enum StrategyConfig {
     BlueGreen,
     /// Recreate: stop old first, brief downtime (for stateful single-instance apps).
     Recreate,
+    /// Replace: stop old first but keep it, restarting it if the new one fails.
+    Replace,
 }
 
 /// Image pull policy.
@@ -176,6 +253,10 @@ pub enum PullPolicy {
     Always,
     /// Never pull - use local image only.
     Never,
+    /// Pull only when the image is missing locally.
+    IfNotPresent,
+    /// Pull only when the registry digest differs from the local image.
+    DigestChanged,
 }
 
 impl Config {
@@ -188,6 +269,19 @@ impl Config {
         Self::from_yaml(&content)
     }
 
+    /// Load `path` with `fragments` merged on top, in order.
+    ///
+    /// Mappings merge key by key; sequences and scalars in a fragment
+    /// replace the value before them.
+    pub fn load_with_fragments(path: &Path, fragments: &[PathBuf]) -> Result<Self> {
+        if fragments.is_empty() {
+            return Self::load(path);
+        }
+        let merged = fragments::load_merged(path, fragments)?;
+        serde_yaml::from_value(merged).map_err(Error::from)
+    }
+
+    /// Find the config in `dir` and merge any `.peleka/config.d` fragments.
     pub fn discover(dir: &Path) -> Result<Self> {
         let candidates = [
             dir.join(CONFIG_FILENAME),
@@ -197,8 +291,19 @@ impl Config {
 
         for path in &candidates {
             if path.exists() {
-                let config = Self::load(path)?;
+                let fragments = fragments::fragment_paths(&dir.join(CONFIG_FRAGMENT_DIR))?;
+                for fragment in &fragments {
+                    tracing::debug!("Merging config fragment {}", fragment.display());
+                }
+                let config = Self::load_with_fragments(path, &fragments)?;
                 config.validate_placeholders()?;
+                if let Some(required) = config.requires_newer_peleka(crate::version::VERSION) {
+                    tracing::warn!(
+                        "config requires peleka {} or newer, but this is {}",
+                        required,
+                        crate::version::VERSION
+                    );
+                }
                 return Ok(config);
             }
         }
@@ -284,12 +389,30 @@ impl Config {
     }
 
     /// Get the network name for this deployment.
-    /// Uses configured network name or falls back to "peleka".
-    pub fn network_name(&self) -> &str {
-        self.network
-            .as_ref()
-            .map(|n| n.name.as_str())
-            .unwrap_or("peleka")
+    ///
+    /// Uses the configured network name if set. Otherwise each service gets
+    /// its own `peleka-<service>` network, unless `network.shared` opts back
+    /// into the single shared `peleka` network.
+    pub fn network_name(&self) -> String {
+        match &self.network {
+            Some(NetworkConfig {
+                name: Some(name), ..
+            }) => name.clone(),
+            Some(NetworkConfig { shared: true, .. }) => SHARED_NETWORK_NAME.to_string(),
+            _ => format!("{}-{}", SHARED_NETWORK_NAME, self.service),
+        }
+    }
+
+    /// Whether this service's network is shared with other services.
+    pub fn uses_shared_network(&self) -> bool {
+        self.network.as_ref().is_some_and(|n| n.shared)
+    }
+
+    /// The declared `min_peleka_version`, if it is newer than `current`.
+    pub fn requires_newer_peleka(&self, current: &str) -> Option<&str> {
+        self.min_peleka_version
+            .as_deref()
+            .filter(|required| crate::version::is_newer(required, current))
     }
 
     /// Get the stop timeout for containers.
@@ -312,6 +435,13 @@ impl Config {
         })
     }
 
+    /// Container port of the first `ports` entry, if any.
+    pub fn primary_container_port(&self) -> Option<u16> {
+        let spec = self.ports.first()?;
+        let port_part = spec.split('/').next().unwrap_or(spec);
+        port_part.rsplit(':').next()?.parse().ok()
+    }
+
     pub fn template() -> Self {
         Config {
             service: ServiceName::new("my-app").unwrap(),
@@ -320,27 +450,41 @@ impl Config {
                 host: "server.example.com".to_string(),
                 port: 22,
                 user: Some("deploy".to_string()),
+                deploy_user: None,
+                admin_user: None,
                 runtime: None,
                 socket: None,
                 trust_first_connection: false,
+                inactivity_timeout: None,
+                keepalive_interval: None,
+                reconnect_attempts: None,
             }),
+            min_peleka_version: None,
             ports: vec![],
             volumes: vec![],
             env: HashMap::new(),
             labels: HashMap::new(),
             command: None,
+            args: None,
             healthcheck: None,
             health_timeout: default_health_timeout(),
+            health: None,
             image_pull_timeout: None,
             pull_policy: PullPolicy::default(),
+            podman_auto_update: None,
+            image_archive: None,
             resources: None,
+            read_only: false,
+            tmpfs: vec![],
             network: None,
             restart: RestartPolicy::default(),
             stop: None,
             cleanup: None,
             logging: None,
             strategy: None,
+            discovery: vec![],
             destinations: HashMap::new(),
+            env_overrides: Vec::new(),
         }
     }
 }
@@ -374,6 +518,36 @@ mod tests {
         assert!(config.has_host_port_bindings());
     }
 
+    #[test]
+    fn network_name_defaults_to_per_service() {
+        let config = Config::template();
+        assert_eq!(config.network_name(), "peleka-my-app");
+        assert!(!config.uses_shared_network());
+    }
+
+    #[test]
+    fn network_name_shared_compatibility_mode() {
+        let mut config = Config::template();
+        config.network = Some(NetworkConfig {
+            name: None,
+            shared: true,
+            aliases: vec![],
+        });
+        assert_eq!(config.network_name(), "peleka");
+        assert!(config.uses_shared_network());
+    }
+
+    #[test]
+    fn network_name_explicit_wins() {
+        let mut config = Config::template();
+        config.network = Some(NetworkConfig {
+            name: Some("custom".to_string()),
+            shared: true,
+            aliases: vec![],
+        });
+        assert_eq!(config.network_name(), "custom");
+    }
+
     #[test]
     fn has_host_port_bindings_handles_protocol_suffix() {
         let mut config = Config::template();
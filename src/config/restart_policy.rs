@@ -1,6 +1,7 @@
 // ABOUTME: Container restart policy configuration.
 // ABOUTME: Supports no, always, unless-stopped, and on-failure[:max-retries].
 
+use crate::runtime::RestartPolicyConfig;
 use serde::de::{self, Deserialize, Deserializer};
 use std::fmt;
 use std::str::FromStr;
@@ -16,6 +17,20 @@ pub enum RestartPolicy {
     },
 }
 
+impl RestartPolicy {
+    /// Convert to the runtime's restart policy representation.
+    pub fn to_runtime(&self) -> RestartPolicyConfig {
+        match self {
+            RestartPolicy::No => RestartPolicyConfig::No,
+            RestartPolicy::Always => RestartPolicyConfig::Always,
+            RestartPolicy::UnlessStopped => RestartPolicyConfig::UnlessStopped,
+            RestartPolicy::OnFailure { max_retries } => RestartPolicyConfig::OnFailure {
+                max_retries: *max_retries,
+            },
+        }
+    }
+}
+
 impl FromStr for RestartPolicy {
     type Err = String;
 
@@ -4,6 +4,7 @@
 use crate::runtime::RuntimeType;
 use crate::ssh::SessionConfig;
 use serde::Deserialize;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
@@ -12,12 +13,38 @@ pub struct ServerConfig {
     pub port: u16,
     #[serde(default)]
     pub user: Option<String>,
+    /// SSH user for runtime access (deploy, rollback, exec, logs, ...).
+    /// Takes precedence over `user`.
+    #[serde(default)]
+    pub deploy_user: Option<String>,
+    /// SSH user for server administration tasks that need more privileges.
+    /// Falls back to the deploy user.
+    #[serde(default)]
+    pub admin_user: Option<String>,
     #[serde(default)]
     pub runtime: Option<RuntimeType>,
     #[serde(default)]
     pub socket: Option<String>,
     #[serde(default = "default_trust_first_connection")]
     pub trust_first_connection: bool,
+    /// SSH inactivity timeout (default: 30s).
+    #[serde(default, with = "humantime_serde::option")]
+    pub inactivity_timeout: Option<Duration>,
+    /// Interval between SSH keepalive requests (default: 15s).
+    #[serde(default, with = "humantime_serde::option")]
+    pub keepalive_interval: Option<Duration>,
+    /// Reconnection attempts after a dropped session (default: 3, 0 disables).
+    #[serde(default)]
+    pub reconnect_attempts: Option<u32>,
+}
+
+/// Which identity an SSH operation runs as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SshRole {
+    /// Day-to-day runtime access; least privilege.
+    Deploy,
+    /// Server administration (e.g. checking system services).
+    Admin,
 }
 
 fn default_port() -> u16 {
@@ -60,9 +87,14 @@ impl ServerConfig {
             host: host.to_string(),
             port,
             user: user_part.map(|s| s.to_string()),
+            deploy_user: None,
+            admin_user: None,
             runtime: None,
             socket: None,
             trust_first_connection: default_trust_first_connection(),
+            inactivity_timeout: None,
+            keepalive_interval: None,
+            reconnect_attempts: None,
         })
     }
 
@@ -74,14 +106,28 @@ impl ServerConfig {
         }
     }
 
-    /// Get the SSH user for this server.
-    /// Falls back to $USER env var, then "root".
+    /// Get the SSH user for deploy operations on this server.
+    /// Uses `deploy_user`, then `user`, then $USER, then "root".
     pub fn ssh_user(&self) -> String {
-        self.user
+        self.deploy_user
             .clone()
+            .or_else(|| self.user.clone())
             .unwrap_or_else(|| std::env::var("USER").unwrap_or_else(|_| "root".to_string()))
     }
 
+    /// Get the SSH user for operations in the given role.
+    pub fn ssh_user_for(&self, role: SshRole) -> String {
+        match (role, &self.admin_user) {
+            (SshRole::Admin, Some(admin)) => admin.clone(),
+            _ => self.ssh_user(),
+        }
+    }
+
+    /// Whether admin operations log in as a different user than deploys.
+    pub fn has_separate_admin(&self) -> bool {
+        self.ssh_user_for(SshRole::Admin) != self.ssh_user()
+    }
+
     /// Get runtime type as string for display purposes.
     /// Returns "auto" if no runtime is explicitly configured.
     pub fn runtime_string(&self) -> String {
@@ -91,10 +137,25 @@ impl ServerConfig {
             .unwrap_or_else(|| "auto".to_string())
     }
 
-    /// Create SSH session config for connecting to this server.
+    /// Create SSH session config for deploy operations on this server.
     pub fn ssh_session_config(&self) -> SessionConfig {
-        SessionConfig::new(&self.host, self.ssh_user())
+        self.ssh_session_config_for(SshRole::Deploy)
+    }
+
+    /// Create SSH session config for operations in the given role.
+    pub fn ssh_session_config_for(&self, role: SshRole) -> SessionConfig {
+        let mut config = SessionConfig::new(&self.host, self.ssh_user_for(role))
             .port(self.port)
-            .trust_on_first_use(self.trust_first_connection)
+            .trust_on_first_use(self.trust_first_connection);
+        if let Some(timeout) = self.inactivity_timeout {
+            config = config.inactivity_timeout(Some(timeout));
+        }
+        if let Some(interval) = self.keepalive_interval {
+            config = config.keepalive_interval(Some(interval));
+        }
+        if let Some(attempts) = self.reconnect_attempts {
+            config = config.reconnect_attempts(attempts);
+        }
+        config
     }
 }
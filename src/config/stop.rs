@@ -1,5 +1,5 @@
 // ABOUTME: Container graceful shutdown configuration.
-// ABOUTME: Defines timeout and signal for stopping containers.
+// ABOUTME: Defines timeout, signal, and pre-stop command for stopping containers.
 
 use serde::Deserialize;
 use std::time::Duration;
@@ -11,6 +11,24 @@ pub struct StopConfig {
 
     #[serde(default = "default_signal")]
     pub signal: String,
+
+    /// Command run inside the old container right before it is stopped
+    /// during cleanup (e.g. `nginx -s quit`). Failures are logged, not fatal.
+    #[serde(default)]
+    pub pre_stop_command: Option<String>,
+
+    /// How long the pre-stop command may run before the stop proceeds anyway.
+    #[serde(default = "default_pre_stop_timeout", with = "humantime_serde")]
+    pub pre_stop_timeout: Duration,
+}
+
+impl StopConfig {
+    /// Arguments to exec for the pre-stop command, if one is configured.
+    pub fn pre_stop_args(&self) -> Option<Vec<String>> {
+        self.pre_stop_command
+            .as_deref()
+            .map(super::healthcheck::exec_args)
+    }
 }
 
 fn default_timeout() -> Duration {
@@ -21,11 +39,17 @@ fn default_signal() -> String {
     "SIGTERM".to_string()
 }
 
+fn default_pre_stop_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
 impl Default for StopConfig {
     fn default() -> Self {
         StopConfig {
             timeout: default_timeout(),
             signal: default_signal(),
+            pre_stop_command: None,
+            pre_stop_timeout: default_pre_stop_timeout(),
         }
     }
 }
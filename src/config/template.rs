@@ -0,0 +1,141 @@
+// ABOUTME: Deploy-time template variable substitution for config values.
+// ABOUTME: Expands `{{name}}` placeholders in command and args entries.
+
+use std::collections::HashMap;
+
+/// Variables available to `{{name}}` placeholders at deploy time.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateVars {
+    vars: HashMap<String, String>,
+}
+
+impl TemplateVars {
+    /// Build the standard variable set for a deployment.
+    ///
+    /// - `service`: service name
+    /// - `image`: full image reference
+    /// - `version`: image tag, falling back to the digest
+    ///
+    /// `slot` (`blue`, `green` or `shadow`) is not part of this set: it is
+    /// added when `command`/`args` are resolved for the new container, so
+    /// it is only available there.
+    pub fn for_config(config: &super::Config) -> Self {
+        let mut vars = HashMap::new();
+        vars.insert("service".to_string(), config.service.to_string());
+        vars.insert("image".to_string(), config.image.to_string());
+        let version = config
+            .image
+            .tag()
+            .or(config.image.digest())
+            .unwrap_or("latest");
+        vars.insert("version".to_string(), version.to_string());
+        Self { vars }
+    }
+
+    /// Add or replace a variable.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.vars.insert(name.into(), value.into());
+    }
+
+    /// Expand all `{{name}}` placeholders in `input`.
+    ///
+    /// Whitespace inside the braces is ignored (`{{ version }}` works).
+    /// Unknown variables are errors so that typos don't silently reach the
+    /// container. Braces around anything other than a plain name, such as
+    /// Go templates (`{{.Name}}`) or an unterminated `{{`, are kept as is.
+    pub fn render(&self, input: &str) -> Result<String, String> {
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some(start) = rest.find("{{") {
+            output.push_str(&rest[..start]);
+            let after_open = &rest[start + 2..];
+            let placeholder = after_open
+                .find("}}")
+                .map(|end| (after_open[..end].trim(), end))
+                .filter(|(name, _)| is_variable_name(name));
+            let Some((name, end)) = placeholder else {
+                output.push_str("{{");
+                rest = after_open;
+                continue;
+            };
+            let value = self
+                .vars
+                .get(name)
+                .ok_or_else(|| format!("unknown template variable '{}' in '{}'", name, input))?;
+            output.push_str(value);
+            rest = &after_open[end + 2..];
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    /// Expand placeholders in every entry of a list.
+    pub fn render_all(&self, items: &[String]) -> Result<Vec<String>, String> {
+        items.iter().map(|item| self.render(item)).collect()
+    }
+}
+
+/// Whether `name` can be a template variable: a letter or underscore
+/// followed by letters, digits or underscores.
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        let mut vars = TemplateVars::default();
+        vars.set("version", "v1.2.3");
+        vars.set("service", "myapp");
+        vars
+    }
+
+    #[test]
+    fn renders_known_variables() {
+        assert_eq!(
+            vars().render("--release={{version}}").unwrap(),
+            "--release=v1.2.3"
+        );
+        assert_eq!(
+            vars().render("{{ service }}-{{version}}").unwrap(),
+            "myapp-v1.2.3"
+        );
+    }
+
+    #[test]
+    fn leaves_plain_strings_untouched() {
+        assert_eq!(vars().render("serve --port 80").unwrap(), "serve --port 80");
+    }
+
+    #[test]
+    fn unknown_variable_is_error() {
+        let err = vars().render("{{nope}}").unwrap_err();
+        assert!(err.contains("nope"));
+    }
+
+    #[test]
+    fn unterminated_braces_are_kept() {
+        assert_eq!(
+            vars().render("--release={{version").unwrap(),
+            "--release={{version"
+        );
+    }
+
+    #[test]
+    fn non_variable_braces_are_kept() {
+        assert_eq!(
+            vars()
+                .render("--format={{.Name}} {{json .}} {{}} {{version}}")
+                .unwrap(),
+            "--format={{.Name}} {{json .}} {{}} v1.2.3"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 use crate::config::Config;
 use crate::types::{ContainerId, ImageRef, ServiceName};
 
+use super::metadata::DeployMetadata;
 use super::state::{Completed, ContainerStarted, CutOver, HealthChecked, Initialized};
 
 /// A deployment in progress, parameterized by its current state.
@@ -15,6 +16,11 @@ use super::state::{Completed, ContainerStarted, CutOver, HealthChecked, Initiali
 pub struct Deployment<S> {
     pub(crate) config: Config,
     pub(crate) old_container: Option<ContainerId>,
+    pub(crate) shadow: bool,
+    /// Whether the old container was stopped before the new one started.
+    pub(crate) old_stopped: bool,
+    /// Deploy run metadata exported into the new container's environment.
+    pub(crate) metadata: DeployMetadata,
     pub(crate) state: S,
 }
 
@@ -24,6 +30,9 @@ impl Deployment<Initialized> {
         Deployment {
             config,
             old_container: None,
+            shadow: false,
+            old_stopped: false,
+            metadata: DeployMetadata::new(),
             state: Initialized,
         }
     }
@@ -33,9 +42,36 @@ impl Deployment<Initialized> {
         Deployment {
             config,
             old_container: Some(old_container),
+            shadow: false,
+            old_stopped: false,
+            metadata: DeployMetadata::new(),
             state: Initialized,
         }
     }
+
+    /// Create a shadow deployment.
+    ///
+    /// The container is started and health checked alongside whatever is
+    /// currently serving, but is never given the service alias or host
+    /// ports, and existing containers are left untouched.
+    pub fn new_shadow(config: Config) -> Self {
+        Deployment {
+            config,
+            old_container: None,
+            shadow: true,
+            old_stopped: false,
+            metadata: DeployMetadata::new(),
+            state: Initialized,
+        }
+    }
+
+    /// Use metadata shared with the rest of the deploy run.
+    ///
+    /// Without this, each deployment gets its own deploy ID.
+    pub fn with_metadata(mut self, metadata: DeployMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
 }
 
 impl<S> Deployment<S> {
@@ -54,6 +90,16 @@ impl<S> Deployment<S> {
         &self.config
     }
 
+    /// Whether this is a shadow deployment.
+    pub fn is_shadow(&self) -> bool {
+        self.shadow
+    }
+
+    /// Deploy run metadata (ID, time and, once pulled, image digest).
+    pub fn metadata(&self) -> &DeployMetadata {
+        &self.metadata
+    }
+
     /// Get the old container ID (None on first deploy).
     pub fn old_container(&self) -> Option<&ContainerId> {
         self.old_container.as_ref()
@@ -27,7 +27,9 @@ pub enum DeployErrorKind {
     NoPreviousDeployment,
     Config,
     LockHeld,
+    LocalLockHeld,
     Lock,
+    InsufficientMemory,
 }
 
 /// Information about who holds a deployment lock.
@@ -83,7 +85,9 @@ impl DeployError {
             InnerDeployError::NoPreviousDeployment { .. } => DeployErrorKind::NoPreviousDeployment,
             InnerDeployError::ConfigError { .. } => DeployErrorKind::Config,
             InnerDeployError::LockHeld { .. } => DeployErrorKind::LockHeld,
+            InnerDeployError::LocalLockHeld { .. } => DeployErrorKind::LocalLockHeld,
             InnerDeployError::LockError { .. } => DeployErrorKind::Lock,
+            InnerDeployError::InsufficientMemory { .. } => DeployErrorKind::InsufficientMemory,
         }
     }
 
@@ -200,8 +204,16 @@ enum InnerDeployError {
         started_at: DateTime<Utc>,
     },
 
+    #[snafu(display(
+        "another deploy from this machine is running (pid {pid}, started at {started_at})"
+    ))]
+    LocalLockHeld { pid: u32, started_at: DateTime<Utc> },
+
     #[snafu(display("lock error: {message}"))]
     LockError { message: String },
+
+    #[snafu(display("not enough memory to run old and new containers side by side: {message}"))]
+    InsufficientMemory { message: String },
 }
 
 // Context selectors for converting errors at call sites with proper categorization
@@ -342,9 +354,19 @@ impl DeployError {
         })
     }
 
+    pub fn local_lock_held(pid: u32, started_at: DateTime<Utc>) -> Self {
+        DeployError(InnerDeployError::LocalLockHeld { pid, started_at })
+    }
+
     pub fn lock_error(message: impl Into<String>) -> Self {
         DeployError(InnerDeployError::LockError {
             message: message.into(),
         })
     }
+
+    pub fn insufficient_memory(message: impl Into<String>) -> Self {
+        DeployError(InnerDeployError::InsufficientMemory {
+            message: message.into(),
+        })
+    }
 }
@@ -0,0 +1,177 @@
+// ABOUTME: Local advisory lock so one machine runs one deploy per service+destination.
+// ABOUTME: Lock files live in the local XDG state directory and are held with an OS file lock.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use crate::types::ServiceName;
+
+use super::{DeployError, LockInfo};
+
+/// Name used in lock file names when no destination is selected.
+const DEFAULT_DESTINATION: &str = "default";
+
+/// Local directory holding lock files: `$XDG_STATE_HOME/peleka/locks`,
+/// falling back to `~/.local/state/peleka/locks`.
+pub fn local_lock_dir() -> PathBuf {
+    let state = std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .unwrap_or_else(std::env::temp_dir);
+    state.join("peleka").join("locks")
+}
+
+/// A held local deploy lock. Released when dropped.
+///
+/// This only guards against concurrent runs on the same machine (e.g. two
+/// CI jobs on one runner); the server-side [`DeployLock`](super::DeployLock)
+/// still arbitrates between machines.
+///
+/// The lock file persists; holding it means holding an exclusive OS lock on
+/// it, which the OS releases when the process exits, so a crashed run never
+/// leaves a stale lock. The file's contents (holder pid and start time) only
+/// feed the error message.
+#[derive(Debug)]
+pub struct LocalLock {
+    file: File,
+}
+
+impl LocalLock {
+    /// Acquire the lock for `service` deployed to `destination`.
+    pub fn acquire(
+        dir: &Path,
+        service: &ServiceName,
+        destination: Option<&str>,
+    ) -> Result<Self, DeployError> {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            DeployError::lock_error(format!("failed to create {}: {}", dir.display(), e))
+        })?;
+        let path = Self::path(dir, service, destination);
+        let io_error =
+            |e: std::io::Error| DeployError::lock_error(format!("{}: {}", path.display(), e));
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut contents = String::new();
+                let holder = file
+                    .read_to_string(&mut contents)
+                    .ok()
+                    .and_then(|_| serde_json::from_str::<LockInfo>(&contents).ok());
+                return Err(match holder {
+                    Some(info) => DeployError::local_lock_held(info.pid, info.started_at),
+                    None => DeployError::lock_error(format!(
+                        "{} is held by another deploy",
+                        path.display()
+                    )),
+                });
+            }
+            Err(TryLockError::Error(e)) => return Err(io_error(e)),
+        }
+
+        let info = serde_json::to_string(&LockInfo::new(service))
+            .map_err(|e| DeployError::lock_error(format!("failed to serialize lock: {}", e)))?;
+        file.set_len(0).map_err(io_error)?;
+        file.rewind().map_err(io_error)?;
+        file.write_all(info.as_bytes()).map_err(io_error)?;
+
+        Ok(Self { file })
+    }
+
+    /// Lock file for a service and destination.
+    pub fn path(dir: &Path, service: &ServiceName, destination: Option<&str>) -> PathBuf {
+        dir.join(format!(
+            "{}@{}.lock",
+            service,
+            destination.unwrap_or(DEFAULT_DESTINATION)
+        ))
+    }
+}
+
+impl Drop for LocalLock {
+    fn drop(&mut self) {
+        // Clear the holder; the OS lock is released when the file closes
+        let _ = self.file.set_len(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> ServiceName {
+        ServiceName::new("myapp").unwrap()
+    }
+
+    #[test]
+    fn second_acquire_reports_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let _lock = LocalLock::acquire(dir.path(), &service(), Some("staging")).unwrap();
+
+        let err = LocalLock::acquire(dir.path(), &service(), Some("staging")).unwrap_err();
+        assert_eq!(err.kind(), crate::deploy::DeployErrorKind::LocalLockHeld);
+        assert!(
+            err.to_string()
+                .contains(&format!("pid {}", std::process::id()))
+        );
+    }
+
+    #[test]
+    fn destinations_lock_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let _staging = LocalLock::acquire(dir.path(), &service(), Some("staging")).unwrap();
+        let _default = LocalLock::acquire(dir.path(), &service(), None).unwrap();
+        assert!(dir.path().join("myapp@default.lock").exists());
+    }
+
+    #[test]
+    fn drop_releases_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        drop(LocalLock::acquire(dir.path(), &service(), None).unwrap());
+
+        LocalLock::acquire(dir.path(), &service(), None).unwrap();
+    }
+
+    #[test]
+    fn lock_file_records_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let _lock = LocalLock::acquire(dir.path(), &service(), None).unwrap();
+
+        let contents =
+            std::fs::read_to_string(LocalLock::path(dir.path(), &service(), None)).unwrap();
+        let info: LockInfo = serde_json::from_str(&contents).unwrap();
+        assert_eq!(info.pid, std::process::id());
+    }
+
+    #[test]
+    fn unlocked_file_from_dead_process_is_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut info = LockInfo::new(&service());
+        info.pid = u32::MAX;
+        std::fs::write(
+            LocalLock::path(dir.path(), &service(), None),
+            serde_json::to_string(&info).unwrap(),
+        )
+        .unwrap();
+
+        LocalLock::acquire(dir.path(), &service(), None).unwrap();
+    }
+
+    #[test]
+    fn unlocked_unreadable_file_is_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(LocalLock::path(dir.path(), &service(), None), "not json").unwrap();
+
+        LocalLock::acquire(dir.path(), &service(), None).unwrap();
+    }
+}
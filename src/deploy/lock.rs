@@ -38,10 +38,24 @@ impl LockInfo {
 
     /// Check if this lock is stale (older than 1 hour).
     pub fn is_stale(&self) -> bool {
-        let age = Utc::now() - self.started_at;
+        self.is_stale_at(Utc::now())
+    }
+
+    /// Check if this lock is stale relative to `now`.
+    pub fn is_stale_at(&self, now: DateTime<Utc>) -> bool {
+        let age = now - self.started_at;
         age.num_hours() >= 1
     }
 
+    /// Check if this lock was taken by the current process.
+    ///
+    /// After a dropped SSH session the lock could not be released; the
+    /// reconnected deploy reclaims it instead of waiting for it to go stale.
+    pub fn is_held_by_current_process(&self) -> bool {
+        self.pid == std::process::id()
+            && self.holder == gethostname::gethostname().to_string_lossy()
+    }
+
     /// Path to the lock file for a service.
     /// Uses $HOME for shell expansion compatibility.
     pub fn lock_path(service: &ServiceName) -> String {
@@ -83,8 +97,11 @@ impl<'a> DeployLock<'a> {
         // Ensure state directory exists
         Self::ensure_state_dir(session).await?;
 
-        // Prepare lock info
-        let lock_info = LockInfo::new(service);
+        // Lock timestamps use the server clock so deployers with skewed
+        // local clocks agree on when a lock goes stale.
+        let skew = session.clock_skew().await.unwrap_or_default();
+        let mut lock_info = LockInfo::new(service);
+        lock_info.started_at = skew.to_server_time(lock_info.started_at);
         let lock_json = serde_json::to_string(&lock_info)
             .map_err(|e| DeployError::lock_error(format!("failed to serialize lock: {}", e)))?;
         let escaped_json = lock_json.replace('\'', "'\\''");
@@ -110,7 +127,9 @@ impl<'a> DeployLock<'a> {
         }
 
         // Lock acquisition failed - check if existing lock should be broken
-        let should_break = Self::check_existing_lock(session, &lock_path, force).await?;
+        let server_now = skew.to_server_time(Utc::now());
+        let should_break =
+            Self::check_existing_lock(session, &lock_path, force, server_now).await?;
 
         if !should_break {
             // Lock is valid and held by someone else
@@ -172,6 +191,7 @@ impl<'a> DeployLock<'a> {
         session: &Session,
         lock_path: &str,
         force: bool,
+        server_now: DateTime<Utc>,
     ) -> Result<bool, DeployError> {
         let output = session
             .exec(&format!("cat \"{}\"", lock_path))
@@ -186,7 +206,10 @@ impl<'a> DeployLock<'a> {
 
         match serde_json::from_str::<LockInfo>(&output.stdout) {
             Ok(existing_lock) => {
-                if force {
+                if existing_lock.is_held_by_current_process() {
+                    tracing::debug!("Reclaiming lock taken by this deploy before reconnecting");
+                    Ok(true)
+                } else if force {
                     tracing::warn!(
                         "Breaking lock held by {} (pid {}) since {}",
                         existing_lock.holder,
@@ -194,7 +217,7 @@ impl<'a> DeployLock<'a> {
                         existing_lock.started_at
                     );
                     Ok(true)
-                } else if existing_lock.is_stale() {
+                } else if existing_lock.is_stale_at(server_now) {
                     tracing::warn!(
                         "Auto-breaking stale lock held by {} (pid {}) since {}",
                         existing_lock.holder,
@@ -302,6 +325,16 @@ mod tests {
         assert!(!info.holder.is_empty());
     }
 
+    #[test]
+    fn own_lock_is_held_by_current_process() {
+        let service = ServiceName::new("test-service").unwrap();
+        let mut info = LockInfo::new(&service);
+        assert!(info.is_held_by_current_process());
+
+        info.pid = info.pid.wrapping_add(1);
+        assert!(!info.is_held_by_current_process());
+    }
+
     #[test]
     fn lock_path_uses_state_dir() {
         let service = ServiceName::new("myapp").unwrap();
@@ -326,4 +359,13 @@ mod tests {
         info.started_at = Utc::now() - chrono::Duration::hours(2);
         assert!(info.is_stale());
     }
+
+    #[test]
+    fn staleness_uses_supplied_clock() {
+        let service = ServiceName::new("test").unwrap();
+        let info = LockInfo::new(&service);
+        // A server clock two hours ahead sees a just-written lock as stale
+        assert!(info.is_stale_at(Utc::now() + chrono::Duration::hours(2)));
+        assert!(!info.is_stale_at(info.started_at));
+    }
 }
@@ -0,0 +1,112 @@
+// ABOUTME: Host memory check run before starting a blue-green container.
+// ABOUTME: Reads MemAvailable over SSH and compares it to the new container's limit plus headroom.
+
+use std::fmt;
+
+use crate::config::Config;
+use crate::ssh::{self, Session};
+
+/// Memory needed to start the new container next to the old one.
+///
+/// The old container is already running, so its usage is already missing
+/// from the host's available memory; only the new limit and the configured
+/// headroom have to fit in what is left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryCheck {
+    /// New container limit plus headroom, in bytes.
+    pub required: u64,
+    /// Memory the host reports as available, in bytes.
+    pub available: u64,
+}
+
+impl MemoryCheck {
+    /// Build a check for `config`, or None if no memory limit is configured.
+    pub fn for_config(config: &Config, available: u64) -> Option<Self> {
+        let resources = config.resources.as_ref()?;
+        let limit = resources.memory_bytes()?;
+        Some(Self {
+            required: limit.saturating_add(resources.memory_headroom_bytes()),
+            available,
+        })
+    }
+
+    /// Whether the new container fits alongside the old one.
+    pub fn fits(&self) -> bool {
+        self.required <= self.available
+    }
+}
+
+impl fmt::Display for MemoryCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "needs {} MiB, {} MiB available",
+            self.required / (1024 * 1024),
+            self.available / (1024 * 1024)
+        )
+    }
+}
+
+/// Read the host's available memory in bytes from `/proc/meminfo`.
+pub async fn host_available_memory(session: &Session) -> ssh::Result<u64> {
+    let output = session
+        .exec("cat /proc/meminfo")
+        .await?
+        .require_tool("cat")?;
+    parse_mem_available(&output.stdout).ok_or_else(|| {
+        ssh::Error::CommandFailed("MemAvailable not found in /proc/meminfo".to_string())
+    })
+}
+
+/// Extract `MemAvailable` (reported in kB) from `/proc/meminfo` contents.
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let rest = line.strip_prefix("MemAvailable:")?;
+        let kb: u64 = rest.split_whitespace().next()?.parse().ok()?;
+        Some(kb * 1024)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ResourcesConfig;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn config_with(resources: &str) -> Config {
+        let mut config = Config::template();
+        config.resources = Some(serde_yaml::from_str::<ResourcesConfig>(resources).unwrap());
+        config
+    }
+
+    #[test]
+    fn parses_mem_available() {
+        let meminfo = "MemTotal:        2031616 kB\nMemFree:          102400 kB\nMemAvailable:     524288 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(512 * MIB));
+    }
+
+    #[test]
+    fn missing_mem_available_is_none() {
+        assert_eq!(parse_mem_available("MemTotal: 2031616 kB\n"), None);
+    }
+
+    #[test]
+    fn no_check_without_memory_limit() {
+        assert!(MemoryCheck::for_config(&Config::template(), 512 * MIB).is_none());
+        assert!(MemoryCheck::for_config(&config_with("cpus: \"1.0\""), 512 * MIB).is_none());
+    }
+
+    #[test]
+    fn requires_limit_plus_headroom() {
+        let config = config_with("memory: 256m\nmemory_headroom: 128m");
+
+        let check = MemoryCheck::for_config(&config, 384 * MIB).unwrap();
+        assert_eq!(check.required, 384 * MIB);
+        assert!(check.fits());
+
+        let check = MemoryCheck::for_config(&config, 383 * MIB).unwrap();
+        assert!(!check.fits());
+        assert_eq!(check.to_string(), "needs 384 MiB, 383 MiB available");
+    }
+}
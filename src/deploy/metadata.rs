@@ -0,0 +1,114 @@
+// ABOUTME: Deploy metadata injected into every deployed container's environment.
+// ABOUTME: Identifies the deploy run, image digest, deploy time and slot for traceability.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::HashMap;
+
+/// Environment variable names set on every deployed container.
+pub const DEPLOY_ID_ENV: &str = "PELEKA_DEPLOY_ID";
+pub const IMAGE_DIGEST_ENV: &str = "PELEKA_IMAGE_DIGEST";
+pub const DEPLOYED_AT_ENV: &str = "PELEKA_DEPLOYED_AT";
+pub const SLOT_ENV: &str = "PELEKA_SLOT";
+
+/// Facts about a deploy run that applications can report back.
+///
+/// One value is shared by all servers in a run, so the same deploy ID shows
+/// up on every host. The image digest is filled in per server after the pull.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeployMetadata {
+    /// Identifier of the deploy run, e.g. `20261016T120000Z-3f2a9c1b`.
+    pub id: String,
+    /// When the deploy run started.
+    pub deployed_at: DateTime<Utc>,
+    /// Registry digest of the deployed image (`sha256:...`), when known.
+    /// Images loaded from an archive or built locally have none.
+    pub image_digest: Option<String>,
+}
+
+impl DeployMetadata {
+    /// Metadata for a deploy run starting now.
+    pub fn new() -> Self {
+        Self::at(Utc::now())
+    }
+
+    /// Metadata for a deploy run started at `deployed_at`.
+    pub fn at(deployed_at: DateTime<Utc>) -> Self {
+        Self {
+            id: run_id_at(deployed_at),
+            deployed_at,
+            image_digest: None,
+        }
+    }
+
+    /// Environment variables for a container deployed into `slot`.
+    pub fn env(&self, slot: &str) -> HashMap<String, String> {
+        let mut env = HashMap::from([
+            (DEPLOY_ID_ENV.to_string(), self.id.clone()),
+            (
+                DEPLOYED_AT_ENV.to_string(),
+                self.deployed_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+            (SLOT_ENV.to_string(), slot.to_string()),
+        ]);
+        if let Some(digest) = &self.image_digest {
+            env.insert(IMAGE_DIGEST_ENV.to_string(), digest.clone());
+        }
+        env
+    }
+}
+
+/// ID for a peleka run (deploy, exec, ...) starting now.
+pub fn new_run_id() -> String {
+    run_id_at(Utc::now())
+}
+
+/// ID for a run started at `at`, e.g. `20261016T120000Z-3f2a9c1b`.
+fn run_id_at(at: DateTime<Utc>) -> String {
+    // Mix in the pid so two runs in the same second still differ
+    let suffix = at.timestamp_subsec_nanos() ^ std::process::id().rotate_left(16);
+    format!("{}-{:08x}", at.format("%Y%m%dT%H%M%SZ"), suffix)
+}
+
+impl Default for DeployMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn id_starts_with_deploy_time() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let metadata = DeployMetadata::at(at);
+        assert!(
+            metadata.id.starts_with("20261016T120000Z-"),
+            "{}",
+            metadata.id
+        );
+        assert_eq!(metadata.id.len(), "20261016T120000Z-".len() + 8);
+    }
+
+    #[test]
+    fn env_includes_slot_and_time() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let metadata = DeployMetadata::at(at);
+        let env = metadata.env("green");
+        assert_eq!(env[DEPLOY_ID_ENV], metadata.id);
+        assert_eq!(env[DEPLOYED_AT_ENV], "2026-10-16T12:00:00Z");
+        assert_eq!(env[SLOT_ENV], "green");
+        assert!(!env.contains_key(IMAGE_DIGEST_ENV));
+    }
+
+    #[test]
+    fn env_includes_digest_when_known() {
+        let metadata = DeployMetadata {
+            image_digest: Some("sha256:abc".to_string()),
+            ..DeployMetadata::new()
+        };
+        assert_eq!(metadata.env("blue")[IMAGE_DIGEST_ENV], "sha256:abc");
+    }
+}
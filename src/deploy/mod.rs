@@ -3,20 +3,35 @@
 
 mod deployment;
 mod error;
+mod local_lock;
 mod lock;
+mod memory;
+mod metadata;
 mod orphans;
+mod release;
 mod rollback;
 mod state;
 mod strategy;
+mod supervise;
 mod transitions;
+mod verify;
 
 pub use deployment::Deployment;
 pub use error::{
     ContainerErrorExt, DeployError, DeployErrorKind, ImageErrorExt, LockHolderInfo, NetworkErrorExt,
 };
+pub use local_lock::{LocalLock, local_lock_dir};
 pub use lock::{DeployLock, LockInfo};
-pub use orphans::{CleanupFailure, CleanupResult, cleanup_orphans, detect_orphans};
+pub use memory::{MemoryCheck, host_available_memory};
+pub use metadata::{DeployMetadata, new_run_id};
+pub use orphans::{
+    CleanupFailure, CleanupResult, EPHEMERAL_LABEL, RUN_ID_LABEL, cleanup_ephemeral,
+    cleanup_orphans, detect_orphans,
+};
+pub use release::{Release, find_release};
 pub use rollback::manual_rollback;
 pub use state::{Completed, ContainerStarted, CutOver, HealthChecked, ImagePulled, Initialized};
 pub use strategy::DeployStrategy;
+pub use supervise::{HealthTracker, RestartLimiter, restart_container};
 pub use transitions::TransitionResult;
+pub use verify::{Drift, VerifyReport, check_container, verify_service};
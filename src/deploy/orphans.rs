@@ -3,6 +3,7 @@
 
 use crate::runtime::{ContainerError, ContainerFilters, ContainerOps, ContainerSummary};
 use crate::types::{ContainerId, ServiceName};
+use std::collections::HashMap;
 
 /// Result of a cleanup operation.
 #[derive(Debug)]
@@ -38,7 +39,8 @@ pub struct CleanupFailure {
 ///
 /// An orphan is a container that:
 /// - Is managed by peleka (`peleka.managed=true`)
-/// - Belongs to the specified service (`peleka.service=<service>`)
+/// - Belongs to the specified service (`peleka.service=<service>`);
+///   other services on the same host are never considered
 /// - Is not in the provided list of known containers (old/new)
 ///
 /// # Arguments
@@ -59,15 +61,95 @@ pub async fn detect_orphans<R: ContainerOps>(
 
     let containers = runtime.list_containers(&filters).await?;
 
-    // Filter out known containers
+    // Filter out known containers. The label check is defensive: on a shared
+    // host, never treat another service's containers as ours.
     let orphans: Vec<ContainerSummary> = containers
         .into_iter()
+        .filter(|c| c.labels.get("peleka.service").map(String::as_str) == Some(service.as_str()))
         .filter(|c| !known_containers.iter().any(|k| k == &c.id))
         .collect();
 
     Ok(orphans)
 }
 
+/// Label carrying the ID of the deploy run that created a container.
+pub(crate) const DEPLOY_ID_LABEL: &str = "peleka.deploy-id";
+
+/// Label marking short-lived containers peleka creates and removes itself,
+/// such as external health checkers.
+pub const EPHEMERAL_LABEL: &str = "peleka.ephemeral";
+
+/// Label carrying the ID of the run that created an ephemeral container.
+pub const RUN_ID_LABEL: &str = "peleka.run-id";
+
+/// Remove ephemeral containers of a service left by other, interrupted runs.
+///
+/// Containers from `run_id` (the current run) are kept. Returns the IDs of
+/// the removed containers; removal failures are logged and skipped.
+pub async fn cleanup_ephemeral<R: ContainerOps>(
+    runtime: &R,
+    service: &ServiceName,
+    run_id: &str,
+) -> Result<Vec<ContainerId>, ContainerError> {
+    let filters = ContainerFilters {
+        labels: HashMap::from([
+            (EPHEMERAL_LABEL.to_string(), "true".to_string()),
+            ("peleka.service".to_string(), service.to_string()),
+        ]),
+        all: true,
+        ..Default::default()
+    };
+
+    let mut removed = Vec::new();
+    for container in runtime.list_containers(&filters).await? {
+        if container.labels.get(RUN_ID_LABEL).map(String::as_str) == Some(run_id) {
+            continue;
+        }
+        match runtime.remove_container(&container.id, true).await {
+            Ok(()) => removed.push(container.id),
+            Err(e) => tracing::warn!(
+                "Failed to remove leftover ephemeral container {}: {}",
+                container.id,
+                e
+            ),
+        }
+    }
+    Ok(removed)
+}
+
+/// Pick containers left by an interrupted run that block creating `name`.
+///
+/// A leftover has the name the new container will use, never got running
+/// (created, exited or dead), and carries the deploy ID of a run other than
+/// `deploy_id`. Containers without a deploy ID were not made by an
+/// interrupted peleka run and are left alone, as is `keep` (the old
+/// container being replaced).
+pub(crate) fn select_leftovers(
+    containers: &[ContainerSummary],
+    name: &str,
+    deploy_id: &str,
+    keep: Option<&ContainerId>,
+) -> Vec<ContainerId> {
+    containers
+        .iter()
+        .filter(|c| c.name == name)
+        // Podman reports "configured" and "stopped" for created and exited
+        .filter(|c| {
+            matches!(
+                c.state.as_str(),
+                "created" | "configured" | "exited" | "stopped" | "dead"
+            )
+        })
+        .filter(|c| {
+            c.labels
+                .get(DEPLOY_ID_LABEL)
+                .is_some_and(|label| label != deploy_id)
+        })
+        .filter(|c| keep != Some(&c.id))
+        .map(|c| c.id.clone())
+        .collect()
+}
+
 /// Clean up orphaned containers.
 ///
 /// Stops and removes all provided containers. Returns detailed results
@@ -112,6 +194,42 @@ pub async fn cleanup_orphans<R: ContainerOps>(
 mod tests {
     use super::*;
 
+    fn summary(id: &str, name: &str, state: &str, deploy_id: Option<&str>) -> ContainerSummary {
+        let mut labels = HashMap::new();
+        if let Some(deploy_id) = deploy_id {
+            labels.insert(DEPLOY_ID_LABEL.to_string(), deploy_id.to_string());
+        }
+        ContainerSummary {
+            id: ContainerId::new(id.to_string()),
+            name: name.to_string(),
+            image: "app:v1".to_string(),
+            state: state.to_string(),
+            status: String::new(),
+            labels,
+        }
+    }
+
+    #[test]
+    fn leftovers_are_stopped_same_name_containers_from_older_runs() {
+        let containers = vec![
+            summary("crashed", "app-green", "created", Some("run-1")),
+            summary("unlabeled", "app-green", "exited", None),
+            summary("running", "app-green", "running", Some("run-1")),
+            summary("other-name", "app-blue", "exited", Some("run-1")),
+            summary("ours", "app-green", "created", Some("run-2")),
+        ];
+
+        let leftovers = select_leftovers(&containers, "app-green", "run-2", None);
+        assert_eq!(leftovers, vec![ContainerId::new("crashed".to_string())]);
+    }
+
+    #[test]
+    fn old_container_is_never_a_leftover() {
+        let containers = vec![summary("old", "app-green", "exited", Some("run-1"))];
+        let keep = ContainerId::new("old".to_string());
+        assert!(select_leftovers(&containers, "app-green", "run-2", Some(&keep)).is_empty());
+    }
+
     #[test]
     fn cleanup_result_all_succeeded_when_no_failures() {
         let result = CleanupResult {
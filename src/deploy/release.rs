@@ -0,0 +1,147 @@
+// ABOUTME: Lookup of a service's active and previous release containers.
+// ABOUTME: The active release is running; the previous one is kept stopped for rollback.
+
+use crate::runtime::{ContainerFilters, ContainerOps, ContainerSummary};
+use crate::types::ServiceName;
+
+use super::DeployError;
+use super::orphans::DEPLOY_ID_LABEL;
+
+/// Which release of a service to target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Release {
+    /// The running container currently serving traffic.
+    Active,
+    /// The stopped container from the release before, kept for rollback.
+    Previous,
+}
+
+impl std::fmt::Display for Release {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Release::Active => write!(f, "active"),
+            Release::Previous => write!(f, "previous"),
+        }
+    }
+}
+
+/// Find the container for a release of a service.
+///
+/// Shadow containers (from `deploy --shadow`) are never returned, and
+/// neither are containers that never ran (created, configured or dead).
+pub async fn find_release<R: ContainerOps>(
+    runtime: &R,
+    service: &ServiceName,
+    release: Release,
+) -> Result<Option<ContainerSummary>, DeployError> {
+    let filters = ContainerFilters::for_service(service, true);
+    let containers = runtime
+        .list_containers(&filters)
+        .await
+        .map_err(|e| DeployError::config_error(format!("failed to list containers: {}", e)))?;
+
+    Ok(select_release(containers, release))
+}
+
+/// Pick the container for `release` from a service's containers.
+///
+/// When several containers qualify, the most recent one wins. Deploy IDs
+/// start with the run's UTC timestamp, so they order by creation time;
+/// containers from before deploy IDs were recorded count as oldest.
+fn select_release(containers: Vec<ContainerSummary>, release: Release) -> Option<ContainerSummary> {
+    containers
+        .into_iter()
+        .filter(|c| c.labels.get("peleka.shadow").map(String::as_str) != Some("true"))
+        .filter(|c| match release {
+            Release::Active => c.state == "running",
+            // Podman reports "stopped" for exited
+            Release::Previous => matches!(c.state.as_str(), "exited" | "stopped"),
+        })
+        .max_by(|a, b| {
+            a.labels
+                .get(DEPLOY_ID_LABEL)
+                .cmp(&b.labels.get(DEPLOY_ID_LABEL))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ContainerId;
+    use std::collections::HashMap;
+
+    fn summary(id: &str, state: &str, shadow: bool) -> ContainerSummary {
+        let mut labels = HashMap::new();
+        if shadow {
+            labels.insert("peleka.shadow".to_string(), "true".to_string());
+        }
+        labeled(id, state, labels)
+    }
+
+    fn deployed(id: &str, state: &str, deploy_id: &str) -> ContainerSummary {
+        let labels = HashMap::from([(DEPLOY_ID_LABEL.to_string(), deploy_id.to_string())]);
+        labeled(id, state, labels)
+    }
+
+    fn labeled(id: &str, state: &str, labels: HashMap<String, String>) -> ContainerSummary {
+        ContainerSummary {
+            id: ContainerId::new(id.to_string()),
+            name: id.to_string(),
+            image: "app:v1".to_string(),
+            state: state.to_string(),
+            status: String::new(),
+            labels,
+        }
+    }
+
+    #[test]
+    fn selects_running_as_active_and_stopped_as_previous() {
+        let containers = vec![
+            summary("blue", "exited", false),
+            summary("green", "running", false),
+        ];
+
+        let active = select_release(containers.clone(), Release::Active).unwrap();
+        assert_eq!(active.name, "green");
+
+        let previous = select_release(containers, Release::Previous).unwrap();
+        assert_eq!(previous.name, "blue");
+    }
+
+    #[test]
+    fn ignores_shadow_containers() {
+        let containers = vec![summary("shadow", "running", true)];
+        assert!(select_release(containers, Release::Active).is_none());
+    }
+
+    #[test]
+    fn no_previous_on_first_deploy() {
+        let containers = vec![summary("blue", "running", false)];
+        assert!(select_release(containers, Release::Previous).is_none());
+    }
+
+    #[test]
+    fn previous_is_the_most_recent_stopped_release() {
+        let containers = vec![
+            deployed("older", "exited", "20260101T000000Z-aaaaaaaa"),
+            deployed("newer", "exited", "20260201T000000Z-bbbbbbbb"),
+            summary("unlabeled", "exited", false),
+            deployed("active", "running", "20260301T000000Z-cccccccc"),
+        ];
+
+        let previous = select_release(containers, Release::Previous).unwrap();
+        assert_eq!(previous.name, "newer");
+    }
+
+    #[test]
+    fn containers_that_never_ran_are_not_releases() {
+        let containers = vec![
+            deployed("old", "exited", "20260101T000000Z-aaaaaaaa"),
+            deployed("crashed", "created", "20260201T000000Z-bbbbbbbb"),
+            deployed("broken", "dead", "20260201T000000Z-cccccccc"),
+        ];
+
+        let previous = select_release(containers, Release::Previous).unwrap();
+        assert_eq!(previous.name, "old");
+    }
+}
@@ -13,6 +13,11 @@ pub enum DeployStrategy {
     /// Recreate deployment: stop old container first, then start new.
     /// Required when host port bindings prevent running two containers simultaneously.
     Recreate,
+
+    /// Replace deployment: stop (but keep) old container, then start new.
+    /// Never runs two copies at once; the old container is restarted if the
+    /// new one fails to start or pass its health check.
+    Replace,
 }
 
 impl DeployStrategy {
@@ -29,6 +34,7 @@ impl DeployStrategy {
             return match strategy {
                 StrategyConfig::BlueGreen => (DeployStrategy::BlueGreen, None),
                 StrategyConfig::Recreate => (DeployStrategy::Recreate, None),
+                StrategyConfig::Replace => (DeployStrategy::Replace, None),
             };
         }
 
@@ -79,6 +85,16 @@ mod tests {
         assert!(reason.is_none()); // No reason needed - user explicitly chose
     }
 
+    #[test]
+    fn explicit_replace_strategy() {
+        let mut config = Config::template();
+        config.strategy = Some(StrategyConfig::Replace);
+
+        let (strategy, reason) = DeployStrategy::for_config(&config);
+        assert_eq!(strategy, DeployStrategy::Replace);
+        assert!(reason.is_none());
+    }
+
     #[test]
     fn explicit_blue_green_strategy() {
         let mut config = Config::template();
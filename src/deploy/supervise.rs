@@ -0,0 +1,122 @@
+// ABOUTME: Building blocks for restart-on-unhealthy supervision.
+// ABOUTME: Detects transitions to unhealthy and rate-limits restarts.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::runtime::{ContainerOps, HealthState};
+use crate::types::ContainerId;
+
+use super::error::{ContainerErrorExt, DeployError};
+
+/// Tracks a container's health to report transitions into unhealthy.
+#[derive(Debug, Default)]
+pub struct HealthTracker {
+    last: Option<HealthState>,
+}
+
+impl HealthTracker {
+    /// Record an observation. Returns true if the container just became unhealthy.
+    pub fn observe(&mut self, state: HealthState) -> bool {
+        let was_unhealthy = self.last == Some(HealthState::Unhealthy);
+        self.last = Some(state);
+        state == HealthState::Unhealthy && !was_unhealthy
+    }
+
+    /// Forget the last observation (e.g. after a restart or container change).
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Sliding-window limit on restarts.
+#[derive(Debug)]
+pub struct RestartLimiter {
+    max: u32,
+    window: Duration,
+    history: VecDeque<Instant>,
+}
+
+impl RestartLimiter {
+    pub fn new(max: u32, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Record a restart at `now` if the limit allows it.
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        while let Some(&oldest) = self.history.front() {
+            if now.duration_since(oldest) >= self.window {
+                self.history.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.history.len() as u32 >= self.max {
+            return false;
+        }
+        self.history.push_back(now);
+        true
+    }
+}
+
+/// Restart a container in place (stop, then start).
+pub async fn restart_container<R: ContainerOps>(
+    runtime: &R,
+    id: &ContainerId,
+    stop_timeout: Duration,
+) -> Result<(), DeployError> {
+    runtime
+        .stop_container(id, stop_timeout)
+        .await
+        .context_container_stop()?;
+    runtime
+        .start_container(id)
+        .await
+        .map_err(|e| DeployError::container_start_failed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_reports_only_transitions() {
+        let mut tracker = HealthTracker::default();
+        assert!(!tracker.observe(HealthState::Healthy));
+        assert!(tracker.observe(HealthState::Unhealthy));
+        assert!(!tracker.observe(HealthState::Unhealthy));
+        assert!(!tracker.observe(HealthState::Healthy));
+        assert!(tracker.observe(HealthState::Unhealthy));
+    }
+
+    #[test]
+    fn tracker_reports_unhealthy_on_first_observation() {
+        let mut tracker = HealthTracker::default();
+        assert!(tracker.observe(HealthState::Unhealthy));
+        tracker.reset();
+        assert!(tracker.observe(HealthState::Unhealthy));
+    }
+
+    #[test]
+    fn limiter_blocks_after_max_within_window() {
+        let start = Instant::now();
+        let mut limiter = RestartLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start + Duration::from_secs(1)));
+        assert!(!limiter.try_acquire(start + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn limiter_allows_again_after_window() {
+        let start = Instant::now();
+        let mut limiter = RestartLimiter::new(1, Duration::from_secs(60));
+        assert!(limiter.try_acquire(start));
+        assert!(!limiter.try_acquire(start + Duration::from_secs(30)));
+        assert!(limiter.try_acquire(start + Duration::from_secs(61)));
+    }
+}
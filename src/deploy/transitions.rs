@@ -1,17 +1,24 @@
 // ABOUTME: State transition methods for deployment orchestration.
 // ABOUTME: Each method consumes self and returns the next state on success.
 
+use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 
-use crate::config::{Config, PullPolicy, resolve_env_map};
+use crate::config::{
+    AUTO_UPDATE_LABEL, Config, ENV_OVERRIDES_LABEL, ExternalHealthcheck, HealthcheckConfig,
+    PullPolicy, StopConfig, TemplateVars, resolve_env_map,
+};
 use crate::runtime::{
-    ContainerConfig, ContainerOps, ImageOps, NetworkConfig as RuntimeNetworkConfig, NetworkOps,
-    RegistryAuth, RestartPolicyConfig, VolumeMount,
+    ContainerConfig, ContainerError, ContainerFilters, ContainerOps, ExecConfig, ExecOps,
+    ImageArchive, ImageOps, NetworkConfig as RuntimeNetworkConfig, NetworkOps, RegistryAuth,
+    RestartPolicyConfig, VolumeMount,
 };
-use crate::types::{ContainerId, NetworkAlias, NetworkId};
+use crate::types::{ContainerId, ImageRef, NetworkAlias, NetworkId};
 
 use super::Deployment;
 use super::error::{ContainerErrorExt, DeployError, ImageErrorExt};
+use super::orphans::{DEPLOY_ID_LABEL, EPHEMERAL_LABEL, RUN_ID_LABEL, select_leftovers};
 use super::state::{Completed, ContainerStarted, CutOver, HealthChecked, ImagePulled, Initialized};
 
 /// Result type for transitions that may need rollback on failure.
@@ -27,20 +34,121 @@ enum HealthPollResult {
     ExecFailed(String),
     /// Health check timed out.
     Timeout,
+    /// The command (or `sh` it needs) does not exist in the container.
+    MissingExecutable(String),
+}
+
+/// How a health check reaches the new container.
+enum HealthProbe {
+    /// Exec the command inside the container.
+    Exec(Vec<String>),
+    /// Run a checker container that probes it over the network.
+    External(Box<ContainerConfig>),
 }
 
 /// Run a single health check poll with timeout.
 async fn poll_health_once<R: ContainerOps>(
     runtime: &R,
     container_id: &ContainerId,
-    cmd: &[String],
+    probe: &HealthProbe,
     timeout: Duration,
 ) -> HealthPollResult {
-    match tokio::time::timeout(timeout, runtime.run_healthcheck(container_id, cmd)).await {
-        Ok(Ok(true)) => HealthPollResult::Healthy,
-        Ok(Ok(false)) => HealthPollResult::Unhealthy,
-        Ok(Err(e)) => HealthPollResult::ExecFailed(e.to_string()),
-        Err(_) => HealthPollResult::Timeout,
+    let result = match probe {
+        HealthProbe::Exec(cmd) => {
+            tokio::time::timeout(timeout, runtime.run_healthcheck(container_id, cmd))
+                .await
+                .ok()
+        }
+        HealthProbe::External(checker) => run_checker(runtime, checker, timeout).await,
+    };
+
+    match result {
+        Some(Ok(true)) => HealthPollResult::Healthy,
+        Some(Ok(false)) => HealthPollResult::Unhealthy,
+        Some(Err(ContainerError::ExecutableNotFound(name))) => {
+            HealthPollResult::MissingExecutable(name)
+        }
+        Some(Err(e)) => HealthPollResult::ExecFailed(e.to_string()),
+        None => HealthPollResult::Timeout,
+    }
+}
+
+/// Poll once and, if healthy, follow up with the configured warm-up probes.
+///
+/// The first warm-up probe that is not healthy becomes the result, so a
+/// single lucky response does not count as ready.
+async fn poll_until_warm<R: ContainerOps>(
+    runtime: &R,
+    container_id: &ContainerId,
+    probe: &HealthProbe,
+    healthcheck: &HealthcheckConfig,
+) -> HealthPollResult {
+    let first = poll_health_once(runtime, container_id, probe, healthcheck.timeout).await;
+    if !matches!(first, HealthPollResult::Healthy) {
+        return first;
+    }
+
+    for n in 1..=healthcheck.warmup_requests {
+        tokio::time::sleep(healthcheck.warmup_interval).await;
+        let result = poll_health_once(runtime, container_id, probe, healthcheck.timeout).await;
+        if !matches!(result, HealthPollResult::Healthy) {
+            tracing::debug!(
+                "Warm-up request {}/{} failed for {}",
+                n,
+                healthcheck.warmup_requests,
+                container_id
+            );
+            return result;
+        }
+    }
+    HealthPollResult::Healthy
+}
+
+/// Run one checker container to completion, returning None on timeout.
+///
+/// The checker is always removed afterwards, including after a timeout.
+async fn run_checker<R: ContainerOps>(
+    runtime: &R,
+    checker: &ContainerConfig,
+    timeout: Duration,
+) -> Option<Result<bool, ContainerError>> {
+    // A checker left behind by an interrupted run would block the name
+    let _ = runtime
+        .remove_container(&ContainerId::new(checker.name.clone()), true)
+        .await;
+
+    let id = match runtime.create_container(checker).await {
+        Ok(id) => id,
+        Err(e) => return Some(Err(e)),
+    };
+
+    let result = tokio::time::timeout(timeout, async {
+        runtime.start_container(&id).await?;
+        Ok(runtime.wait_container(&id).await? == 0)
+    })
+    .await
+    .ok();
+
+    if let Err(e) = runtime.remove_container(&id, true).await {
+        tracing::debug!("Failed to remove healthcheck container {}: {}", id, e);
+    }
+
+    result
+}
+
+/// Explain a health check command that cannot run in the container.
+fn missing_executable_reason(name: &str, cmd: &str) -> String {
+    if name == "sh" {
+        format!(
+            "container has no `sh`, which healthcheck `{}` needs for its shell syntax; \
+             use a plain command (e.g. `/app/healthcheck`) that can run without a shell",
+            cmd
+        )
+    } else {
+        format!(
+            "healthcheck needs `{}`, which is not installed in the container",
+            name
+        )
     }
 }
 
@@ -53,16 +161,22 @@ impl<S> Deployment<S> {
     fn container_name(&self) -> String {
         // Use blue/green naming for zero-downtime deployment
         // The actual state (active/previous) is tracked via labels
-        let suffix = if self.old_container.is_some() {
+        format!("{}-{}", self.config.service, self.slot())
+    }
+
+    /// Deployment slot (blue/green, or shadow) for the new container.
+    fn slot(&self) -> &'static str {
+        if self.shadow {
+            "shadow"
+        } else if self.old_container.is_some() {
             "green"
         } else {
             "blue"
-        };
-        format!("{}-{}", self.config.service, suffix)
+        }
     }
 
     /// Get the network name to use.
-    fn network_name(&self) -> &str {
+    fn network_name(&self) -> String {
         self.config.network_name()
     }
 
@@ -72,6 +186,24 @@ impl<S> Deployment<S> {
     }
 }
 
+impl<S> Deployment<S> {
+    /// Restart the old container if the replace strategy stopped it.
+    ///
+    /// Best effort: failures are logged, since this only runs while already
+    /// handling another error.
+    async fn restore_old_container<R: ContainerOps>(&mut self, runtime: &R) {
+        if !self.old_stopped {
+            return;
+        }
+        if let Some(old_id) = &self.old_container {
+            match runtime.start_container(old_id).await {
+                Ok(()) => self.old_stopped = false,
+                Err(e) => tracing::warn!("Failed to restart previous container {}: {}", old_id, e),
+            }
+        }
+    }
+}
+
 /// Internal helper for rollback - stops and removes a container.
 async fn rollback_container<R: ContainerOps>(
     runtime: &R,
@@ -93,6 +225,41 @@ async fn rollback_container<R: ContainerOps>(
 // =============================================================================
 
 impl Deployment<Initialized> {
+    /// Remove containers left by an interrupted run that hold the name the
+    /// new container will use.
+    ///
+    /// Only containers that never got running and carry another run's deploy
+    /// ID are removed; the old container is left alone. Returns the IDs of
+    /// the removed containers.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if containers cannot be listed or removed.
+    pub async fn remove_leftovers<R: ContainerOps>(
+        &self,
+        runtime: &R,
+    ) -> Result<Vec<ContainerId>, DeployError> {
+        let filters = ContainerFilters::for_service(&self.config.service, true);
+        let containers = runtime
+            .list_containers(&filters)
+            .await
+            .map_err(|e| DeployError::config_error(format!("failed to list containers: {}", e)))?;
+
+        let leftovers = select_leftovers(
+            &containers,
+            &self.container_name(),
+            &self.metadata.id,
+            self.old_container.as_ref(),
+        );
+        for id in &leftovers {
+            runtime
+                .remove_container(id, true)
+                .await
+                .context_container_remove()?;
+        }
+        Ok(leftovers)
+    }
+
     /// Ensure the deployment network exists, creating it if necessary.
     ///
     /// # Returns
@@ -111,26 +278,37 @@ impl Deployment<Initialized> {
         let network_name = self.network_name();
 
         // Check if network already exists
-        if runtime.network_exists(network_name).await.unwrap_or(false) {
+        if runtime.network_exists(&network_name).await.unwrap_or(false) {
             // Network exists, return name as ID (Docker/Podman accept both)
-            return Ok(NetworkId::new(network_name.to_string()));
+            return Ok(NetworkId::new(network_name));
+        }
+
+        // Label the network so it can be traced back to its owning service.
+        // Shared networks belong to no single service.
+        let mut labels = std::collections::HashMap::new();
+        labels.insert("peleka.managed".to_string(), "true".to_string());
+        if !self.config.uses_shared_network() {
+            labels.insert(
+                "peleka.service".to_string(),
+                self.config.service.to_string(),
+            );
         }
 
         // Try to create the network
         let config = RuntimeNetworkConfig {
-            name: network_name.to_string(),
+            name: network_name.clone(),
             driver: Some("bridge".to_string()),
-            labels: std::collections::HashMap::new(),
+            labels,
         };
 
         match runtime.create_network(&config).await {
             Ok(_) => {
                 // Return name as ID for consistency
-                Ok(NetworkId::new(network_name.to_string()))
+                Ok(NetworkId::new(network_name))
             }
             Err(NetworkError::AlreadyExists(_)) => {
                 // Race condition: network was created between check and create
-                Ok(NetworkId::new(network_name.to_string()))
+                Ok(NetworkId::new(network_name))
             }
             Err(e) => Err(DeployError::network_creation_failed(e.to_string())),
         }
@@ -138,82 +316,234 @@ impl Deployment<Initialized> {
 
     /// Pull the container image from the registry.
     ///
-    /// Respects `pull_policy` configuration:
+    /// Respects `pull_policy` configuration (`if-not-present` when
+    /// `podman_auto_update` is in effect):
     /// - `always`: Always pull from registry (default)
     /// - `never`: Skip pulling, use local image only
+    /// - `if-not-present`: Pull only if the image is missing locally
+    /// - `digest-changed`: Pull only if the registry digest differs from
+    ///   the local image's; falls back to pulling if the registry cannot
+    ///   be queried
+    ///
+    /// With `image_archive` set, the archive is loaded instead and the
+    /// registry is never contacted, regardless of `pull_policy`.
     ///
     /// # Errors
     ///
-    /// Returns `DeployError::ImagePullFailed` if the image cannot be pulled,
-    /// or `DeployError::ImagePullTimeout` if the configured timeout is exceeded.
+    /// Returns `DeployError::ImagePullFailed` if the image cannot be pulled
+    /// or loaded, or `DeployError::ImagePullTimeout` if the configured
+    /// timeout is exceeded.
     #[must_use = "deployment state must be used"]
     pub async fn pull_image<R: ImageOps>(
         self,
         runtime: &R,
         auth: Option<&RegistryAuth>,
     ) -> Result<Deployment<ImagePulled>, DeployError> {
-        // Skip pull if policy is Never (for local images)
-        if self.config.pull_policy == PullPolicy::Never {
-            return Ok(Deployment {
-                config: self.config,
-                old_container: self.old_container,
-                state: ImagePulled,
-            });
-        }
-
-        let pull_future = runtime.pull_image(&self.config.image, auth);
+        // Skip pull when the policy says the local image is good enough
+        if self.config.image_archive.is_some()
+            || needs_pull(
+                runtime,
+                &self.config.image,
+                self.config.effective_pull_policy(),
+                auth,
+            )
+            .await?
+        {
+            let pull_future = async {
+                match &self.config.image_archive {
+                    Some(path) => load_archive(runtime, path, &self.config.image).await,
+                    None => runtime
+                        .pull_image(&self.config.image, auth)
+                        .await
+                        .context_image_pull(),
+                }
+            };
 
-        match self.config.image_pull_timeout {
-            Some(timeout) => {
-                tokio::time::timeout(timeout, pull_future)
-                    .await
-                    .map_err(|_| DeployError::image_pull_timeout(timeout.as_secs()))?
-                    .context_image_pull()?;
-            }
-            None => {
-                pull_future.await.context_image_pull()?;
+            match self.config.image_pull_timeout {
+                Some(timeout) => {
+                    tokio::time::timeout(timeout, pull_future)
+                        .await
+                        .map_err(|_| DeployError::image_pull_timeout(timeout.as_secs()))??;
+                }
+                None => {
+                    pull_future.await?;
+                }
             }
         }
 
+        // Record what was actually pulled for PELEKA_IMAGE_DIGEST
+        let mut metadata = self.metadata;
+        metadata.image_digest = local_digest(runtime, &self.config.image).await;
+
         Ok(Deployment {
             config: self.config,
             old_container: self.old_container,
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata,
             state: ImagePulled,
         })
     }
 }
 
+/// Registry digest of the local copy of `image`, if it has one.
+///
+/// Digests are already limited to `image`'s repository, so an image also
+/// pulled under another name never reports that repository's digest.
+async fn local_digest<R: ImageOps>(runtime: &R, image: &ImageRef) -> Option<String> {
+    match runtime.image_digests(image).await {
+        Ok(digests) => digests.into_iter().next(),
+        Err(e) => {
+            tracing::debug!("Could not read digest of {}: {}", image, e);
+            None
+        }
+    }
+}
+
+/// Decide whether the pull policy requires contacting the registry.
+async fn needs_pull<R: ImageOps>(
+    runtime: &R,
+    image: &ImageRef,
+    policy: PullPolicy,
+    auth: Option<&RegistryAuth>,
+) -> Result<bool, DeployError> {
+    match policy {
+        PullPolicy::Always => Ok(true),
+        PullPolicy::Never => Ok(false),
+        PullPolicy::IfNotPresent => Ok(!runtime.image_exists(image).await.context_image_pull()?),
+        PullPolicy::DigestChanged => {
+            let local = runtime.image_digests(image).await.context_image_pull()?;
+            if local.is_empty() {
+                return Ok(true);
+            }
+            match runtime.registry_digest(image, auth).await {
+                Ok(remote) => {
+                    let changed = !local.contains(&remote);
+                    tracing::debug!(
+                        "Registry digest for {} is {} ({})",
+                        image,
+                        remote,
+                        if changed { "changed" } else { "unchanged" }
+                    );
+                    Ok(changed)
+                }
+                Err(e) => {
+                    tracing::warn!("Could not check registry digest for {}: {}", image, e);
+                    Ok(true)
+                }
+            }
+        }
+    }
+}
+
+/// Load an image archive and verify the configured reference now points at it.
+///
+/// The archive's config digest is the image ID the runtime assigns on load,
+/// so comparing the two catches archives built for a different tag.
+async fn load_archive<R: ImageOps>(
+    runtime: &R,
+    path: &Path,
+    image: &ImageRef,
+) -> Result<(), DeployError> {
+    let archive = ImageArchive::open(path).context_image_pull()?;
+    runtime
+        .load_image(&archive.path)
+        .await
+        .context_image_pull()?;
+
+    match runtime.image_id(image).await.context_image_pull()? {
+        Some(id) if id == archive.config_digest => Ok(()),
+        Some(id) => Err(DeployError::image_pull_failed(format!(
+            "{} is {} after loading {}, expected {}",
+            image,
+            id,
+            path.display(),
+            archive.config_digest
+        ))),
+        None => Err(DeployError::image_pull_failed(format!(
+            "{} not found after loading {}; the archive must be tagged {}",
+            image,
+            path.display(),
+            image
+        ))),
+    }
+}
+
 // =============================================================================
 // ImagePulled -> ContainerStarted
 // =============================================================================
 
 impl Deployment<ImagePulled> {
+    /// Stop the old container before starting the new one (replace strategy).
+    ///
+    /// Unlike recreate, the old container is kept so it can be restarted if
+    /// the new one fails to start or pass its health check. The configured
+    /// pre-stop command runs first, as it does during cleanup.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the old container cannot be stopped.
+    #[must_use = "deployment state must be used"]
+    pub async fn stop_old_container<R: ContainerOps + ExecOps>(
+        mut self,
+        runtime: &R,
+    ) -> Result<Self, DeployError> {
+        if let Some(old_id) = &self.old_container {
+            if let Some(stop) = self.config.stop.as_ref() {
+                run_pre_stop(runtime, old_id, stop).await;
+            }
+            match runtime
+                .stop_container(old_id, self.config.stop_timeout())
+                .await
+            {
+                Ok(()) | Err(ContainerError::NotRunning(_)) => {}
+                Err(e) => return Err(e).context_container_stop(),
+            }
+            self.old_stopped = true;
+        }
+        Ok(self)
+    }
+
     /// Create and start a new container.
     ///
+    /// If the replace strategy stopped the old container, it is restarted
+    /// when the new one fails to come up.
+    ///
     /// # Errors
     ///
     /// Returns error if container creation or start fails.
     #[must_use = "deployment state must be used"]
     pub async fn start_container<R: ContainerOps + NetworkOps>(
-        self,
+        mut self,
         runtime: &R,
     ) -> Result<Deployment<ContainerStarted>, DeployError> {
         let config = self.build_container_config()?;
-        let container_id = runtime
+        let container_id = match runtime
             .create_container(&config)
             .await
-            .context_container_create()?;
+            .context_container_create()
+        {
+            Ok(id) => id,
+            Err(e) => {
+                self.restore_old_container(runtime).await;
+                return Err(e);
+            }
+        };
 
         // Start the container
         if let Err(e) = runtime.start_container(&container_id).await {
             // Clean up the created container on start failure
             let _ = runtime.remove_container(&container_id, true).await;
+            self.restore_old_container(runtime).await;
             return Err(DeployError::container_start_failed(e.to_string()));
         }
 
         Ok(Deployment {
             config: self.config,
             old_container: self.old_container,
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata: self.metadata,
             state: ContainerStarted(container_id),
         })
     }
@@ -227,12 +557,18 @@ impl Deployment<ImagePulled> {
         );
         labels.insert("peleka.managed".to_string(), "true".to_string());
         // Track deployment slot (blue/green) for zero-downtime deployment
-        let slot = if self.old_container.is_some() {
-            "green"
-        } else {
-            "blue"
-        };
-        labels.insert("peleka.slot".to_string(), slot.to_string());
+        labels.insert("peleka.slot".to_string(), self.slot().to_string());
+        labels.insert(DEPLOY_ID_LABEL.to_string(), self.metadata.id.clone());
+        // Shadow containers are throwaway; Podman must never restart them
+        if let Some(policy) = self.config.auto_update_policy().filter(|_| !self.shadow) {
+            labels.insert(AUTO_UPDATE_LABEL.to_string(), policy.as_str().to_string());
+        }
+        if let Some(summary) = self.config.env_override_summary() {
+            labels.insert(ENV_OVERRIDES_LABEL.to_string(), summary);
+        }
+        if self.shadow {
+            labels.insert("peleka.shadow".to_string(), "true".to_string());
+        }
 
         // Parse volumes from config
         let volumes: Vec<VolumeMount> = self
@@ -242,44 +578,52 @@ impl Deployment<ImagePulled> {
             .filter_map(|v| parse_volume_mount(v))
             .collect();
 
-        // Parse port mappings
-        let ports = self
-            .config
-            .ports
-            .iter()
-            .filter_map(|p| parse_port_mapping(p))
-            .collect();
+        // Parse port mappings. Shadow containers publish nothing so they can
+        // run alongside whatever currently owns the host ports.
+        let ports = if self.shadow {
+            Vec::new()
+        } else {
+            self.config
+                .ports
+                .iter()
+                .filter_map(|p| parse_port_mapping(p))
+                .collect()
+        };
 
-        // Resolve environment variables (fails if required var is missing)
-        let env = resolve_env_map(&self.config.env)
+        // Resolve environment variables (fails if required var is missing).
+        // Deploy metadata is set last so applications can rely on it.
+        let mut env = resolve_env_map(&self.config.env)
             .map_err(|e| DeployError::config_error(e.to_string()))?;
+        env.extend(self.metadata.env(self.slot()));
 
         // Convert restart policy
-        let restart_policy = match &self.config.restart {
-            crate::config::RestartPolicy::No => RestartPolicyConfig::No,
-            crate::config::RestartPolicy::Always => RestartPolicyConfig::Always,
-            crate::config::RestartPolicy::UnlessStopped => RestartPolicyConfig::UnlessStopped,
-            crate::config::RestartPolicy::OnFailure { max_retries } => {
-                RestartPolicyConfig::OnFailure {
-                    max_retries: *max_retries,
-                }
-            }
-        };
+        let restart_policy = self.config.restart.to_runtime();
 
-        // Convert healthcheck config - use user-provided command directly
-        let healthcheck = self.config.healthcheck.as_ref().map(|hc| {
-            let test = vec!["CMD-SHELL".to_string(), hc.cmd.clone()];
-            crate::runtime::HealthcheckConfig {
-                test,
+        // Convert healthcheck config - exec form unless the command needs a shell.
+        // External checks run outside the container, so it gets none.
+        let healthcheck = self
+            .config
+            .healthcheck
+            .as_ref()
+            .filter(|hc| hc.external.is_none())
+            .map(|hc| crate::runtime::HealthcheckConfig {
+                test: hc.container_test(),
                 interval: hc.interval,
                 timeout: hc.timeout,
                 retries: hc.retries,
                 start_period: hc.start_period,
-            }
-        });
+            });
+
+        // Network aliases - include service name for discovery. Shadow
+        // containers never take the alias, so no traffic reaches them.
+        let network_aliases = if self.shadow {
+            Vec::new()
+        } else {
+            vec![self.service_alias()]
+        };
 
-        // Network aliases - include service name for discovery
-        let network_aliases = vec![self.service_alias()];
+        // Expand deploy-time template variables in command/args
+        let (entrypoint, command) = self.resolve_command()?;
 
         Ok(ContainerConfig {
             name: self.container_name(),
@@ -288,8 +632,10 @@ impl Deployment<ImagePulled> {
             labels,
             ports,
             volumes,
-            command: self.config.command.clone(),
-            entrypoint: None,
+            tmpfs: self.config.tmpfs_mounts(),
+            read_only: self.config.read_only,
+            command,
+            entrypoint,
             working_dir: None,
             user: None,
             restart_policy,
@@ -298,21 +644,47 @@ impl Deployment<ImagePulled> {
                 .resources
                 .as_ref()
                 .map(|r| crate::runtime::ResourceLimits {
-                    memory: r.memory.as_ref().and_then(|m| parse_memory_string(m)),
+                    memory: r.memory_bytes(),
                     cpus: r.cpus.as_ref().and_then(|c| c.parse().ok()),
                 }),
             healthcheck,
             stop_timeout: self.config.stop.as_ref().map(|s| s.timeout),
-            network: self
-                .config
-                .network
-                .as_ref()
-                .map(|_| self.network_name().to_string()),
+            network: self.config.network.as_ref().map(|_| self.network_name()),
             network_aliases,
         })
     }
 }
 
+/// Entrypoint and CMD overrides for the container; `None` keeps the image's.
+type CommandOverrides = (Option<Vec<String>>, Option<Vec<String>>);
+
+impl<S> Deployment<S> {
+    /// Resolve `command`/`args` into (entrypoint, cmd) with templates expanded.
+    ///
+    /// With `args` set, `command` replaces the image ENTRYPOINT and `args`
+    /// replaces CMD. With only `command` set, it replaces CMD as before.
+    fn resolve_command(&self) -> Result<CommandOverrides, DeployError> {
+        let mut vars = TemplateVars::for_config(&self.config);
+        vars.set("slot", self.slot());
+
+        let render = |items: &Option<Vec<String>>| {
+            items
+                .as_deref()
+                .map(|items| vars.render_all(items))
+                .transpose()
+                .map_err(DeployError::config_error)
+        };
+
+        let command = render(&self.config.command)?;
+        let args = render(&self.config.args)?;
+
+        Ok(match args {
+            Some(args) => (command, Some(args)),
+            None => (None, command),
+        })
+    }
+}
+
 // =============================================================================
 // ContainerStarted -> HealthChecked
 // =============================================================================
@@ -329,7 +701,7 @@ impl Deployment<ContainerStarted> {
     ///
     /// Returns `(self, error)` on failure to allow rollback.
     #[must_use = "deployment state must be used"]
-    pub async fn health_check<R: ContainerOps>(
+    pub async fn health_check<R: ContainerOps + ImageOps>(
         self,
         runtime: &R,
         timeout: Duration,
@@ -343,19 +715,31 @@ impl Deployment<ContainerStarted> {
                 return Ok(Deployment {
                     config: self.config,
                     old_container: self.old_container,
+                    shadow: self.shadow,
+                    old_stopped: self.old_stopped,
+                    metadata: self.metadata,
                     state: HealthChecked(self.state.0),
                 });
             }
         };
 
-        // Build the healthcheck command: ["sh", "-c", cmd]
-        let healthcheck_cmd = vec!["sh".to_string(), "-c".to_string(), healthcheck.cmd.clone()];
+        // Plain commands run directly; shell syntax goes through ["sh", "-c", cmd]
+        let probe = match &healthcheck.external {
+            Some(external) => match self.prepare_checker(runtime, external).await {
+                Ok(checker) => HealthProbe::External(Box::new(checker)),
+                Err(e) => return Err((self, e)),
+            },
+            None => HealthProbe::Exec(healthcheck.exec_args()),
+        };
         let poll_interval = healthcheck.interval;
 
         // Helper to create the success state transition
         let succeed = || Deployment {
             config: self.config.clone(),
             old_container: self.old_container.clone(),
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata: self.metadata.clone(),
             state: HealthChecked(self.state.0.clone()),
         };
 
@@ -365,11 +749,14 @@ impl Deployment<ContainerStarted> {
             let deadline = std::time::Instant::now() + healthcheck.start_period;
 
             while std::time::Instant::now() < deadline {
-                if let HealthPollResult::Healthy =
-                    poll_health_once(runtime, container_id, &healthcheck_cmd, healthcheck.timeout)
-                        .await
-                {
-                    return Ok(succeed());
+                match poll_until_warm(runtime, container_id, &probe, healthcheck).await {
+                    HealthPollResult::Healthy => return Ok(succeed()),
+                    // Retrying cannot make a missing binary appear
+                    HealthPollResult::MissingExecutable(name) => {
+                        let reason = missing_executable_reason(&name, &healthcheck.cmd);
+                        return Err((self, DeployError::health_check_failed(reason)));
+                    }
+                    _ => {}
                 }
                 tokio::time::sleep(poll_interval).await;
             }
@@ -380,19 +767,17 @@ impl Deployment<ContainerStarted> {
         let mut retries_remaining = healthcheck.retries;
 
         while start.elapsed() < timeout {
-            let failure_reason = match poll_health_once(
-                runtime,
-                container_id,
-                &healthcheck_cmd,
-                healthcheck.timeout,
-            )
-            .await
-            {
-                HealthPollResult::Healthy => return Ok(succeed()),
-                HealthPollResult::Unhealthy => "container reported unhealthy".to_string(),
-                HealthPollResult::ExecFailed(e) => format!("healthcheck exec failed: {}", e),
-                HealthPollResult::Timeout => "healthcheck command timed out".to_string(),
-            };
+            let failure_reason =
+                match poll_until_warm(runtime, container_id, &probe, healthcheck).await {
+                    HealthPollResult::Healthy => return Ok(succeed()),
+                    HealthPollResult::Unhealthy => "container reported unhealthy".to_string(),
+                    HealthPollResult::ExecFailed(e) => format!("healthcheck exec failed: {}", e),
+                    HealthPollResult::Timeout => "healthcheck command timed out".to_string(),
+                    HealthPollResult::MissingExecutable(name) => {
+                        let reason = missing_executable_reason(&name, &healthcheck.cmd);
+                        return Err((self, DeployError::health_check_failed(reason)));
+                    }
+                };
 
             if retries_remaining == 0 {
                 return Err((self, DeployError::health_check_failed(failure_reason)));
@@ -404,21 +789,109 @@ impl Deployment<ContainerStarted> {
         Err((self, DeployError::health_check_timeout(timeout.as_secs())))
     }
 
-    /// Rollback: stop and remove the new container.
+    /// Pull the checker image and build a checker container aimed at the
+    /// new container's address on its network.
+    async fn prepare_checker<R: ContainerOps + ImageOps>(
+        &self,
+        runtime: &R,
+        external: &ExternalHealthcheck,
+    ) -> Result<ContainerConfig, DeployError> {
+        if !runtime
+            .image_exists(&external.image)
+            .await
+            .context_image_pull()?
+        {
+            runtime
+                .pull_image(&external.image, None)
+                .await
+                .context_image_pull()?;
+        }
+
+        let info = runtime
+            .inspect_container(self.state.container_id())
+            .await
+            .map_err(|e| {
+                DeployError::health_check_failed(format!("cannot inspect new container: {}", e))
+            })?;
+
+        // Prefer the service network, fall back to whatever the container is
+        // on; networks without an address are skipped
+        let networks = &info.network_settings.networks;
+        let (network, host) = networks
+            .get_key_value(&self.network_name())
+            .into_iter()
+            .chain(networks.iter())
+            .find(|(_, net)| !net.ip_address.is_empty())
+            .map(|(name, net)| (name.clone(), net.ip_address.clone()))
+            .ok_or_else(|| {
+                DeployError::health_check_failed(
+                    "new container has no network address for the external healthcheck".to_string(),
+                )
+            })?;
+
+        let mut vars = TemplateVars::for_config(&self.config);
+        vars.set("host", host.clone());
+        let args = external
+            .exec_args(&vars)
+            .map_err(DeployError::config_error)?;
+
+        // Ephemeral, so a checker left by a killed run is cleaned up later
+        let labels = HashMap::from([
+            (
+                "peleka.healthcheck".to_string(),
+                self.config.service.to_string(),
+            ),
+            (
+                "peleka.service".to_string(),
+                self.config.service.to_string(),
+            ),
+            (EPHEMERAL_LABEL.to_string(), "true".to_string()),
+            (RUN_ID_LABEL.to_string(), self.metadata.id.clone()),
+        ]);
+
+        Ok(ContainerConfig {
+            name: format!("{}-check", self.container_name()),
+            image: external.image.clone(),
+            env: HashMap::from([("PELEKA_HEALTHCHECK_HOST".to_string(), host)]),
+            labels,
+            ports: Vec::new(),
+            volumes: Vec::new(),
+            tmpfs: HashMap::new(),
+            read_only: false,
+            command: None,
+            // Replace the image entrypoint so the checker runs exactly `cmd`
+            entrypoint: Some(args),
+            working_dir: None,
+            user: None,
+            restart_policy: RestartPolicyConfig::No,
+            resources: None,
+            healthcheck: None,
+            stop_timeout: None,
+            network: Some(network),
+            network_aliases: Vec::new(),
+        })
+    }
+
+    /// Rollback: stop and remove the new container, restarting the old one
+    /// if the replace strategy stopped it.
     ///
     /// # Errors
     ///
     /// Returns error if cleanup fails.
     #[must_use = "deployment state must be used"]
     pub async fn rollback<R: ContainerOps>(
-        self,
+        mut self,
         runtime: &R,
     ) -> Result<Deployment<Initialized>, DeployError> {
         let stop_timeout = self.config.stop_timeout();
         rollback_container(runtime, self.state.container_id(), stop_timeout).await?;
+        self.restore_old_container(runtime).await;
         Ok(Deployment {
             config: self.config,
             old_container: self.old_container,
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata: self.metadata,
             state: Initialized,
         })
     }
@@ -469,25 +942,33 @@ impl Deployment<HealthChecked> {
         Ok(Deployment {
             config: self.config,
             old_container: self.old_container,
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata: self.metadata,
             state: CutOver(self.state.0),
         })
     }
 
-    /// Rollback: stop and remove the new container.
+    /// Rollback: stop and remove the new container, restarting the old one
+    /// if the replace strategy stopped it.
     ///
     /// # Errors
     ///
     /// Returns error if cleanup fails.
     #[must_use = "deployment state must be used"]
     pub async fn rollback<R: ContainerOps>(
-        self,
+        mut self,
         runtime: &R,
     ) -> Result<Deployment<Initialized>, DeployError> {
         let stop_timeout = self.config.stop_timeout();
         rollback_container(runtime, self.state.container_id(), stop_timeout).await?;
+        self.restore_old_container(runtime).await;
         Ok(Deployment {
             config: self.config,
             old_container: self.old_container,
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata: self.metadata,
             state: Initialized,
         })
     }
@@ -508,11 +989,12 @@ impl Deployment<CutOver> {
     ///
     /// Returns error if cleanup fails.
     #[must_use = "deployment state must be used"]
-    pub async fn cleanup<R: ContainerOps>(
+    pub async fn cleanup<R: ContainerOps + ExecOps>(
         self,
         runtime: &R,
     ) -> Result<Deployment<Completed>, DeployError> {
-        if let Some(old_container_id) = &self.old_container {
+        // With the replace strategy the old container is already stopped
+        if let Some(old_container_id) = self.old_container.as_ref().filter(|_| !self.old_stopped) {
             // Wait for grace period to allow in-flight requests to complete
             let grace_period = self
                 .config
@@ -525,6 +1007,11 @@ impl Deployment<CutOver> {
                 tokio::time::sleep(grace_period).await;
             }
 
+            // Let the old container wind down (drain queues, close listeners)
+            if let Some(stop) = self.config.stop.as_ref() {
+                run_pre_stop(runtime, old_container_id, stop).await;
+            }
+
             // Stop with configured timeout or default
             let stop_timeout = self
                 .config
@@ -546,11 +1033,49 @@ impl Deployment<CutOver> {
         Ok(Deployment {
             config: self.config,
             old_container: self.old_container,
+            shadow: self.shadow,
+            old_stopped: self.old_stopped,
+            metadata: self.metadata,
             state: Completed(self.state.0),
         })
     }
 }
 
+/// Run the configured pre-stop command inside the old container.
+///
+/// Failures and timeouts are logged; the container is stopped regardless.
+async fn run_pre_stop<R: ExecOps>(runtime: &R, container: &ContainerId, stop: &StopConfig) {
+    let Some(cmd) = stop.pre_stop_args() else {
+        return;
+    };
+    let exec = ExecConfig {
+        cmd,
+        timeout: Some(stop.pre_stop_timeout),
+        ..Default::default()
+    };
+
+    match tokio::time::timeout(stop.pre_stop_timeout, runtime.exec(container, &exec)).await {
+        Ok(Ok(result)) if result.exit_code == 0 => {}
+        Ok(Ok(result)) => {
+            tracing::warn!(
+                "Pre-stop command exited with code {} in {}",
+                result.exit_code,
+                container
+            );
+        }
+        Ok(Err(e)) => {
+            tracing::warn!("Pre-stop command failed in {}: {}", container, e);
+        }
+        Err(_) => {
+            tracing::warn!(
+                "Pre-stop command timed out after {:?} in {}",
+                stop.pre_stop_timeout,
+                container
+            );
+        }
+    }
+}
+
 // =============================================================================
 // Completed - Terminal State
 // =============================================================================
@@ -628,19 +1153,3 @@ fn parse_port_mapping(spec: &str) -> Option<crate::runtime::PortMapping> {
         _ => None,
     }
 }
-
-/// Parse a memory string like "512m" or "1g" into bytes.
-fn parse_memory_string(spec: &str) -> Option<u64> {
-    let spec = spec.to_lowercase();
-    let (num_str, multiplier) = if spec.ends_with("g") {
-        (&spec[..spec.len() - 1], 1024 * 1024 * 1024)
-    } else if spec.ends_with("m") {
-        (&spec[..spec.len() - 1], 1024 * 1024)
-    } else if spec.ends_with("k") {
-        (&spec[..spec.len() - 1], 1024)
-    } else {
-        (spec.as_str(), 1)
-    };
-
-    num_str.parse::<u64>().ok().map(|n| n * multiplier)
-}
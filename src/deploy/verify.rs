@@ -0,0 +1,314 @@
+// ABOUTME: Read-only drift detection between peleka.yml and running containers.
+// ABOUTME: Compares image, network aliases, health and restart policy without mutating anything.
+
+use std::fmt;
+
+use crate::config::Config;
+use crate::runtime::{
+    ContainerFilters, ContainerInfo, ContainerOps, ContainerState, HealthState, ImageOps,
+    RestartPolicyConfig,
+};
+
+use super::error::DeployError;
+
+/// A single deviation between the configuration and the running state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// No running container exists for the service.
+    NotRunning,
+    /// The container was created from a different image reference.
+    ImageMismatch { expected: String, actual: String },
+    /// The container's image ID differs from the local image for the configured reference.
+    DigestMismatch { expected: String, actual: String },
+    /// The configured image is not present on the server.
+    ImageMissing { image: String },
+    /// The container is not attached to the service network.
+    NetworkMissing { network: String },
+    /// The service alias is missing on the service network.
+    AliasMissing { network: String, alias: String },
+    /// The container's healthcheck reports unhealthy.
+    Unhealthy,
+    /// The container's restart policy differs from the configured one.
+    RestartPolicyMismatch {
+        expected: RestartPolicyConfig,
+        actual: Option<RestartPolicyConfig>,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::NotRunning => write!(f, "no running container"),
+            Drift::ImageMismatch { expected, actual } => {
+                write!(f, "image is {} (expected {})", actual, expected)
+            }
+            Drift::DigestMismatch { expected, actual } => {
+                write!(f, "image digest is {} (expected {})", actual, expected)
+            }
+            Drift::ImageMissing { image } => write!(f, "image {} not present on server", image),
+            Drift::NetworkMissing { network } => write!(f, "not attached to network {}", network),
+            Drift::AliasMissing { network, alias } => {
+                write!(f, "alias {} missing on network {}", alias, network)
+            }
+            Drift::Unhealthy => write!(f, "container is unhealthy"),
+            Drift::RestartPolicyMismatch { expected, actual } => match actual {
+                Some(actual) => write!(f, "restart policy is {} (expected {})", actual, expected),
+                None => write!(f, "restart policy absent (expected {})", expected),
+            },
+        }
+    }
+}
+
+/// Result of verifying a service on one server.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Containers that were checked.
+    pub containers: Vec<String>,
+    /// Deviations found, paired with the container name they apply to (if any).
+    pub drifts: Vec<(Option<String>, Drift)>,
+}
+
+impl VerifyReport {
+    /// Returns true if no drift was found.
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+}
+
+/// Verify that the running service matches the configuration.
+///
+/// Only read operations are issued against the runtime: no images are pulled
+/// and no containers or networks are touched.
+pub async fn verify_service<R: ContainerOps + ImageOps>(
+    runtime: &R,
+    config: &Config,
+) -> Result<VerifyReport, DeployError> {
+    let filters = ContainerFilters::for_service(&config.service, false);
+    let containers = runtime
+        .list_containers(&filters)
+        .await
+        .map_err(|e| DeployError::config_error(format!("failed to list containers: {}", e)))?;
+    let containers: Vec<_> = containers
+        .into_iter()
+        .filter(|c| c.labels.get("peleka.shadow").map(String::as_str) != Some("true"))
+        .collect();
+
+    let mut report = VerifyReport::default();
+    if containers.is_empty() {
+        report.drifts.push((None, Drift::NotRunning));
+        return Ok(report);
+    }
+
+    let expected_image_id = runtime
+        .image_id(&config.image)
+        .await
+        .map_err(|e| DeployError::config_error(format!("failed to inspect image: {}", e)))?;
+
+    for summary in containers {
+        let info = runtime.inspect_container(&summary.id).await.map_err(|e| {
+            DeployError::config_error(format!("failed to inspect container: {}", e))
+        })?;
+        for drift in check_container(config, &info, expected_image_id.as_deref()) {
+            report.drifts.push((Some(info.name.clone()), drift));
+        }
+        report.containers.push(info.name);
+    }
+
+    Ok(report)
+}
+
+/// Compare a single container against the configuration.
+///
+/// `expected_image_id` is the local image ID for the configured reference,
+/// or None if the image is not present on the server.
+pub fn check_container(
+    config: &Config,
+    info: &ContainerInfo,
+    expected_image_id: Option<&str>,
+) -> Vec<Drift> {
+    let mut drifts = Vec::new();
+
+    if info.state != ContainerState::Running {
+        drifts.push(Drift::NotRunning);
+    }
+
+    let expected_image = config.image.to_string();
+    if info.image != expected_image {
+        drifts.push(Drift::ImageMismatch {
+            expected: expected_image,
+            actual: info.image.clone(),
+        });
+    } else {
+        match expected_image_id {
+            Some(id) if id != info.image_id => drifts.push(Drift::DigestMismatch {
+                expected: id.to_string(),
+                actual: info.image_id.clone(),
+            }),
+            Some(_) => {}
+            None => drifts.push(Drift::ImageMissing {
+                image: expected_image,
+            }),
+        }
+    }
+
+    let network = config.network_name();
+    match info.network_settings.networks.get(&network) {
+        Some(net) => {
+            let alias = config.service.to_string();
+            if !net.aliases.contains(&alias) {
+                drifts.push(Drift::AliasMissing { network, alias });
+            }
+        }
+        None => drifts.push(Drift::NetworkMissing { network }),
+    }
+
+    if info.health == Some(HealthState::Unhealthy) {
+        drifts.push(Drift::Unhealthy);
+    }
+
+    let expected = config.restart.to_runtime();
+    if !restart_policy_matches(&expected, info.restart_policy.as_ref()) {
+        drifts.push(Drift::RestartPolicyMismatch {
+            expected,
+            actual: info.restart_policy.clone(),
+        });
+    }
+
+    drifts
+}
+
+/// Compare restart policies, ignoring the retry limit unless one is configured.
+fn restart_policy_matches(
+    expected: &RestartPolicyConfig,
+    actual: Option<&RestartPolicyConfig>,
+) -> bool {
+    match (expected, actual) {
+        (RestartPolicyConfig::No, None) => true,
+        (
+            RestartPolicyConfig::OnFailure { max_retries: None },
+            Some(RestartPolicyConfig::OnFailure { .. }),
+        ) => true,
+        (expected, Some(actual)) => expected == actual,
+        (_, None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::traits::{NetworkInfo, NetworkSettings};
+    use crate::types::ContainerId;
+    use std::collections::HashMap;
+
+    fn config() -> Config {
+        Config::template()
+    }
+
+    fn matching_container(config: &Config) -> ContainerInfo {
+        let mut networks = HashMap::new();
+        networks.insert(
+            config.network_name(),
+            NetworkInfo {
+                network_id: "net1".to_string(),
+                ip_address: "10.0.0.2".to_string(),
+                gateway: "10.0.0.1".to_string(),
+                aliases: vec![config.service.to_string()],
+            },
+        );
+        ContainerInfo {
+            id: ContainerId::new("abc123".to_string()),
+            name: "my-app-blue".to_string(),
+            image: config.image.to_string(),
+            image_id: "sha256:aaa".to_string(),
+            state: ContainerState::Running,
+            health: Some(HealthState::Healthy),
+            created: String::new(),
+            labels: HashMap::new(),
+            network_settings: NetworkSettings { networks },
+            restart_policy: Some(config.restart.to_runtime()),
+            restart_count: 0,
+            oom_killed: false,
+            exit_code: Some(0),
+        }
+    }
+
+    #[test]
+    fn matching_container_has_no_drift() {
+        let config = config();
+        let info = matching_container(&config);
+        assert!(check_container(&config, &info, Some("sha256:aaa")).is_empty());
+    }
+
+    #[test]
+    fn detects_digest_mismatch() {
+        let config = config();
+        let info = matching_container(&config);
+        let drifts = check_container(&config, &info, Some("sha256:bbb"));
+        assert_eq!(
+            drifts,
+            vec![Drift::DigestMismatch {
+                expected: "sha256:bbb".to_string(),
+                actual: "sha256:aaa".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn detects_image_mismatch() {
+        let config = config();
+        let mut info = matching_container(&config);
+        info.image = "my-registry/my-app:previous".to_string();
+        let drifts = check_container(&config, &info, Some("sha256:aaa"));
+        assert!(matches!(drifts[0], Drift::ImageMismatch { .. }));
+    }
+
+    #[test]
+    fn detects_missing_alias() {
+        let config = config();
+        let mut info = matching_container(&config);
+        for net in info.network_settings.networks.values_mut() {
+            net.aliases.clear();
+        }
+        let drifts = check_container(&config, &info, Some("sha256:aaa"));
+        assert!(matches!(drifts[0], Drift::AliasMissing { .. }));
+    }
+
+    #[test]
+    fn detects_unhealthy_container() {
+        let config = config();
+        let mut info = matching_container(&config);
+        info.health = Some(HealthState::Unhealthy);
+        let drifts = check_container(&config, &info, Some("sha256:aaa"));
+        assert_eq!(drifts, vec![Drift::Unhealthy]);
+    }
+
+    #[test]
+    fn detects_absent_restart_policy() {
+        let config = config();
+        let mut info = matching_container(&config);
+        info.restart_policy = None;
+        let drifts = check_container(&config, &info, Some("sha256:aaa"));
+        assert!(matches!(
+            drifts[0],
+            Drift::RestartPolicyMismatch { actual: None, .. }
+        ));
+    }
+
+    #[test]
+    fn on_failure_without_limit_matches_any_limit() {
+        assert!(restart_policy_matches(
+            &RestartPolicyConfig::OnFailure { max_retries: None },
+            Some(&RestartPolicyConfig::OnFailure {
+                max_retries: Some(5)
+            }),
+        ));
+        assert!(!restart_policy_matches(
+            &RestartPolicyConfig::OnFailure {
+                max_retries: Some(3)
+            },
+            Some(&RestartPolicyConfig::OnFailure {
+                max_retries: Some(5)
+            }),
+        ));
+    }
+}
@@ -41,6 +41,14 @@ impl Warning {
         }
     }
 
+    /// Create a clock skew warning.
+    pub fn clock_skew(message: impl Into<String>) -> Self {
+        Self {
+            kind: WarningKind::ClockSkew,
+            message: message.into(),
+        }
+    }
+
     /// Create an SSH disconnect warning.
     pub fn ssh_disconnect(message: impl Into<String>) -> Self {
         Self {
@@ -57,6 +65,8 @@ pub enum WarningKind {
     LockRelease,
     /// Failed to cleanly disconnect SSH session.
     SshDisconnect,
+    /// Server clock differs noticeably from the local clock.
+    ClockSkew,
 }
 
 #[cfg(test)]
@@ -88,5 +98,8 @@ mod tests {
 
         let ssh_warning = Warning::ssh_disconnect("test");
         assert_eq!(ssh_warning.kind, WarningKind::SshDisconnect);
+
+        let skew_warning = Warning::clock_skew("test");
+        assert_eq!(skew_warning.kind, WarningKind::ClockSkew);
     }
 }
@@ -0,0 +1,170 @@
+// ABOUTME: Post-cutover service discovery integration.
+// ABOUTME: Registers the new container with Consul, webhooks, or Tailscale via SSH.
+
+use crate::config::DiscoveryConfig;
+use crate::ssh::Session;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Errors from service discovery updates.
+#[derive(Debug, Error)]
+pub enum DiscoveryError {
+    #[error("{provider} registration failed: {message}")]
+    Failed {
+        provider: &'static str,
+        message: String,
+    },
+
+    #[error("SSH error: {0}")]
+    Ssh(#[from] crate::ssh::Error),
+}
+
+/// The endpoint being published to service discovery.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveryTarget {
+    /// Service name.
+    pub service: String,
+    /// Server host the container runs on.
+    pub server: String,
+    /// Container ID of the new deployment.
+    pub container: String,
+    /// Container IP address on the service network.
+    pub address: String,
+    /// Port the service listens on, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+}
+
+/// Update a single discovery provider with the new container's address.
+pub async fn register(
+    session: &Session,
+    provider: &DiscoveryConfig,
+    target: &DiscoveryTarget,
+) -> Result<(), DiscoveryError> {
+    let command = registration_command(provider, target);
+    let output = session.exec(&command).await?;
+
+    if !output.success() {
+        let message = if output.stderr.trim().is_empty() {
+            format!("exit code {}", output.exit_code)
+        } else {
+            output.stderr.trim().to_string()
+        };
+        return Err(DiscoveryError::Failed {
+            provider: provider.kind(),
+            message,
+        });
+    }
+
+    Ok(())
+}
+
+/// Build the shell command that performs the registration on the server.
+pub fn registration_command(provider: &DiscoveryConfig, target: &DiscoveryTarget) -> String {
+    match provider {
+        DiscoveryConfig::Consul {
+            address,
+            port,
+            tags,
+        } => {
+            let mut payload = serde_json::json!({
+                "ID": format!("{}-{}", target.service, target.server),
+                "Name": target.service,
+                "Address": target.address,
+                "Tags": tags,
+            });
+            if let Some(port) = port.or(target.port) {
+                payload["Port"] = serde_json::json!(port);
+            }
+            format!(
+                "curl -fsS -X PUT -H 'Content-Type: application/json' -d {} {}",
+                shell_quote(&payload.to_string()),
+                shell_quote(&format!(
+                    "{}/v1/agent/service/register",
+                    address.trim_end_matches('/')
+                ))
+            )
+        }
+        DiscoveryConfig::Webhook { url, method, port } => {
+            let mut target = target.clone();
+            if port.is_some() {
+                target.port = *port;
+            }
+            let payload = serde_json::to_string(&target).unwrap_or_default();
+            format!(
+                "curl -fsS -X {} -H 'Content-Type: application/json' -d {} {}",
+                shell_quote(method),
+                shell_quote(&payload),
+                shell_quote(url)
+            )
+        }
+        DiscoveryConfig::Tailscale { tags } => {
+            format!(
+                "tailscale set --advertise-tags={}",
+                shell_quote(&tags.join(","))
+            )
+        }
+    }
+}
+
+/// Quote a string for safe use as a single POSIX shell word.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target() -> DiscoveryTarget {
+        DiscoveryTarget {
+            service: "web".to_string(),
+            server: "app1".to_string(),
+            container: "abc123".to_string(),
+            address: "10.89.0.5".to_string(),
+            port: Some(8080),
+        }
+    }
+
+    #[test]
+    fn consul_command_registers_with_agent() {
+        let provider = DiscoveryConfig::Consul {
+            address: "http://127.0.0.1:8500/".to_string(),
+            port: None,
+            tags: vec!["v1".to_string()],
+        };
+        let cmd = registration_command(&provider, &target());
+        assert!(cmd.contains("'http://127.0.0.1:8500/v1/agent/service/register'"));
+        assert!(cmd.contains("\"Address\":\"10.89.0.5\""));
+        assert!(cmd.contains("\"Port\":8080"));
+        assert!(cmd.contains("-X PUT"));
+    }
+
+    #[test]
+    fn webhook_port_override() {
+        let provider = DiscoveryConfig::Webhook {
+            url: "https://dns.example.com/records".to_string(),
+            method: "POST".to_string(),
+            port: Some(9000),
+        };
+        let cmd = registration_command(&provider, &target());
+        assert!(cmd.contains("\"port\":9000"));
+        assert!(cmd.ends_with("'https://dns.example.com/records'"));
+    }
+
+    #[test]
+    fn tailscale_joins_tags() {
+        let provider = DiscoveryConfig::Tailscale {
+            tags: vec!["tag:web".to_string(), "tag:prod".to_string()],
+        };
+        assert_eq!(
+            registration_command(&provider, &target()),
+            "tailscale set --advertise-tags='tag:web,tag:prod'"
+        );
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
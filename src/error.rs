@@ -45,6 +45,12 @@ pub enum Error {
 
     #[error("hook failed: {0}")]
     Hook(String),
+
+    #[error("configuration drift detected on {0} server(s)")]
+    DriftDetected(usize),
+
+    #[error("could not verify {0} server(s)")]
+    VerifyIncomplete(usize),
 }
 
 impl Error {
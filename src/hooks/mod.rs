@@ -1,11 +1,15 @@
 // ABOUTME: Hooks system for deployment lifecycle events.
-// ABOUTME: Discovers and executes shell scripts at pre-deploy, post-deploy, and on-error points.
+// ABOUTME: Discovers and executes shell scripts at deploy lifecycle and health event points.
 
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
+use crate::deploy::DeployStrategy;
 use crate::types::ServiceName;
 
 /// Hook execution points in the deployment lifecycle.
@@ -17,6 +21,8 @@ pub enum HookPoint {
     PostDeploy,
     /// On deployment failure. Failure logs warning.
     OnError,
+    /// When `peleka watch` sees a container turn unhealthy. Failure logs warning.
+    OnUnhealthy,
 }
 
 impl HookPoint {
@@ -26,6 +32,7 @@ impl HookPoint {
             HookPoint::PreDeploy => "pre-deploy",
             HookPoint::PostDeploy => "post-deploy",
             HookPoint::OnError => "on-error",
+            HookPoint::OnUnhealthy => "on-unhealthy",
         }
     }
 
@@ -35,7 +42,7 @@ impl HookPoint {
     }
 }
 
-/// Context passed to hooks via environment variables.
+/// Context passed to hooks via environment variables and, as JSON, on stdin.
 #[derive(Debug, Clone)]
 pub struct HookContext {
     pub service: ServiceName,
@@ -43,6 +50,69 @@ pub struct HookContext {
     pub server: String,
     pub runtime: String,
     pub previous_version: Option<String>,
+    /// Summary of the deployed configuration.
+    pub config: ConfigSummary,
+    /// Results of the phases completed so far in this run.
+    pub phases: Vec<PhaseResult>,
+    /// Time since the run started.
+    pub elapsed: Option<Duration>,
+    /// `unix://` URL of the locally forwarded runtime socket, while the
+    /// server's session is open.
+    pub docker_host: Option<String>,
+}
+
+/// The parts of the configuration hooks most often need.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigSummary {
+    pub network: String,
+    pub ports: Vec<String>,
+    pub strategy: String,
+    pub restart: String,
+    pub healthcheck: Option<String>,
+}
+
+impl From<&crate::config::Config> for ConfigSummary {
+    fn from(config: &crate::config::Config) -> Self {
+        let strategy = match DeployStrategy::for_config(config).0 {
+            DeployStrategy::BlueGreen => "blue-green",
+            DeployStrategy::Recreate => "recreate",
+            DeployStrategy::Replace => "replace",
+        };
+        Self {
+            network: config.network_name(),
+            ports: config.ports.clone(),
+            strategy: strategy.to_string(),
+            restart: config.restart.to_string(),
+            healthcheck: config
+                .healthcheck
+                .as_ref()
+                .map(|hc| hc.command().to_string()),
+        }
+    }
+}
+
+/// Outcome of one phase of a run (e.g. deploying to one server).
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseResult {
+    pub phase: String,
+    pub server: Option<String>,
+    pub success: bool,
+    pub duration_secs: f64,
+    pub error: Option<String>,
+}
+
+/// JSON document written to each hook's stdin.
+#[derive(Serialize)]
+struct HookPayload<'a> {
+    hook: &'static str,
+    service: &'a str,
+    image: &'a str,
+    server: &'a str,
+    runtime: &'a str,
+    previous_version: Option<&'a str>,
+    config: &'a ConfigSummary,
+    phases: &'a [PhaseResult],
+    elapsed_secs: Option<f64>,
 }
 
 impl HookContext {
@@ -54,9 +124,42 @@ impl HookContext {
             server: server.host.clone(),
             runtime: server.runtime_string(),
             previous_version: None,
+            config: ConfigSummary::from(config),
+            phases: Vec::new(),
+            elapsed: None,
+            docker_host: None,
         }
     }
 
+    /// Expose the server's forwarded runtime socket, if one is open.
+    pub fn with_forwarded_socket(mut self, socket: Option<String>) -> Self {
+        self.docker_host = socket.map(|path| format!("unix://{}", path));
+        self
+    }
+
+    /// Attach the phase results and elapsed time of the run so far.
+    pub fn with_phases(mut self, phases: &[PhaseResult], elapsed: Duration) -> Self {
+        self.phases = phases.to_vec();
+        self.elapsed = Some(elapsed);
+        self
+    }
+
+    /// Serialize the full context as the JSON document sent on stdin.
+    pub fn to_json(&self, point: HookPoint) -> String {
+        let payload = HookPayload {
+            hook: point.filename(),
+            service: self.service.as_str(),
+            image: &self.image,
+            server: &self.server,
+            runtime: &self.runtime,
+            previous_version: self.previous_version.as_deref(),
+            config: &self.config,
+            phases: &self.phases,
+            elapsed_secs: self.elapsed.map(|d| d.as_secs_f64()),
+        };
+        serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_string())
+    }
+
     /// Convert context to environment variables.
     pub fn to_env(&self) -> HashMap<String, String> {
         let mut env = HashMap::new();
@@ -67,6 +170,9 @@ impl HookContext {
         if let Some(ref prev) = self.previous_version {
             env.insert("PELEKA_PREVIOUS_VERSION".to_string(), prev.clone());
         }
+        if let Some(ref host) = self.docker_host {
+            env.insert("PELEKA_DOCKER_HOST".to_string(), host.clone());
+        }
         env
     }
 }
@@ -116,13 +222,28 @@ impl HookRunner {
         tracing::info!("Running {} hook: {}", point.filename(), hook_path.display());
 
         let env_vars = context.to_env();
+        let payload = context.to_json(point);
 
-        let output = Command::new(&hook_path)
+        let output = match Command::new(&hook_path)
             .envs(&env_vars)
+            .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
-            .await;
+            .spawn()
+        {
+            Ok(mut child) => {
+                // Write from a separate task so a hook that never reads stdin
+                // can't block on a full pipe. Hooks that ignore stdin close it
+                // early; the resulting broken pipe is not an error.
+                if let Some(mut stdin) = child.stdin.take() {
+                    tokio::spawn(async move {
+                        let _ = stdin.write_all(payload.as_bytes()).await;
+                    });
+                }
+                child.wait_with_output().await
+            }
+            Err(e) => Err(e),
+        };
 
         match output {
             Ok(output) => {
@@ -167,6 +288,7 @@ mod tests {
         assert_eq!(HookPoint::PreDeploy.filename(), "pre-deploy");
         assert_eq!(HookPoint::PostDeploy.filename(), "post-deploy");
         assert_eq!(HookPoint::OnError.filename(), "on-error");
+        assert_eq!(HookPoint::OnUnhealthy.filename(), "on-unhealthy");
     }
 
     #[test]
@@ -184,6 +306,10 @@ mod tests {
             server: "app.example.com".to_string(),
             runtime: "podman".to_string(),
             previous_version: Some("v1.2.2".to_string()),
+            config: ConfigSummary::default(),
+            phases: vec![],
+            elapsed: None,
+            docker_host: None,
         };
 
         let env = context.to_env();
@@ -211,12 +337,71 @@ mod tests {
             server: "localhost".to_string(),
             runtime: "docker".to_string(),
             previous_version: None,
+            config: ConfigSummary::default(),
+            phases: vec![],
+            elapsed: None,
+            docker_host: None,
         };
 
         let env = context.to_env();
         assert!(!env.contains_key("PELEKA_PREVIOUS_VERSION"));
     }
 
+    #[test]
+    fn hook_context_exposes_forwarded_socket() {
+        let context = HookContext {
+            service: ServiceName::new("myapp").unwrap(),
+            image: "myapp:latest".to_string(),
+            server: "localhost".to_string(),
+            runtime: "docker".to_string(),
+            previous_version: None,
+            config: ConfigSummary::default(),
+            phases: vec![],
+            elapsed: None,
+            docker_host: None,
+        };
+        assert!(!context.to_env().contains_key("PELEKA_DOCKER_HOST"));
+
+        let context = context.with_forwarded_socket(Some("/tmp/peleka-42-0.sock".to_string()));
+        assert_eq!(
+            context.to_env().get("PELEKA_DOCKER_HOST"),
+            Some(&"unix:///tmp/peleka-42-0.sock".to_string())
+        );
+    }
+
+    #[test]
+    fn hook_context_to_json() {
+        let context = HookContext {
+            service: ServiceName::new("myapp").unwrap(),
+            image: "myapp:v2".to_string(),
+            server: "app.example.com".to_string(),
+            runtime: "docker".to_string(),
+            previous_version: None,
+            config: ConfigSummary {
+                network: "peleka-myapp".to_string(),
+                strategy: "blue-green".to_string(),
+                ..Default::default()
+            },
+            phases: vec![PhaseResult {
+                phase: "deploy".to_string(),
+                server: Some("app.example.com".to_string()),
+                success: true,
+                duration_secs: 1.5,
+                error: None,
+            }],
+            elapsed: Some(Duration::from_secs(2)),
+            docker_host: None,
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&context.to_json(HookPoint::PostDeploy)).unwrap();
+        assert_eq!(json["hook"], "post-deploy");
+        assert_eq!(json["service"], "myapp");
+        assert_eq!(json["config"]["network"], "peleka-myapp");
+        assert_eq!(json["phases"][0]["success"], true);
+        assert_eq!(json["elapsed_secs"], 2.0);
+    }
+
     #[test]
     fn hook_runner_checks_hooks_dir() {
         let runner = HookRunner::new(Path::new("/nonexistent"));
@@ -4,9 +4,13 @@
 pub mod config;
 pub mod deploy;
 pub mod diagnostics;
+pub mod discovery;
 pub mod error;
 pub mod hooks;
 pub mod output;
 pub mod runtime;
 pub mod ssh;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 pub mod types;
+pub mod version;
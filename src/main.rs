@@ -5,8 +5,9 @@ mod cli;
 mod commands;
 
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, CompletionTarget};
 use peleka::config::{self, Config};
+use peleka::deploy::Release;
 use peleka::error::{Error, Result};
 use peleka::output::{Output, OutputMode};
 use std::env;
@@ -37,6 +38,9 @@ async fn main() {
     };
     let output = Output::new(output_mode);
 
+    // Sockets from crashed runs are never unlinked; clear them before forwarding new ones
+    peleka::ssh::cleanup_stale_sockets(&peleka::ssh::default_runtime_dir());
+
     let result = run(cli, output).await;
 
     if let Err(e) = result {
@@ -64,6 +68,11 @@ fn handle_error(e: Error) -> ! {
                 }
                 std::process::exit(2);
             }
+            DeployErrorKind::LocalLockHeld => {
+                eprintln!("Error: {e}");
+                eprintln!("       Tip: Wait for it to finish; --force does not override this");
+                std::process::exit(2);
+            }
             DeployErrorKind::HealthCheckTimeout => {
                 if let Some(secs) = deploy_err.timeout_seconds() {
                     eprintln!("Error: Health check timed out after {}s", secs);
@@ -96,6 +105,13 @@ fn handle_error(e: Error) -> ! {
                 }
                 std::process::exit(4);
             }
+            DeployErrorKind::InsufficientMemory => {
+                eprintln!("Error: {e}");
+                eprintln!(
+                    "       Tip: Set on_low_memory: replace under resources, or free memory on the server"
+                );
+                std::process::exit(12);
+            }
             _ => {
                 eprintln!("Error: {e}");
                 std::process::exit(1);
@@ -118,10 +134,29 @@ fn handle_error(e: Error) -> ! {
             eprintln!("       Tip: Add servers to peleka.yml");
             std::process::exit(7);
         }
+        Error::DriftDetected(_) => {
+            eprintln!("Error: {e}");
+            eprintln!("       Tip: Run 'peleka deploy' to converge servers to peleka.yml");
+            std::process::exit(11);
+        }
         Error::Runtime(runtime_err) => match runtime_err.kind() {
             RuntimeErrorKind::NoRuntimeFound => {
                 eprintln!("Error: No container runtime found");
-                eprintln!("       Tip: Install Docker or Podman on the target server");
+                match runtime_err.prerequisites() {
+                    Some(prerequisites) => {
+                        eprintln!(
+                            "       Checked: {}",
+                            prerequisites.sockets_checked.join(", ")
+                        );
+                        for finding in prerequisites.findings() {
+                            eprintln!("       Found: {}", finding);
+                        }
+                        for step in prerequisites.remediation() {
+                            eprintln!("       Tip: {}", step);
+                        }
+                    }
+                    None => eprintln!("       Tip: Install Docker or Podman on the target server"),
+                }
                 std::process::exit(8);
             }
             RuntimeErrorKind::ConnectionFailed => {
@@ -154,11 +189,25 @@ async fn run(cli: Cli, output: Output) -> Result<()> {
             let cwd = env::current_dir()?;
             config::init_config(&cwd, service.as_deref(), image.as_deref(), force)
         }
-        Commands::Deploy { destination, force } => {
+        Commands::Deploy {
+            destination,
+            force,
+            shadow,
+            env: env_vars,
+            env_file,
+        } => {
             let cwd = env::current_dir()?;
-            let config =
-                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
-            commands::deploy(config, force, output).await
+            let mut overrides = Vec::new();
+            for path in &env_file {
+                overrides.extend(config::read_env_file(path)?);
+            }
+            for var in &env_vars {
+                overrides.push(config::parse_env_assignment(var)?);
+            }
+            let config = Config::discover(&cwd)?
+                .with_optional_destination(destination.as_deref())?
+                .with_env_overrides(overrides);
+            commands::deploy(config, destination.as_deref(), force, shadow, output).await
         }
         Commands::Rollback { destination } => {
             let cwd = env::current_dir()?;
@@ -166,6 +215,18 @@ async fn run(cli: Cli, output: Output) -> Result<()> {
                 Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
             commands::rollback(config, output).await
         }
+        Commands::Verify { destination } => {
+            let cwd = env::current_dir()?;
+            let config =
+                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
+            commands::verify(config, output).await
+        }
+        Commands::Watch { destination } => {
+            let cwd = env::current_dir()?;
+            let config =
+                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
+            commands::watch(config, output).await
+        }
         Commands::Exec {
             destination,
             command,
@@ -175,5 +236,70 @@ async fn run(cli: Cli, output: Output) -> Result<()> {
                 Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
             commands::exec_command(config, command, output).await
         }
+        Commands::Logs {
+            destination,
+            previous,
+            follow,
+            tail,
+        } => {
+            let cwd = env::current_dir()?;
+            let config =
+                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
+            let args = commands::LogsArgs {
+                release: release(previous),
+                follow,
+                tail,
+            };
+            commands::logs(config, args, output).await
+        }
+        Commands::Ping { destination } => {
+            let cwd = env::current_dir()?;
+            let config =
+                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
+            commands::ping(config, output).await
+        }
+        Commands::Inspect {
+            destination,
+            previous,
+        } => {
+            let cwd = env::current_dir()?;
+            let config =
+                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
+            commands::inspect(config, release(previous), output).await
+        }
+        Commands::DiffFs {
+            destination,
+            previous,
+            ignore,
+        } => {
+            let cwd = env::current_dir()?;
+            let config =
+                Config::discover(&cwd)?.with_optional_destination(destination.as_deref())?;
+            commands::diff_fs(config, release(previous), ignore, output).await
+        }
+        Commands::Completions { shell } => {
+            commands::completions(shell, output);
+            Ok(())
+        }
+        Commands::Version { check } => {
+            let cwd = env::current_dir()?;
+            commands::version(check, &cwd, output).await
+        }
+        Commands::Complete {
+            target: CompletionTarget::Destinations,
+        } => {
+            let cwd = env::current_dir()?;
+            commands::complete_destinations(&cwd, output);
+            Ok(())
+        }
+    }
+}
+
+/// Map a `--previous` flag to the release it selects.
+fn release(previous: bool) -> Release {
+    if previous {
+        Release::Previous
+    } else {
+        Release::Active
     }
 }
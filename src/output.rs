@@ -1,7 +1,11 @@
 // ABOUTME: Output formatting for CLI feedback.
-// ABOUTME: Supports normal, quiet (CI), and JSON output modes.
+// ABOUTME: Supports normal, quiet (CI), and JSON output modes via a single writer thread.
 
 use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Sender};
+use std::thread::JoinHandle;
 use std::time::Instant;
 
 /// Output mode for CLI feedback.
@@ -15,20 +19,129 @@ pub enum OutputMode {
     Json,
 }
 
+/// Status of a command phase on one server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhaseStatus {
+    /// The phase has begun.
+    Started,
+    /// The phase completed successfully.
+    Ok,
+    /// The phase failed.
+    Failed,
+}
+
+/// Stream a command's output came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
 /// Handles CLI output based on the configured mode.
+///
+/// Cloning is cheap and clones share one writer thread, which receives
+/// whole lines over a channel and writes them in order. Concurrent tasks
+/// (parallel deploys, log streams) therefore never interleave partial lines,
+/// and every JSON event reaches stdout as a complete line. Pending output is
+/// flushed when the last clone is dropped.
+#[derive(Clone)]
 pub struct Output {
     mode: OutputMode,
     start_time: Option<Instant>,
+    /// Server name and ANSI color prepended to human-readable lines.
+    prefix: Option<Prefix>,
+    color: bool,
+    writer: Arc<Writer>,
 }
 
+#[derive(Clone)]
+struct Prefix {
+    server: Arc<str>,
+    color: u8,
+}
+
+/// ANSI foreground colors cycled through for server prefixes.
+const PREFIX_COLORS: [u8; 6] = [36, 33, 35, 32, 34, 31];
+
 impl Output {
     pub fn new(mode: OutputMode) -> Self {
+        let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        Self::with_writer(
+            mode,
+            color,
+            Writer::spawn(Box::new(std::io::stdout()), Box::new(std::io::stderr())),
+        )
+    }
+
+    fn with_writer(mode: OutputMode, color: bool, writer: Writer) -> Self {
         Self {
             mode,
             start_time: None,
+            prefix: None,
+            color,
+            writer: Arc::new(writer),
+        }
+    }
+
+    /// Output for work on one server.
+    ///
+    /// Shares the writer (and timer) with `self`; in normal mode every
+    /// progress, phase, warning and error line is prefixed with `[server]`,
+    /// colored per server when stdout is a terminal. JSON events carry the
+    /// server in a `server` field instead.
+    pub fn for_server(&self, server: &str) -> Self {
+        let color = server
+            .bytes()
+            .fold(0usize, |acc, b| acc.wrapping_mul(31).wrapping_add(b.into()));
+        Self {
+            prefix: Some(Prefix {
+                server: server.into(),
+                color: PREFIX_COLORS[color % PREFIX_COLORS.len()],
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Prepend the server prefix, if any, to a human-readable line.
+    fn prefixed(&self, line: &str) -> String {
+        match &self.prefix {
+            Some(prefix) if self.color => {
+                format!("\x1b[{}m[{}]\x1b[0m {}", prefix.color, prefix.server, line)
+            }
+            Some(prefix) => format!("[{}] {}", prefix.server, line),
+            None => line.to_string(),
         }
     }
 
+    fn server(&self) -> Option<&str> {
+        self.prefix.as_ref().map(|p| &*p.server)
+    }
+
+    /// Queue a complete line for stdout.
+    fn out(&self, line: impl Into<String>) {
+        self.writer.send(Stream::Stdout, line.into() + "\n");
+    }
+
+    /// Queue a complete line for stderr.
+    fn err(&self, line: impl Into<String>) {
+        self.writer.send(Stream::Stderr, line.into() + "\n");
+    }
+
+    /// Print a line to stdout verbatim in every mode, e.g. inspect details.
+    pub fn line(&self, line: &str) {
+        self.out(line);
+    }
+
+    /// Write text verbatim in every mode, e.g. log lines or inspect output.
+    ///
+    /// Use this instead of `print!` so the text stays ordered with other
+    /// output from the same command.
+    pub fn write(&self, stream: Stream, data: &str) {
+        self.writer.send(stream, data.to_string());
+    }
+
     /// Start timing an operation.
     pub fn start_timer(&mut self) {
         self.start_time = Some(Instant::now());
@@ -44,7 +157,108 @@ impl Output {
     /// Print a progress message (suppressed in quiet/json mode).
     pub fn progress(&self, message: &str) {
         if self.mode == OutputMode::Normal {
-            println!("{message}");
+            self.out(self.prefixed(message));
+        }
+    }
+
+    /// Report a phase of a mutating command on one server.
+    ///
+    /// In normal mode `message` is printed as an indented progress line
+    /// marked by status (failures go to stderr). In JSON mode a typed `phase`
+    /// event is emitted instead, so deploy, rollback and exec share one
+    /// schema. Quiet mode prints nothing.
+    pub fn phase(&self, server: &str, phase: &str, result: PhaseStatus, message: &str) {
+        match self.mode {
+            OutputMode::Normal => match result {
+                PhaseStatus::Started => self.out(self.prefixed(&format!("  → {message}"))),
+                PhaseStatus::Ok => self.out(self.prefixed(&format!("  ✓ {message}"))),
+                PhaseStatus::Failed => self.err(self.prefixed(&format!("  ✗ {message}"))),
+            },
+            OutputMode::Quiet => {}
+            OutputMode::Json => {
+                if let Some(json) = phase_event_json(server, phase, result, message) {
+                    self.out(json);
+                }
+            }
+        }
+    }
+
+    /// Write output produced by a remote command.
+    ///
+    /// Passed through unchanged in normal and quiet mode; wrapped in an
+    /// `output` event in JSON mode so stdout stays valid NDJSON.
+    pub fn command_output(&self, server: &str, stream: Stream, data: &str) {
+        match self.mode {
+            OutputMode::Normal | OutputMode::Quiet => self.write(stream, data),
+            OutputMode::Json => {
+                let event = CommandOutputEvent {
+                    event: "output",
+                    server,
+                    stream,
+                    data,
+                };
+                if let Ok(json) = serde_json::to_string(&event) {
+                    self.out(json);
+                }
+            }
+        }
+    }
+
+    /// Report the final outcome of a command.
+    ///
+    /// Normal mode prints `message` like `success`. Quiet mode prints only
+    /// `result` with the elapsed time appended, as one deterministic line
+    /// for wrapper scripts (e.g. `deployed app:v1 to 3/3 servers in 42s`).
+    /// JSON mode emits a `success` event carrying both.
+    pub fn finish(&self, message: &str, result: &str) {
+        match self.mode {
+            OutputMode::Normal => self.success(message),
+            OutputMode::Quiet => self.out(self.result_line(result)),
+            OutputMode::Json => {
+                let event = JsonEvent {
+                    event: "success",
+                    server: None,
+                    message,
+                    result: Some(result),
+                    duration_secs: self.start_time.map(|_| self.elapsed_secs()),
+                };
+                if let Ok(json) = serde_json::to_string(&event) {
+                    self.out(json);
+                }
+            }
+        }
+    }
+
+    /// Report the final outcome of a command that failed on some servers.
+    ///
+    /// Printed before the error itself is returned, so every mode still
+    /// gets a result: normal mode writes `message` to stderr, quiet mode the
+    /// same timed `result` line as `finish`, and JSON mode an `error` event
+    /// carrying both.
+    pub fn finish_partial(&self, message: &str, result: &str) {
+        match self.mode {
+            OutputMode::Normal => self.err(message),
+            OutputMode::Quiet => self.out(self.result_line(result)),
+            OutputMode::Json => {
+                let event = JsonEvent {
+                    event: "error",
+                    server: None,
+                    message,
+                    result: Some(result),
+                    duration_secs: self.start_time.map(|_| self.elapsed_secs()),
+                };
+                if let Ok(json) = serde_json::to_string(&event) {
+                    self.err(json);
+                }
+            }
+        }
+    }
+
+    /// Quiet-mode result line, with elapsed time when a timer is running.
+    fn result_line(&self, result: &str) -> String {
+        match self.start_time {
+            Some(_) => format!("{} in {:.0}s", result, self.elapsed_secs()),
+            None => result.to_string(),
         }
     }
 
@@ -54,19 +268,21 @@ impl Output {
             OutputMode::Normal => {
                 let elapsed = self.elapsed_secs();
                 if elapsed > 0.0 {
-                    println!("{message} ({:.1}s)", elapsed);
+                    self.out(format!("{message} ({:.1}s)", elapsed));
                 } else {
-                    println!("{message}");
+                    self.out(message);
                 }
             }
             OutputMode::Quiet => {
                 // Print only the essential result
-                println!("{message}");
+                self.out(message);
             }
             OutputMode::Json => {
                 let event = JsonEvent {
                     event: "success",
+                    server: None,
                     message,
+                    result: None,
                     duration_secs: if self.start_time.is_some() {
                         Some(self.elapsed_secs())
                     } else {
@@ -74,7 +290,7 @@ impl Output {
                     },
                 };
                 if let Ok(json) = serde_json::to_string(&event) {
-                    println!("{json}");
+                    self.out(json);
                 }
             }
         }
@@ -84,12 +300,14 @@ impl Output {
     pub fn error(&self, message: &str) {
         match self.mode {
             OutputMode::Normal | OutputMode::Quiet => {
-                eprintln!("Error: {message}");
+                self.err(self.prefixed(&format!("Error: {message}")));
             }
             OutputMode::Json => {
                 let event = JsonEvent {
                     event: "error",
+                    server: self.server(),
                     message,
+                    result: None,
                     duration_secs: if self.start_time.is_some() {
                         Some(self.elapsed_secs())
                     } else {
@@ -97,7 +315,7 @@ impl Output {
                     },
                 };
                 if let Ok(json) = serde_json::to_string(&event) {
-                    eprintln!("{json}");
+                    self.err(json);
                 }
             }
         }
@@ -107,7 +325,7 @@ impl Output {
     pub fn warning(&self, message: &str) {
         match self.mode {
             OutputMode::Normal => {
-                eprintln!("Warning: {message}");
+                self.err(self.prefixed(&format!("Warning: {message}")));
             }
             OutputMode::Quiet => {
                 // Suppress warnings in quiet mode
@@ -115,21 +333,262 @@ impl Output {
             OutputMode::Json => {
                 let event = JsonEvent {
                     event: "warning",
+                    server: self.server(),
                     message,
+                    result: None,
                     duration_secs: None,
                 };
                 if let Ok(json) = serde_json::to_string(&event) {
-                    eprintln!("{json}");
+                    self.err(json);
+                }
+            }
+        }
+    }
+}
+
+/// Single consumer that owns stdout and stderr for an [`Output`] and its clones.
+struct Writer {
+    tx: Option<Sender<(Stream, String)>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Writer {
+    fn spawn(mut stdout: Box<dyn Write + Send>, mut stderr: Box<dyn Write + Send>) -> Self {
+        let (tx, rx) = mpsc::channel::<(Stream, String)>();
+        let thread = std::thread::Builder::new()
+            .name("peleka-output".to_string())
+            .spawn(move || {
+                for (stream, text) in rx {
+                    let sink = match stream {
+                        Stream::Stdout => &mut stdout,
+                        Stream::Stderr => &mut stderr,
+                    };
+                    // A closed pipe (e.g. `| head`) must not take the process down
+                    let _ = sink.write_all(text.as_bytes()).and_then(|_| sink.flush());
                 }
+            })
+            .ok();
+        Self {
+            tx: thread.is_some().then_some(tx),
+            thread,
+        }
+    }
+
+    fn send(&self, stream: Stream, text: String) {
+        let unsent = match &self.tx {
+            Some(tx) => tx.send((stream, text)).err().map(|e| e.0.1),
+            None => Some(text),
+        };
+        // No writer thread: write directly rather than lose output
+        if let Some(text) = unsent {
+            match stream {
+                Stream::Stdout => print!("{text}"),
+                Stream::Stderr => eprint!("{text}"),
             }
         }
     }
 }
 
+impl Drop for Writer {
+    fn drop(&mut self) {
+        // Closing the channel ends the thread once queued lines are written
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct JsonEvent<'a> {
     event: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server: Option<&'a str>,
     message: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     duration_secs: Option<f64>,
 }
+
+/// Serialize a `phase` event as a single NDJSON line.
+fn phase_event_json(
+    server: &str,
+    phase: &str,
+    result: PhaseStatus,
+    message: &str,
+) -> Option<String> {
+    let event = PhaseEvent {
+        event: "phase",
+        phase,
+        server,
+        result,
+        message,
+    };
+    serde_json::to_string(&event).ok()
+}
+
+#[derive(Serialize)]
+struct PhaseEvent<'a> {
+    event: &'a str,
+    phase: &'a str,
+    server: &'a str,
+    result: PhaseStatus,
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+struct CommandOutputEvent<'a> {
+    event: &'a str,
+    server: &'a str,
+    stream: Stream,
+    data: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// In-memory sink shared between the writer thread and the test.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().clone()).unwrap()
+        }
+    }
+
+    /// Output writing into buffers instead of the terminal.
+    fn captured(mode: OutputMode) -> (Output, Buffer, Buffer) {
+        let (stdout, stderr) = (Buffer::default(), Buffer::default());
+        let writer = Writer::spawn(Box::new(stdout.clone()), Box::new(stderr.clone()));
+        (Output::with_writer(mode, false, writer), stdout, stderr)
+    }
+
+    #[test]
+    fn concurrent_json_events_are_whole_lines() {
+        let (output, stdout, _) = captured(OutputMode::Json);
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let output = output.for_server(&format!("web{i}"));
+                std::thread::spawn(move || {
+                    for n in 0..50 {
+                        output.phase(&format!("web{i}"), "pull", PhaseStatus::Ok, &"x".repeat(n));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        drop(output);
+
+        let contents = stdout.contents();
+        assert_eq!(contents.lines().count(), 400);
+        for line in contents.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["event"], "phase");
+        }
+    }
+
+    #[test]
+    fn server_output_is_prefixed() {
+        let (output, stdout, stderr) = captured(OutputMode::Normal);
+        let web1 = output.for_server("web1");
+        web1.progress("Pulling image...");
+        web1.warning("slow pull");
+        output.progress("Done");
+        drop((output, web1));
+
+        assert_eq!(stdout.contents(), "[web1] Pulling image...\nDone\n");
+        assert_eq!(stderr.contents(), "[web1] Warning: slow pull\n");
+    }
+
+    #[test]
+    fn json_warning_carries_server() {
+        let (output, _, stderr) = captured(OutputMode::Json);
+        output.for_server("web2").warning("disk almost full");
+        drop(output);
+
+        let value: serde_json::Value = serde_json::from_str(stderr.contents().trim()).unwrap();
+        assert_eq!(value["server"], "web2");
+        assert_eq!(value["message"], "disk almost full");
+    }
+
+    #[test]
+    fn colored_prefix_wraps_server_name() {
+        let (output, _, _) = captured(OutputMode::Normal);
+        let output = Output {
+            color: true,
+            ..output
+        }
+        .for_server("web1");
+        let line = output.prefixed("hello");
+        assert!(line.starts_with("\x1b["));
+        assert!(line.contains("[web1]\x1b[0m hello"));
+    }
+
+    #[test]
+    fn phase_event_has_typed_fields() {
+        let json =
+            phase_event_json("web1", "rollback", PhaseStatus::Ok, "Rollback successful").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["event"], "phase");
+        assert_eq!(value["phase"], "rollback");
+        assert_eq!(value["server"], "web1");
+        assert_eq!(value["result"], "ok");
+        assert_eq!(value["message"], "Rollback successful");
+    }
+
+    #[test]
+    fn quiet_partial_finish_prints_result_line() {
+        let (output, stdout, stderr) = captured(OutputMode::Quiet);
+        output.finish_partial(
+            "Rollback failed on 1/2 server(s)",
+            "rolled back app on 1/2 servers",
+        );
+        drop(output);
+
+        assert_eq!(stdout.contents(), "rolled back app on 1/2 servers\n");
+        assert_eq!(stderr.contents(), "");
+    }
+
+    #[test]
+    fn result_line_without_timer_is_unchanged() {
+        let output = Output::new(OutputMode::Quiet);
+        assert_eq!(
+            output.result_line("deployed app:v1 to 3/3 servers"),
+            "deployed app:v1 to 3/3 servers"
+        );
+    }
+
+    #[test]
+    fn result_line_appends_elapsed_time() {
+        let mut output = Output::new(OutputMode::Quiet);
+        output.start_timer();
+        let line = output.result_line("rolled back app on 2/2 servers");
+        assert!(line.starts_with("rolled back app on 2/2 servers in "));
+        assert!(line.ends_with('s'));
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn phase_event_is_single_line() {
+        let json = phase_event_json("web1", "exec", PhaseStatus::Failed, "exit 1").unwrap();
+        assert!(!json.contains('\n'));
+        assert!(json.contains("\"result\":\"failed\""));
+    }
+}
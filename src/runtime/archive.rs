@@ -0,0 +1,246 @@
+// ABOUTME: Reads image metadata from OCI and docker-archive tarballs.
+// ABOUTME: Finds the config digest so a loaded image can be verified against the archive.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::traits::ImageError;
+
+const BLOCK_SIZE: u64 = 512;
+
+/// An image tarball produced by `docker save` or `podman save`.
+#[derive(Debug, Clone)]
+pub struct ImageArchive {
+    /// Path to the archive on the local machine.
+    pub path: PathBuf,
+    /// Digest of the image config (`sha256:...`), which is the image ID once loaded.
+    pub config_digest: String,
+}
+
+impl ImageArchive {
+    /// Open an archive and read its config digest.
+    ///
+    /// Supports docker-archive (`manifest.json`) and OCI layout (`index.json`)
+    /// tarballs. Only the metadata entries are read; layers are skipped.
+    pub fn open(path: &Path) -> Result<Self, ImageError> {
+        let mut file = File::open(path).map_err(|e| archive_error(path, e))?;
+        let config_digest = read_config_digest(&mut file).map_err(|e| archive_error(path, e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            config_digest,
+        })
+    }
+}
+
+fn archive_error(path: &Path, e: impl std::fmt::Display) -> ImageError {
+    ImageError::LoadFailed(format!("{}: {}", path.display(), e))
+}
+
+#[derive(Deserialize)]
+struct DockerManifest {
+    #[serde(rename = "Config")]
+    config: String,
+}
+
+#[derive(Deserialize)]
+struct OciIndex {
+    manifests: Vec<OciDescriptor>,
+}
+
+#[derive(Deserialize)]
+struct OciManifest {
+    config: OciDescriptor,
+}
+
+#[derive(Deserialize)]
+struct OciDescriptor {
+    digest: String,
+}
+
+/// Find the image config digest in a docker-archive or OCI tarball.
+fn read_config_digest<R: Read + Seek>(tar: &mut R) -> std::io::Result<String> {
+    if let Some(data) = read_entry(tar, "manifest.json")? {
+        let manifests: Vec<DockerManifest> = serde_json::from_slice(&data)?;
+        let config = manifests
+            .first()
+            .map(|m| m.config.as_str())
+            .ok_or_else(|| invalid("manifest.json lists no images"))?;
+        // `blobs/sha256/<hex>` (OCI-style) or `<hex>.json` (legacy)
+        let file = config.rsplit('/').next().unwrap_or(config);
+        let hex = file.strip_suffix(".json").unwrap_or(file);
+        return Ok(format!("sha256:{}", hex));
+    }
+
+    let index = read_entry(tar, "index.json")?
+        .ok_or_else(|| invalid("neither manifest.json nor index.json found"))?;
+    let index: OciIndex = serde_json::from_slice(&index)?;
+    let manifest_digest = index
+        .manifests
+        .first()
+        .map(|m| m.digest.as_str())
+        .ok_or_else(|| invalid("index.json lists no manifests"))?;
+
+    let manifest = read_entry(tar, &blob_path(manifest_digest)?)?
+        .ok_or_else(|| invalid(format!("manifest blob {} missing", manifest_digest)))?;
+    let manifest: OciManifest = serde_json::from_slice(&manifest)?;
+    Ok(manifest.config.digest)
+}
+
+/// Path of a blob inside an OCI layout, e.g. `blobs/sha256/<hex>`.
+fn blob_path(digest: &str) -> std::io::Result<String> {
+    let (algorithm, hex) = digest
+        .split_once(':')
+        .ok_or_else(|| invalid(format!("malformed digest {}", digest)))?;
+    Ok(format!("blobs/{}/{}", algorithm, hex))
+}
+
+/// Read the contents of a regular file entry, scanning from the start.
+fn read_entry<R: Read + Seek>(tar: &mut R, name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    tar.seek(SeekFrom::Start(0))?;
+    let mut header = [0u8; BLOCK_SIZE as usize];
+
+    loop {
+        if let Err(e) = tar.read_exact(&mut header) {
+            return match e.kind() {
+                std::io::ErrorKind::UnexpectedEof => Ok(None),
+                _ => Err(e),
+            };
+        }
+        // Two zero blocks end the archive; one is enough to stop looking
+        if header.iter().all(|&b| b == 0) {
+            return Ok(None);
+        }
+
+        let size = entry_size(&header)?;
+        let is_file = matches!(header[156], b'0' | 0);
+        if is_file && entry_name(&header) == name {
+            let mut data = vec![0u8; size as usize];
+            tar.read_exact(&mut data)?;
+            return Ok(Some(data));
+        }
+
+        let padded = size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+        tar.seek(SeekFrom::Current(padded as i64))?;
+    }
+}
+
+/// Entry name from a ustar header, without a leading `./`.
+fn entry_name(header: &[u8]) -> String {
+    let field = |range: std::ops::Range<usize>| {
+        let bytes = &header[range];
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).into_owned()
+    };
+    let name = field(0..100);
+    let prefix = if &header[257..262] == b"ustar" {
+        field(345..500)
+    } else {
+        String::new()
+    };
+    let full = if prefix.is_empty() {
+        name
+    } else {
+        format!("{}/{}", prefix, name)
+    };
+    full.trim_start_matches("./").to_string()
+}
+
+/// Entry size from the header: octal text, or base-256 for large files.
+fn entry_size(header: &[u8]) -> std::io::Result<u64> {
+    let field = &header[124..136];
+    if field[0] & 0x80 != 0 {
+        return Ok(field[1..]
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b)));
+    }
+    let text = String::from_utf8_lossy(field);
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| invalid(format!("bad tar size field {:?}", text)))
+}
+
+fn invalid(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Build a minimal ustar archive from (name, contents) pairs.
+    fn tar(entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+        let mut out = Vec::new();
+        for (name, data) in entries {
+            let mut header = [0u8; BLOCK_SIZE as usize];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            let size = format!("{:011o}\0", data.len());
+            header[124..136].copy_from_slice(size.as_bytes());
+            header[156] = b'0';
+            header[257..263].copy_from_slice(b"ustar\0");
+            out.extend_from_slice(&header);
+            out.extend_from_slice(data);
+            let pad =
+                (BLOCK_SIZE as usize - data.len() % BLOCK_SIZE as usize) % BLOCK_SIZE as usize;
+            out.extend(std::iter::repeat_n(0u8, pad));
+        }
+        out.extend(std::iter::repeat_n(0u8, 2 * BLOCK_SIZE as usize));
+        Cursor::new(out)
+    }
+
+    #[test]
+    fn reads_docker_archive_digest() {
+        let mut archive = tar(&[
+            ("abc123/layer.tar", &[1u8; 700]),
+            (
+                "manifest.json",
+                br#"[{"Config":"abc123.json","RepoTags":["app:1"],"Layers":["abc123/layer.tar"]}]"#,
+            ),
+        ]);
+        assert_eq!(read_config_digest(&mut archive).unwrap(), "sha256:abc123");
+    }
+
+    #[test]
+    fn reads_oci_style_docker_manifest() {
+        let mut archive = tar(&[(
+            "manifest.json",
+            br#"[{"Config":"blobs/sha256/def456","RepoTags":["app:1"]}]"#,
+        )]);
+        assert_eq!(read_config_digest(&mut archive).unwrap(), "sha256:def456");
+    }
+
+    #[test]
+    fn reads_oci_layout_digest() {
+        let mut archive = tar(&[
+            ("oci-layout", br#"{"imageLayoutVersion":"1.0.0"}"#),
+            (
+                "./index.json",
+                br#"{"schemaVersion":2,"manifests":[{"digest":"sha256:m1","size":10}]}"#,
+            ),
+            (
+                "blobs/sha256/m1",
+                br#"{"schemaVersion":2,"config":{"digest":"sha256:cfg","size":5},"layers":[]}"#,
+            ),
+        ]);
+        assert_eq!(read_config_digest(&mut archive).unwrap(), "sha256:cfg");
+    }
+
+    #[test]
+    fn rejects_archive_without_metadata() {
+        let mut archive = tar(&[("layer.tar", b"data")]);
+        let err = read_config_digest(&mut archive).unwrap_err();
+        assert!(err.to_string().contains("manifest.json"));
+    }
+
+    #[test]
+    fn parses_octal_size() {
+        let mut header = [0u8; 512];
+        header[124..136].copy_from_slice(b"00000001750\0");
+        assert_eq!(entry_size(&header).unwrap(), 1000);
+    }
+}
@@ -3,11 +3,11 @@
 
 use crate::runtime::traits::sealed::Sealed;
 use crate::runtime::traits::{
-    ContainerConfig, ContainerError, ContainerFilters, ContainerInfo, ContainerOps, ContainerState,
-    ContainerSummary, ExecConfig, ExecError, ExecInfo, ExecOps, ExecResult, HealthState,
-    ImageError, ImageOps, LogError, LogLine, LogOps, LogOptions, LogStream, NetworkConfig,
-    NetworkError, NetworkInfo, NetworkOps, NetworkSettings, Protocol, RegistryAuth,
-    RestartPolicyConfig, RuntimeInfo, RuntimeInfoError, RuntimeMetadata,
+    ChangeKind, ContainerConfig, ContainerError, ContainerFilters, ContainerInfo, ContainerOps,
+    ContainerState, ContainerSummary, ExecConfig, ExecError, ExecInfo, ExecOps, ExecResult,
+    FilesystemChange, HealthState, ImageError, ImageOps, LogError, LogLine, LogOps, LogOptions,
+    LogStream, NetworkConfig, NetworkError, NetworkInfo, NetworkOps, NetworkSettings, Protocol,
+    RegistryAuth, RestartPolicyConfig, RuntimeInfo, RuntimeInfoError, RuntimeMetadata,
 };
 use crate::runtime::types::RuntimeType;
 use crate::ssh::Session;
@@ -20,14 +20,17 @@ use bollard::models::{
     PortBinding, RestartPolicy, RestartPolicyNameEnum,
 };
 use bollard::query_parameters::{
-    CreateContainerOptions, CreateImageOptions, InspectContainerOptions, ListContainersOptions,
-    LogsOptions, RemoveContainerOptions, RemoveImageOptions, StopContainerOptions,
+    CreateContainerOptions, CreateImageOptions, ImportImageOptions, InspectContainerOptions,
+    ListContainersOptions, LogsOptions, RemoveContainerOptions, RemoveImageOptions,
+    StopContainerOptions, WaitContainerOptions,
 };
 use futures::{Stream, StreamExt};
 use hyper_util::rt::TokioIo;
 use std::collections::HashMap;
+use std::path::Path;
 use std::pin::Pin;
 use std::time::Duration;
+use tokio::io::AsyncReadExt;
 use tokio::net::UnixStream;
 
 // =============================================================================
@@ -419,9 +422,21 @@ pub async fn connect_via_session(
         }
     };
 
+    connect_to_socket(session, runtime_type, &remote_socket).await
+}
+
+/// Connect to a container runtime at a known remote socket path.
+///
+/// Use with the socket returned by `detect_runtime()` to avoid probing the
+/// server again.
+pub async fn connect_to_socket(
+    session: &Session,
+    runtime_type: RuntimeType,
+    remote_socket: &str,
+) -> Result<BollardRuntime, RuntimeInfoError> {
     // Forward the socket via SSH
     let local_socket = session
-        .forward_socket(&remote_socket)
+        .forward_socket(remote_socket)
         .await
         .map_err(|e| RuntimeInfoError::ConnectionFailed(e.to_string()))?;
 
@@ -504,6 +519,48 @@ impl ImageOps for BollardRuntime {
         Ok(())
     }
 
+    async fn load_image(&self, archive: &Path) -> Result<(), ImageError> {
+        let file = tokio::fs::File::open(archive).await.map_err(|e| {
+            ImageError::LoadFailed(format!("failed to open {}: {}", archive.display(), e))
+        })?;
+
+        // Stream the archive in chunks so large images never sit in memory.
+        // A read error ends the stream with that error so the import fails
+        // instead of loading a truncated archive.
+        let body = futures::stream::unfold(Some(file), |state| async move {
+            let mut file = state?;
+            let mut buf = vec![0u8; 64 * 1024];
+            match file.read(&mut buf).await {
+                Ok(0) => None,
+                Ok(n) => {
+                    buf.truncate(n);
+                    Some((Ok(bytes::Bytes::from(buf)), Some(file)))
+                }
+                Err(e) => Some((Err::<bytes::Bytes, std::io::Error>(e), None)),
+            }
+        });
+
+        let opts = ImportImageOptions {
+            quiet: true,
+            ..Default::default()
+        };
+
+        let mut stream = self.client.import_image_stream(opts, body, None);
+        while let Some(result) = stream.next().await {
+            let info = result
+                .map_err(|e| ImageError::LoadFailed(format!("{}: {}", archive.display(), e)))?;
+            if let Some(detail) = info.error_detail {
+                return Err(ImageError::LoadFailed(format!(
+                    "{}: {}",
+                    archive.display(),
+                    detail.message.unwrap_or_default()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     async fn image_exists(&self, reference: &ImageRef) -> Result<bool, ImageError> {
         let image_name = reference.to_string();
 
@@ -519,6 +576,73 @@ impl ImageOps for BollardRuntime {
         }
     }
 
+    async fn image_id(&self, reference: &ImageRef) -> Result<Option<String>, ImageError> {
+        let image_name = reference.to_string();
+
+        match self.client.inspect_image(&image_name).await {
+            Ok(image) => Ok(image.id),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(None),
+            Err(e) => Err(ImageError::Runtime(format!(
+                "failed to inspect {}: {}",
+                image_name, e
+            ))),
+        }
+    }
+
+    async fn image_digests(&self, reference: &ImageRef) -> Result<Vec<String>, ImageError> {
+        let image_name = reference.to_string();
+
+        // RepoDigests look like "registry/repo@sha256:..." and list every
+        // repository the image was pulled from; keep only the one asked for
+        let repository = reference.repository();
+        match self.client.inspect_image(&image_name).await {
+            Ok(image) => Ok(image
+                .repo_digests
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|d| d.split_once('@'))
+                .filter(|(repo, _)| {
+                    ImageRef::parse(repo).is_ok_and(|r| r.repository() == repository)
+                })
+                .map(|(_, digest)| digest.to_string())
+                .collect()),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(Vec::new()),
+            Err(e) => Err(ImageError::Runtime(format!(
+                "failed to inspect {}: {}",
+                image_name, e
+            ))),
+        }
+    }
+
+    async fn registry_digest(
+        &self,
+        reference: &ImageRef,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<String, ImageError> {
+        let image_name = reference.to_string();
+
+        let credentials = auth.map(|a| bollard::auth::DockerCredentials {
+            username: Some(a.username.clone()),
+            password: Some(a.password.clone()),
+            serveraddress: a.server.clone(),
+            ..Default::default()
+        });
+
+        let info = self
+            .client
+            .inspect_registry_image(&image_name, credentials)
+            .await
+            .map_err(|e| map_image_pull_error(e, &image_name))?;
+
+        info.descriptor.digest.ok_or_else(|| {
+            ImageError::Runtime(format!("registry returned no digest for {}", image_name))
+        })
+    }
+
     async fn remove_image(&self, reference: &ImageRef, force: bool) -> Result<(), ImageError> {
         let image_name = reference.to_string();
 
@@ -600,6 +724,14 @@ impl ContainerOps for BollardRuntime {
             host_config.mounts = Some(mounts);
         }
 
+        // Read-only root filesystem with tmpfs scratch dirs
+        if config.read_only {
+            host_config.readonly_rootfs = Some(true);
+        }
+        if !config.tmpfs.is_empty() {
+            host_config.tmpfs = Some(config.tmpfs.clone());
+        }
+
         // Set port bindings
         let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
         let mut exposed_ports: Vec<String> = Vec::new();
@@ -798,6 +930,23 @@ impl ContainerOps for BollardRuntime {
             }
         }
 
+        // Parse restart policy
+        let restart_policy = details
+            .host_config
+            .as_ref()
+            .and_then(|h| h.restart_policy.as_ref())
+            .and_then(|p| {
+                let max_retries = p.maximum_retry_count.filter(|n| *n > 0).map(|n| n as u32);
+                p.name.map(|name| match name {
+                    RestartPolicyNameEnum::ALWAYS => RestartPolicyConfig::Always,
+                    RestartPolicyNameEnum::UNLESS_STOPPED => RestartPolicyConfig::UnlessStopped,
+                    RestartPolicyNameEnum::ON_FAILURE => {
+                        RestartPolicyConfig::OnFailure { max_retries }
+                    }
+                    _ => RestartPolicyConfig::No,
+                })
+            });
+
         Ok(ContainerInfo {
             id: id.clone(),
             name: details
@@ -810,11 +959,20 @@ impl ContainerOps for BollardRuntime {
                 .as_ref()
                 .and_then(|c| c.image.clone())
                 .unwrap_or_default(),
+            image_id: details.image.clone().unwrap_or_default(),
             state,
             health,
             created: details.created.map(|dt| dt.to_string()).unwrap_or_default(),
             labels: details.config.and_then(|c| c.labels).unwrap_or_default(),
             network_settings: NetworkSettings { networks },
+            restart_policy,
+            restart_count: details.restart_count.unwrap_or(0).max(0) as u32,
+            oom_killed: details
+                .state
+                .as_ref()
+                .and_then(|s| s.oom_killed)
+                .unwrap_or(false),
+            exit_code: details.state.as_ref().and_then(|s| s.exit_code),
         })
     }
 
@@ -913,6 +1071,48 @@ impl ContainerOps for BollardRuntime {
             .map_err(map_container_rename_error)
     }
 
+    async fn container_changes(
+        &self,
+        id: &ContainerId,
+    ) -> Result<Vec<FilesystemChange>, ContainerError> {
+        let changes = self
+            .client
+            .container_changes(id.as_str())
+            .await
+            .map_err(map_container_not_found_error)?
+            .unwrap_or_default();
+
+        // The API reports kind as 0 (modified), 1 (added) or 2 (deleted)
+        Ok(changes
+            .into_iter()
+            .map(|change| FilesystemChange {
+                path: change.path,
+                kind: match change.kind as u8 {
+                    1 => ChangeKind::Added,
+                    2 => ChangeKind::Deleted,
+                    _ => ChangeKind::Changed,
+                },
+            })
+            .collect())
+    }
+
+    async fn wait_container(&self, id: &ContainerId) -> Result<i64, ContainerError> {
+        let mut stream = self
+            .client
+            .wait_container(id.as_str(), None::<WaitContainerOptions>);
+
+        match stream.next().await {
+            Some(Ok(response)) => Ok(response.status_code),
+            // Bollard reports a non-zero exit as an error carrying the code
+            Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => Ok(code),
+            Some(Err(e)) => Err(map_container_not_found_error(e)),
+            None => Err(ContainerError::Runtime(format!(
+                "wait for {} ended without an exit code",
+                id
+            ))),
+        }
+    }
+
     async fn run_healthcheck(
         &self,
         id: &ContainerId,
@@ -929,13 +1129,30 @@ impl ContainerOps for BollardRuntime {
         // Run the healthcheck command via exec
         match self.exec(id, &exec_config).await {
             Ok(result) => {
+                if let Some(name) = result.missing_executable(cmd) {
+                    return Err(ContainerError::ExecutableNotFound(name));
+                }
                 // Exit code 0 means healthy
                 Ok(result.exit_code == 0)
             }
-            Err(e) => Err(ContainerError::Runtime(format!(
-                "healthcheck exec failed: {}",
-                e
-            ))),
+            Err(e) => {
+                let message = e.to_string();
+                // Some Docker versions fail exec start instead of exiting 126/127
+                if message.contains("executable file not found") {
+                    let result = ExecResult {
+                        exit_code: 127,
+                        stdout: Vec::new(),
+                        stderr: message.clone().into_bytes(),
+                    };
+                    if let Some(name) = result.missing_executable(cmd) {
+                        return Err(ContainerError::ExecutableNotFound(name));
+                    }
+                }
+                Err(ContainerError::Runtime(format!(
+                    "healthcheck exec failed: {}",
+                    message
+                )))
+            }
         }
     }
 }
@@ -0,0 +1,478 @@
+// ABOUTME: Per-run caching wrapper for container runtime queries.
+// ABOUTME: Deduplicates list/inspect round-trips and invalidates on mutation.
+
+use crate::runtime::traits::sealed::Sealed;
+use crate::runtime::traits::{
+    ContainerConfig, ContainerError, ContainerFilters, ContainerInfo, ContainerOps,
+    ContainerSummary, ExecConfig, ExecError, ExecOps, ExecResult, FilesystemChange, ImageError,
+    ImageOps, NetworkConfig, NetworkError, NetworkOps, RegistryAuth,
+};
+use crate::types::{ContainerId, ImageRef, NetworkAlias, NetworkId};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Counters describing how many runtime round-trips the cache absorbed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// `list_containers` calls made by callers.
+    pub list_requests: u64,
+    /// `list_containers` calls served from cache.
+    pub list_hits: u64,
+    /// `inspect_container` calls made by callers.
+    pub inspect_requests: u64,
+    /// `inspect_container` calls served from cache.
+    pub inspect_hits: u64,
+    /// Number of times a mutation cleared the cache.
+    pub invalidations: u64,
+}
+
+impl CacheStats {
+    /// Round-trips to the runtime that were avoided.
+    pub fn round_trips_saved(&self) -> u64 {
+        self.list_hits + self.inspect_hits
+    }
+
+    /// Round-trips that actually reached the runtime.
+    pub fn round_trips_made(&self) -> u64 {
+        (self.list_requests - self.list_hits) + (self.inspect_requests - self.inspect_hits)
+    }
+}
+
+/// Runtime wrapper that caches container listings and inspections for the
+/// duration of a single command.
+///
+/// A deploy lists and inspects the same containers in several phases
+/// (discovery, orphan check, cutover, cleanup). Over a high-latency SSH
+/// tunnel each of those is a full round-trip. This wrapper serves repeated
+/// queries from memory and clears everything on any container or network
+/// mutation, so callers always observe their own writes.
+///
+/// Create one per command run; it is not meant to outlive the command.
+pub struct CachedRuntime<R> {
+    inner: R,
+    lists: Mutex<HashMap<String, Vec<ContainerSummary>>>,
+    inspections: Mutex<HashMap<ContainerId, ContainerInfo>>,
+    list_requests: AtomicU64,
+    list_hits: AtomicU64,
+    inspect_requests: AtomicU64,
+    inspect_hits: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl<R> CachedRuntime<R> {
+    /// Wrap a runtime with an empty cache.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            lists: Mutex::new(HashMap::new()),
+            inspections: Mutex::new(HashMap::new()),
+            list_requests: AtomicU64::new(0),
+            list_hits: AtomicU64::new(0),
+            inspect_requests: AtomicU64::new(0),
+            inspect_hits: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    /// Access the wrapped runtime (bypasses the cache).
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwrap into the underlying runtime.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Snapshot of cache counters.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            list_requests: self.list_requests.load(Ordering::Relaxed),
+            list_hits: self.list_hits.load(Ordering::Relaxed),
+            inspect_requests: self.inspect_requests.load(Ordering::Relaxed),
+            inspect_hits: self.inspect_hits.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drop all cached state. Called after every mutation.
+    pub fn invalidate(&self) {
+        self.lists.lock().clear();
+        self.inspections.lock().clear();
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Build a stable cache key for a set of filters.
+fn filters_key(filters: &ContainerFilters) -> String {
+    let mut labels: Vec<_> = filters
+        .labels
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    labels.sort();
+    format!(
+        "all={};name={};labels={}",
+        filters.all,
+        filters.name.as_deref().unwrap_or(""),
+        labels.join(",")
+    )
+}
+
+impl<R> Sealed for CachedRuntime<R> {}
+
+#[async_trait]
+impl<R: ContainerOps> ContainerOps for CachedRuntime<R> {
+    async fn create_container(
+        &self,
+        config: &ContainerConfig,
+    ) -> Result<ContainerId, ContainerError> {
+        let result = self.inner.create_container(config).await;
+        self.invalidate();
+        result
+    }
+
+    async fn start_container(&self, id: &ContainerId) -> Result<(), ContainerError> {
+        let result = self.inner.start_container(id).await;
+        self.invalidate();
+        result
+    }
+
+    async fn stop_container(
+        &self,
+        id: &ContainerId,
+        timeout: Duration,
+    ) -> Result<(), ContainerError> {
+        let result = self.inner.stop_container(id, timeout).await;
+        self.invalidate();
+        result
+    }
+
+    async fn remove_container(&self, id: &ContainerId, force: bool) -> Result<(), ContainerError> {
+        let result = self.inner.remove_container(id, force).await;
+        self.invalidate();
+        result
+    }
+
+    async fn inspect_container(&self, id: &ContainerId) -> Result<ContainerInfo, ContainerError> {
+        self.inspect_requests.fetch_add(1, Ordering::Relaxed);
+        if let Some(info) = self.inspections.lock().get(id).cloned() {
+            self.inspect_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(info);
+        }
+
+        let info = self.inner.inspect_container(id).await?;
+        self.inspections.lock().insert(id.clone(), info.clone());
+        Ok(info)
+    }
+
+    async fn list_containers(
+        &self,
+        filters: &ContainerFilters,
+    ) -> Result<Vec<ContainerSummary>, ContainerError> {
+        self.list_requests.fetch_add(1, Ordering::Relaxed);
+        let key = filters_key(filters);
+        if let Some(containers) = self.lists.lock().get(&key).cloned() {
+            self.list_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(containers);
+        }
+
+        let containers = self.inner.list_containers(filters).await?;
+        self.lists.lock().insert(key, containers.clone());
+        Ok(containers)
+    }
+
+    async fn rename_container(
+        &self,
+        id: &ContainerId,
+        new_name: &str,
+    ) -> Result<(), ContainerError> {
+        let result = self.inner.rename_container(id, new_name).await;
+        self.invalidate();
+        result
+    }
+
+    async fn container_changes(
+        &self,
+        id: &ContainerId,
+    ) -> Result<Vec<FilesystemChange>, ContainerError> {
+        // Changes grow while the container runs; never serve them from cache
+        self.inner.container_changes(id).await
+    }
+
+    async fn wait_container(&self, id: &ContainerId) -> Result<i64, ContainerError> {
+        // The container is no longer running once this returns
+        let result = self.inner.wait_container(id).await;
+        self.invalidate();
+        result
+    }
+
+    async fn run_healthcheck(
+        &self,
+        id: &ContainerId,
+        cmd: &[String],
+    ) -> Result<bool, ContainerError> {
+        // Health checks run inside the container and don't change its
+        // listing or configuration, so the cache stays valid.
+        self.inner.run_healthcheck(id, cmd).await
+    }
+}
+
+#[async_trait]
+impl<R: ImageOps> ImageOps for CachedRuntime<R> {
+    async fn pull_image(
+        &self,
+        reference: &ImageRef,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<(), ImageError> {
+        self.inner.pull_image(reference, auth).await
+    }
+
+    async fn load_image(&self, archive: &Path) -> Result<(), ImageError> {
+        self.inner.load_image(archive).await
+    }
+
+    async fn image_exists(&self, reference: &ImageRef) -> Result<bool, ImageError> {
+        self.inner.image_exists(reference).await
+    }
+
+    async fn image_id(&self, reference: &ImageRef) -> Result<Option<String>, ImageError> {
+        self.inner.image_id(reference).await
+    }
+
+    async fn image_digests(&self, reference: &ImageRef) -> Result<Vec<String>, ImageError> {
+        self.inner.image_digests(reference).await
+    }
+
+    async fn registry_digest(
+        &self,
+        reference: &ImageRef,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<String, ImageError> {
+        self.inner.registry_digest(reference, auth).await
+    }
+
+    async fn remove_image(&self, reference: &ImageRef, force: bool) -> Result<(), ImageError> {
+        self.inner.remove_image(reference, force).await
+    }
+}
+
+#[async_trait]
+impl<R: NetworkOps> NetworkOps for CachedRuntime<R> {
+    async fn create_network(&self, config: &NetworkConfig) -> Result<NetworkId, NetworkError> {
+        self.inner.create_network(config).await
+    }
+
+    async fn remove_network(&self, id: &NetworkId) -> Result<(), NetworkError> {
+        self.inner.remove_network(id).await
+    }
+
+    async fn connect_to_network(
+        &self,
+        container: &ContainerId,
+        network: &NetworkId,
+        aliases: &[NetworkAlias],
+    ) -> Result<(), NetworkError> {
+        let result = self
+            .inner
+            .connect_to_network(container, network, aliases)
+            .await;
+        self.invalidate();
+        result
+    }
+
+    async fn disconnect_from_network(
+        &self,
+        container: &ContainerId,
+        network: &NetworkId,
+    ) -> Result<(), NetworkError> {
+        let result = self.inner.disconnect_from_network(container, network).await;
+        self.invalidate();
+        result
+    }
+
+    async fn network_exists(&self, name: &str) -> Result<bool, NetworkError> {
+        self.inner.network_exists(name).await
+    }
+}
+
+// Commands run inside a container leave its listing and inspection unchanged
+#[async_trait]
+impl<R: ExecOps> ExecOps for CachedRuntime<R> {
+    async fn exec(
+        &self,
+        container: &ContainerId,
+        config: &ExecConfig,
+    ) -> Result<ExecResult, ExecError> {
+        self.inner.exec(container, config).await
+    }
+
+    async fn exec_create(
+        &self,
+        container: &ContainerId,
+        config: &ExecConfig,
+    ) -> Result<String, ExecError> {
+        self.inner.exec_create(container, config).await
+    }
+
+    async fn exec_start(&self, exec_id: &str) -> Result<ExecResult, ExecError> {
+        self.inner.exec_start(exec_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::traits::{ContainerState, NetworkSettings};
+    use crate::types::ServiceName;
+
+    /// Runtime double that counts how many queries reach it.
+    #[derive(Default)]
+    struct CountingRuntime {
+        lists: AtomicU64,
+        inspects: AtomicU64,
+    }
+
+    impl Sealed for CountingRuntime {}
+
+    #[async_trait]
+    impl ContainerOps for CountingRuntime {
+        async fn create_container(
+            &self,
+            _config: &ContainerConfig,
+        ) -> Result<ContainerId, ContainerError> {
+            Ok(ContainerId::new("new".to_string()))
+        }
+
+        async fn start_container(&self, _id: &ContainerId) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn stop_container(
+            &self,
+            _id: &ContainerId,
+            _timeout: Duration,
+        ) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn remove_container(
+            &self,
+            _id: &ContainerId,
+            _force: bool,
+        ) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn inspect_container(
+            &self,
+            id: &ContainerId,
+        ) -> Result<ContainerInfo, ContainerError> {
+            self.inspects.fetch_add(1, Ordering::Relaxed);
+            Ok(ContainerInfo {
+                id: id.clone(),
+                name: "app-blue".to_string(),
+                image: "app:v1".to_string(),
+                image_id: "sha256:abc".to_string(),
+                state: ContainerState::Running,
+                health: None,
+                created: String::new(),
+                labels: HashMap::new(),
+                network_settings: NetworkSettings::default(),
+                restart_policy: None,
+                restart_count: 0,
+                oom_killed: false,
+                exit_code: None,
+            })
+        }
+
+        async fn list_containers(
+            &self,
+            _filters: &ContainerFilters,
+        ) -> Result<Vec<ContainerSummary>, ContainerError> {
+            self.lists.fetch_add(1, Ordering::Relaxed);
+            Ok(vec![])
+        }
+
+        async fn rename_container(
+            &self,
+            _id: &ContainerId,
+            _new_name: &str,
+        ) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn container_changes(
+            &self,
+            _id: &ContainerId,
+        ) -> Result<Vec<FilesystemChange>, ContainerError> {
+            Ok(Vec::new())
+        }
+
+        async fn wait_container(&self, _id: &ContainerId) -> Result<i64, ContainerError> {
+            Ok(0)
+        }
+
+        async fn run_healthcheck(
+            &self,
+            _id: &ContainerId,
+            _cmd: &[String],
+        ) -> Result<bool, ContainerError> {
+            Ok(true)
+        }
+    }
+
+    fn filters() -> ContainerFilters {
+        ContainerFilters::for_service(&ServiceName::new("app").unwrap(), true)
+    }
+
+    #[tokio::test]
+    async fn repeated_lists_hit_cache() {
+        let runtime = CachedRuntime::new(CountingRuntime::default());
+
+        for _ in 0..4 {
+            runtime.list_containers(&filters()).await.unwrap();
+        }
+
+        assert_eq!(runtime.inner().lists.load(Ordering::Relaxed), 1);
+        let stats = runtime.stats();
+        assert_eq!(stats.list_requests, 4);
+        assert_eq!(stats.list_hits, 3);
+        assert_eq!(stats.round_trips_saved(), 3);
+        assert_eq!(stats.round_trips_made(), 1);
+    }
+
+    #[tokio::test]
+    async fn mutation_invalidates_cache() {
+        let runtime = CachedRuntime::new(CountingRuntime::default());
+        let id = ContainerId::new("abc".to_string());
+
+        runtime.list_containers(&filters()).await.unwrap();
+        runtime.inspect_container(&id).await.unwrap();
+        runtime
+            .stop_container(&id, Duration::from_secs(1))
+            .await
+            .unwrap();
+        runtime.list_containers(&filters()).await.unwrap();
+        runtime.inspect_container(&id).await.unwrap();
+
+        assert_eq!(runtime.inner().lists.load(Ordering::Relaxed), 2);
+        assert_eq!(runtime.inner().inspects.load(Ordering::Relaxed), 2);
+        assert_eq!(runtime.stats().invalidations, 1);
+    }
+
+    #[tokio::test]
+    async fn different_filters_are_cached_separately() {
+        let runtime = CachedRuntime::new(CountingRuntime::default());
+        let running_only = ContainerFilters::for_service(&ServiceName::new("app").unwrap(), false);
+
+        runtime.list_containers(&filters()).await.unwrap();
+        runtime.list_containers(&running_only).await.unwrap();
+
+        assert_eq!(runtime.inner().lists.load(Ordering::Relaxed), 2);
+    }
+}
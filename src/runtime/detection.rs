@@ -8,13 +8,131 @@ use std::path::Path;
 /// Error during runtime detection.
 #[derive(Debug, thiserror::Error)]
 pub enum DetectionError {
-    #[error("no container runtime found (checked Podman and Docker sockets)")]
-    NoRuntimeFound,
+    #[error("no container runtime found (checked {})", .0.sockets_checked.join(", "))]
+    NoRuntimeFound(Box<Prerequisites>),
 
     #[error("SSH error: {0}")]
     Ssh(#[from] crate::ssh::Error),
 }
 
+/// What detection found on a system with no usable runtime socket.
+///
+/// Carried by [`DetectionError::NoRuntimeFound`] so the error can say what
+/// was checked and how to fix it, rather than only that detection failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Prerequisites {
+    /// Socket paths that were probed, in detection order.
+    pub sockets_checked: Vec<String>,
+    /// Whether the user is root (rootful sockets apply instead of per-user ones).
+    pub root: bool,
+    /// Whether a `podman` binary is on PATH.
+    pub podman_installed: bool,
+    /// Whether a `docker` binary is on PATH.
+    pub docker_installed: bool,
+    /// `systemctl is-active podman.socket`, or None if systemd was not checked.
+    /// Filled in by [`Prerequisites::check_system_units`].
+    pub podman_socket_unit: Option<String>,
+    /// `systemctl --user is-active podman.socket`, or None if systemd was not checked.
+    pub podman_user_socket_unit: Option<String>,
+    /// `systemctl is-active docker.socket`, or None if systemd was not checked.
+    /// Filled in by [`Prerequisites::check_system_units`].
+    pub docker_socket_unit: Option<String>,
+}
+
+impl Prerequisites {
+    /// Fill in the system-wide socket unit states.
+    ///
+    /// Checking system services is an admin operation, so callers pass a
+    /// session logged in as the admin user. Failures leave the units unknown.
+    pub async fn check_system_units(&mut self, session: &Session) {
+        match session.exec(SYSTEM_UNITS_SCRIPT).await {
+            Ok(output) => self.apply(&output.stdout),
+            Err(e) => tracing::debug!("system socket unit probe failed: {}", e),
+        }
+    }
+
+    /// Record the `key=value` lines printed by the diagnose scripts.
+    fn apply(&mut self, stdout: &str) {
+        let unit_state = |state: &str| match state {
+            "" => "unknown".to_string(),
+            s => s.to_string(),
+        };
+        for line in stdout.lines() {
+            match line.trim().split_once('=') {
+                Some(("podman_bin", "1")) => self.podman_installed = true,
+                Some(("docker_bin", "1")) => self.docker_installed = true,
+                Some(("podman_unit", state)) => self.podman_socket_unit = Some(unit_state(state)),
+                Some(("podman_user_unit", state)) => {
+                    self.podman_user_socket_unit = Some(unit_state(state))
+                }
+                Some(("docker_unit", state)) => self.docker_socket_unit = Some(unit_state(state)),
+                _ => {}
+            }
+        }
+    }
+
+    /// One line per runtime describing what is installed and running.
+    pub fn findings(&self) -> Vec<String> {
+        let podman_units = [
+            ("podman.socket", &self.podman_socket_unit),
+            ("user podman.socket", &self.podman_user_socket_unit),
+        ];
+        let docker_units = [("docker.socket", &self.docker_socket_unit)];
+        vec![
+            runtime_finding("podman", self.podman_installed, &podman_units),
+            runtime_finding("docker", self.docker_installed, &docker_units),
+        ]
+    }
+
+    /// Commands or steps that would make a runtime socket available.
+    pub fn remediation(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        if self.podman_installed {
+            if self.root {
+                steps.push("Start the Podman socket: systemctl enable --now podman.socket".into());
+            } else {
+                steps.push(
+                    "Start the rootless Podman socket: systemctl --user enable --now podman.socket \
+                     (and loginctl enable-linger $USER so it survives logout)"
+                        .into(),
+                );
+            }
+        }
+        if self.docker_installed {
+            let sudo = if self.root { "" } else { "sudo " };
+            steps.push(format!(
+                "Start the Docker socket: {}systemctl enable --now docker.socket",
+                sudo
+            ));
+        }
+        if !self.podman_installed && !self.docker_installed {
+            steps.push("Install Podman or Docker on the target server".into());
+        }
+        steps.push("If the socket lives elsewhere, set `runtime` and `socket` for the server in peleka.yml".into());
+        steps
+    }
+}
+
+fn runtime_finding(name: &str, installed: bool, units: &[(&str, &Option<String>)]) -> String {
+    let mut line = format!(
+        "{}: {}",
+        name,
+        if installed {
+            "installed"
+        } else {
+            "not installed"
+        }
+    );
+    let states: Vec<String> = units
+        .iter()
+        .filter_map(|(unit, state)| state.as_ref().map(|s| format!("{} {}", unit, s)))
+        .collect();
+    if !states.is_empty() {
+        line.push_str(&format!(" ({})", states.join(", ")));
+    }
+    line
+}
+
 /// Detect container runtime on the local system.
 ///
 /// Detection order:
@@ -22,15 +140,19 @@ pub enum DetectionError {
 /// 2. Rootful Podman socket (`/run/podman/podman.sock`)
 /// 3. Docker socket (`/var/run/docker.sock`)
 pub fn detect_local() -> Result<RuntimeInfo, DetectionError> {
+    let uid = get_uid();
+    let rootless_socket = uid
+        .as_ref()
+        .map(|uid| format!("/run/user/{}/podman/podman.sock", uid));
+
     // 1. Rootless Podman
-    if let Some(uid) = get_uid() {
-        let rootless_socket = format!("/run/user/{}/podman/podman.sock", uid);
-        if Path::new(&rootless_socket).exists() {
-            return Ok(RuntimeInfo {
-                runtime_type: RuntimeType::Podman,
-                socket_path: rootless_socket,
-            });
-        }
+    if let Some(socket_path) = &rootless_socket
+        && Path::new(socket_path).exists()
+    {
+        return Ok(RuntimeInfo {
+            runtime_type: RuntimeType::Podman,
+            socket_path: socket_path.clone(),
+        });
     }
 
     // 2. Rootful Podman
@@ -49,7 +171,28 @@ pub fn detect_local() -> Result<RuntimeInfo, DetectionError> {
         });
     }
 
-    Err(DetectionError::NoRuntimeFound)
+    Err(DetectionError::NoRuntimeFound(Box::new(Prerequisites {
+        sockets_checked: checked_sockets(rootless_socket),
+        root: uid.as_deref() == Some("0"),
+        podman_installed: on_local_path("podman"),
+        docker_installed: on_local_path("docker"),
+        ..Prerequisites::default()
+    })))
+}
+
+/// Whether an executable named `name` is on the local PATH.
+fn on_local_path(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(name).is_file()))
+        .unwrap_or(false)
+}
+
+/// Socket paths probed by detection, in order.
+fn checked_sockets(rootless_socket: Option<String>) -> Vec<String> {
+    rootless_socket
+        .into_iter()
+        .chain([ROOTFUL_PODMAN.to_string(), DOCKER_SOCKET.to_string()])
+        .collect()
 }
 
 fn get_uid() -> Option<String> {
@@ -76,57 +219,207 @@ const DOCKER_SOCKET: &str = "/var/run/docker.sock";
 /// 2. Rootful Podman socket (`/run/podman/podman.sock`)
 /// 3. Docker socket (`/var/run/docker.sock`)
 ///
+/// All candidate sockets are probed with a single batched command, so
+/// detection costs one round-trip regardless of which runtime is found.
 /// If `config` is provided with explicit values, those take precedence.
 pub async fn detect_runtime(
     session: &Session,
     config: Option<&RuntimeConfig>,
 ) -> Result<RuntimeInfo, DetectionError> {
-    // Check for explicit override
+    let explicit = config.and_then(|cfg| cfg.runtime);
+
+    // Explicit runtime and socket: nothing to probe
     if let Some(cfg) = config
         && let Some(runtime_type) = cfg.runtime
+        && let Some(socket_path) = cfg.socket.clone()
     {
-        let socket_path = cfg
-            .socket
-            .clone()
-            .unwrap_or_else(|| default_socket_path(runtime_type));
         return Ok(RuntimeInfo {
             runtime_type,
             socket_path,
         });
     }
 
-    // Auto-detect: check sockets in order
+    let output = session.exec(&probe_script()).await?;
+    let probe = if output.stdout.contains("uid=") {
+        Probe::parse(&output.stdout)
+    } else {
+        // The login shell could not run the batched script (no POSIX sh)
+        tracing::debug!(
+            "batched runtime probe failed (exit {}), probing sockets one at a time",
+            output.exit_code
+        );
+        probe_individually(session).await?
+    };
 
-    // 1. Rootless Podman
-    let uid_output = session.exec("id -u").await?;
-    if uid_output.success() {
-        let uid = uid_output.stdout.trim();
-        let rootless_socket = format!("/run/user/{}/podman/podman.sock", uid);
-        if session.file_exists(&rootless_socket).await? {
-            return Ok(RuntimeInfo {
+    match explicit {
+        Some(runtime_type) => Ok(RuntimeInfo {
+            runtime_type,
+            socket_path: probe.socket_for(runtime_type),
+        }),
+        None => match probe.detect() {
+            Some(info) => Ok(info),
+            // Only pay for the extra round-trip when there is something to explain
+            None => {
+                let prerequisites = match session.exec(DIAGNOSE_SCRIPT).await {
+                    Ok(output) => probe.prerequisites(&output.stdout),
+                    Err(e) => {
+                        tracing::debug!("prerequisites probe failed: {}", e);
+                        probe.prerequisites("")
+                    }
+                };
+                Err(DetectionError::NoRuntimeFound(Box::new(prerequisites)))
+            }
+        },
+    }
+}
+
+/// Probe each socket with its own single-binary command.
+///
+/// Fallback for hosts whose login shell cannot run [`probe_script`]; every
+/// command here is a plain binary invocation that needs no shell syntax.
+async fn probe_individually(session: &Session) -> Result<Probe, DetectionError> {
+    let id = session.exec("id -u").await?;
+    let uid = match id.stdout.trim() {
+        uid if id.success() && !uid.is_empty() => Some(uid.to_string()),
+        _ => {
+            tracing::debug!("could not read uid; skipping rootless Podman socket");
+            None
+        }
+    };
+
+    let mut probe = Probe {
+        uid,
+        ..Probe::default()
+    };
+    if let Some(socket) = probe.rootless_socket() {
+        probe.rootless_podman = path_exists(session, &socket).await?;
+    }
+    probe.rootful_podman = path_exists(session, ROOTFUL_PODMAN).await?;
+    probe.docker = path_exists(session, DOCKER_SOCKET).await?;
+    Ok(probe)
+}
+
+/// Whether `path` exists on the host, using `ls` rather than shell `test`.
+async fn path_exists(session: &Session, path: &str) -> Result<bool, DetectionError> {
+    let output = session
+        .exec(&format!("ls -d {}", path))
+        .await?
+        .require_tool("ls")?;
+    Ok(output.success())
+}
+
+/// Shell script that reports installed runtimes and the user's Podman socket unit.
+///
+/// Run only after detection finds no socket. Unit states are printed only
+/// when systemctl exists, so their absence means systemd was not checked.
+const DIAGNOSE_SCRIPT: &str = "command -v podman >/dev/null 2>&1 && echo podman_bin=1; \
+     command -v docker >/dev/null 2>&1 && echo docker_bin=1; \
+     if command -v systemctl >/dev/null 2>&1; then \
+       echo podman_user_unit=$(systemctl --user is-active podman.socket 2>/dev/null); \
+     fi; \
+     true";
+
+/// Shell script that reports the system-wide runtime socket units.
+///
+/// An admin check, run by [`Prerequisites::check_system_units`] on a
+/// session chosen by the caller.
+const SYSTEM_UNITS_SCRIPT: &str = "if command -v systemctl >/dev/null 2>&1; then \
+       echo podman_unit=$(systemctl is-active podman.socket 2>/dev/null); \
+       echo docker_unit=$(systemctl is-active docker.socket 2>/dev/null); \
+     fi; \
+     true";
+
+/// Shell script that checks every candidate socket in one round-trip.
+///
+/// Prints one `key=value` line per finding; missing sockets print nothing.
+fn probe_script() -> String {
+    format!(
+        "uid=$(id -u 2>/dev/null); echo uid=$uid; \
+         test -e /run/user/$uid/podman/podman.sock && echo rootless_podman=1; \
+         test -e {} && echo rootful_podman=1; \
+         test -e {} && echo docker=1; \
+         true",
+        ROOTFUL_PODMAN, DOCKER_SOCKET
+    )
+}
+
+/// Parsed output of [`probe_script`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Probe {
+    uid: Option<String>,
+    rootless_podman: bool,
+    rootful_podman: bool,
+    docker: bool,
+}
+
+impl Probe {
+    fn parse(stdout: &str) -> Self {
+        let mut probe = Probe::default();
+        for line in stdout.lines() {
+            match line.trim().split_once('=') {
+                Some(("uid", uid)) if !uid.is_empty() => probe.uid = Some(uid.to_string()),
+                Some(("rootless_podman", "1")) => probe.rootless_podman = true,
+                Some(("rootful_podman", "1")) => probe.rootful_podman = true,
+                Some(("docker", "1")) => probe.docker = true,
+                _ => {}
+            }
+        }
+        probe
+    }
+
+    fn rootless_socket(&self) -> Option<String> {
+        self.uid
+            .as_ref()
+            .map(|uid| format!("/run/user/{}/podman/podman.sock", uid))
+    }
+
+    /// Pick a runtime in detection order.
+    fn detect(&self) -> Option<RuntimeInfo> {
+        if self.rootless_podman
+            && let Some(socket_path) = self.rootless_socket()
+        {
+            return Some(RuntimeInfo {
                 runtime_type: RuntimeType::Podman,
-                socket_path: rootless_socket,
+                socket_path,
             });
         }
+        if self.rootful_podman {
+            return Some(RuntimeInfo {
+                runtime_type: RuntimeType::Podman,
+                socket_path: ROOTFUL_PODMAN.to_string(),
+            });
+        }
+        if self.docker {
+            return Some(RuntimeInfo {
+                runtime_type: RuntimeType::Docker,
+                socket_path: DOCKER_SOCKET.to_string(),
+            });
+        }
+        None
     }
 
-    // 2. Rootful Podman
-    if session.file_exists(ROOTFUL_PODMAN).await? {
-        return Ok(RuntimeInfo {
-            runtime_type: RuntimeType::Podman,
-            socket_path: ROOTFUL_PODMAN.to_string(),
-        });
+    /// Combine this probe with the output of [`DIAGNOSE_SCRIPT`].
+    fn prerequisites(&self, diagnose_stdout: &str) -> Prerequisites {
+        let mut prerequisites = Prerequisites {
+            sockets_checked: checked_sockets(self.rootless_socket()),
+            root: self.uid.as_deref() == Some("0"),
+            ..Prerequisites::default()
+        };
+        prerequisites.apply(diagnose_stdout);
+        prerequisites
     }
 
-    // 3. Docker
-    if session.file_exists(DOCKER_SOCKET).await? {
-        return Ok(RuntimeInfo {
-            runtime_type: RuntimeType::Docker,
-            socket_path: DOCKER_SOCKET.to_string(),
-        });
+    /// Socket for an explicitly configured runtime.
+    ///
+    /// Podman prefers the rootful socket and falls back to rootless.
+    fn socket_for(&self, runtime_type: RuntimeType) -> String {
+        match runtime_type {
+            RuntimeType::Podman if !self.rootful_podman && self.rootless_podman => self
+                .rootless_socket()
+                .unwrap_or_else(|| default_socket_path(runtime_type)),
+            _ => default_socket_path(runtime_type),
+        }
     }
-
-    Err(DetectionError::NoRuntimeFound)
 }
 
 fn default_socket_path(runtime: RuntimeType) -> String {
@@ -135,3 +428,101 @@ fn default_socket_path(runtime: RuntimeType) -> String {
         RuntimeType::Podman => ROOTFUL_PODMAN.to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_probe_output() {
+        let probe = Probe::parse("uid=1000\nrootless_podman=1\ndocker=1\n");
+        assert_eq!(probe.uid.as_deref(), Some("1000"));
+        assert!(probe.rootless_podman);
+        assert!(!probe.rootful_podman);
+        assert!(probe.docker);
+    }
+
+    #[test]
+    fn rootless_podman_wins() {
+        let probe = Probe::parse("uid=1000\nrootless_podman=1\nrootful_podman=1\ndocker=1\n");
+        let info = probe.detect().unwrap();
+        assert_eq!(info.runtime_type, RuntimeType::Podman);
+        assert_eq!(info.socket_path, "/run/user/1000/podman/podman.sock");
+    }
+
+    #[test]
+    fn falls_back_to_docker() {
+        let info = Probe::parse("uid=0\ndocker=1\n").detect().unwrap();
+        assert_eq!(info.runtime_type, RuntimeType::Docker);
+        assert_eq!(info.socket_path, DOCKER_SOCKET);
+    }
+
+    #[test]
+    fn nothing_found() {
+        assert!(Probe::parse("uid=0\n").detect().is_none());
+    }
+
+    #[test]
+    fn prerequisites_list_checked_sockets_and_units() {
+        let mut prerequisites =
+            Probe::parse("uid=1000\n").prerequisites("podman_bin=1\npodman_user_unit=inactive\n");
+        prerequisites.apply("podman_unit=inactive\ndocker_unit=\n");
+        assert_eq!(
+            prerequisites.sockets_checked,
+            vec![
+                "/run/user/1000/podman/podman.sock".to_string(),
+                ROOTFUL_PODMAN.to_string(),
+                DOCKER_SOCKET.to_string(),
+            ]
+        );
+        assert!(!prerequisites.root);
+        assert!(prerequisites.podman_installed);
+        assert!(!prerequisites.docker_installed);
+        assert_eq!(
+            prerequisites.findings(),
+            vec![
+                "podman: installed (podman.socket inactive, user podman.socket inactive)",
+                "docker: not installed (docker.socket unknown)",
+            ]
+        );
+    }
+
+    #[test]
+    fn rootless_podman_remediation_uses_user_unit() {
+        let prerequisites = Probe::parse("uid=1000\n").prerequisites("podman_bin=1\n");
+        let steps = prerequisites.remediation();
+        assert!(steps[0].contains("systemctl --user enable --now podman.socket"));
+        assert!(!steps.iter().any(|s| s.contains("Install")));
+    }
+
+    #[test]
+    fn missing_runtimes_suggest_install() {
+        let prerequisites = Probe::parse("uid=0\n").prerequisites("");
+        assert!(prerequisites.root);
+        assert!(prerequisites.findings()[0].ends_with("not installed"));
+        assert!(prerequisites.remediation()[0].contains("Install Podman or Docker"));
+    }
+
+    #[test]
+    fn error_names_checked_sockets() {
+        let err =
+            DetectionError::NoRuntimeFound(Box::new(Probe::parse("uid=0\n").prerequisites("")));
+        assert_eq!(
+            err.to_string(),
+            "no container runtime found (checked /run/user/0/podman/podman.sock, /run/podman/podman.sock, /var/run/docker.sock)"
+        );
+    }
+
+    #[test]
+    fn explicit_podman_uses_rootless_when_rootful_missing() {
+        let probe = Probe::parse("uid=1000\nrootless_podman=1\n");
+        assert_eq!(
+            probe.socket_for(RuntimeType::Podman),
+            "/run/user/1000/podman/podman.sock"
+        );
+        assert_eq!(
+            Probe::parse("uid=1000\n").socket_for(RuntimeType::Podman),
+            ROOTFUL_PODMAN
+        );
+    }
+}
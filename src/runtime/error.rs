@@ -3,7 +3,7 @@
 
 use snafu::Snafu;
 
-use super::detection::DetectionError;
+use super::detection::{DetectionError, Prerequisites};
 use super::traits::RuntimeInfoError;
 
 /// Unified runtime error for detection and connection failures.
@@ -35,7 +35,7 @@ impl RuntimeError {
     pub fn kind(&self) -> RuntimeErrorKind {
         match self {
             RuntimeError::Detection { source } => match source {
-                DetectionError::NoRuntimeFound => RuntimeErrorKind::NoRuntimeFound,
+                DetectionError::NoRuntimeFound(_) => RuntimeErrorKind::NoRuntimeFound,
                 DetectionError::Ssh(_) => RuntimeErrorKind::SshError,
             },
             RuntimeError::Connection { source } => match source {
@@ -54,6 +54,16 @@ impl RuntimeError {
             _ => None,
         }
     }
+
+    /// Returns what detection checked if no runtime was found.
+    pub fn prerequisites(&self) -> Option<&Prerequisites> {
+        match self {
+            RuntimeError::Detection {
+                source: DetectionError::NoRuntimeFound(prerequisites),
+            } => Some(prerequisites),
+            _ => None,
+        }
+    }
 }
 
 impl From<DetectionError> for RuntimeError {
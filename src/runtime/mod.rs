@@ -1,23 +1,27 @@
 // ABOUTME: Container runtime detection and trait abstractions.
 // ABOUTME: Auto-detects available runtime, defines composable capability traits.
 
+mod archive;
 mod bollard;
+mod cache;
 mod detection;
 mod error;
 pub mod traits;
 mod types;
 
-pub use bollard::{BollardRuntime, connect_via_session};
-pub use detection::{DetectionError, detect_local, detect_runtime};
+pub use archive::ImageArchive;
+pub use bollard::{BollardRuntime, connect_to_socket, connect_via_session};
+pub use cache::{CacheStats, CachedRuntime};
+pub use detection::{DetectionError, Prerequisites, detect_local, detect_runtime};
 pub use error::{RuntimeError, RuntimeErrorKind};
 pub use types::{RuntimeConfig, RuntimeInfo, RuntimeType};
 
 // Re-export traits at runtime level for convenience
 pub use traits::{
-    ContainerConfig, ContainerError, ContainerFilters, ContainerInfo, ContainerOps, ContainerState,
-    ContainerSummary, ExecConfig, ExecError, ExecOps, ExecResult, HealthState, HealthcheckConfig,
-    ImageError, ImageOps, LogError, LogLine, LogOps, LogOptions, LogStream, NetworkConfig,
-    NetworkError, NetworkOps, PortMapping, Protocol, RegistryAuth, ResourceLimits,
-    RestartPolicyConfig, RuntimeInfo as RuntimeInfoTrait, RuntimeInfoError, RuntimeMetadata,
-    VolumeMount,
+    ChangeKind, ContainerConfig, ContainerError, ContainerFilters, ContainerInfo, ContainerOps,
+    ContainerState, ContainerSummary, ExecConfig, ExecError, ExecOps, ExecResult, FilesystemChange,
+    HealthState, HealthcheckConfig, ImageError, ImageOps, LogError, LogLine, LogOps, LogOptions,
+    LogStream, NetworkConfig, NetworkError, NetworkOps, PortMapping, Protocol, RegistryAuth,
+    ResourceLimits, RestartPolicyConfig, RuntimeInfo as RuntimeInfoTrait, RuntimeInfoError,
+    RuntimeMetadata, VolumeMount,
 };
@@ -2,7 +2,7 @@
 // ABOUTME: Create, start, stop, remove, inspect, and list containers.
 
 use super::sealed::Sealed;
-use super::shared_types::{ContainerConfig, ContainerInfo};
+use super::shared_types::{ContainerConfig, ContainerInfo, FilesystemChange};
 use crate::types::{ContainerId, ServiceName};
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -46,6 +46,15 @@ pub trait ContainerOps: Sealed + Send + Sync {
         new_name: &str,
     ) -> Result<(), ContainerError>;
 
+    /// Files added, changed or deleted in the container's writable layer since it was created.
+    async fn container_changes(
+        &self,
+        id: &ContainerId,
+    ) -> Result<Vec<FilesystemChange>, ContainerError>;
+
+    /// Wait for a container to exit and return its exit code.
+    async fn wait_container(&self, id: &ContainerId) -> Result<i64, ContainerError>;
+
     /// Manually run a health check command for a container.
     ///
     /// This executes the provided command inside the container and returns
@@ -53,6 +62,9 @@ pub trait ContainerOps: Sealed + Send + Sync {
     ///
     /// This is useful for runtimes that don't automatically run health checks
     /// (e.g., rootless Podman without systemd).
+    ///
+    /// Returns [`ContainerError::ExecutableNotFound`] if the command (or a
+    /// tool it calls through `sh -c`) does not exist in the container.
     async fn run_healthcheck(
         &self,
         id: &ContainerId,
@@ -104,7 +116,7 @@ pub struct ContainerSummary {
 }
 
 /// Errors from container operations.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum ContainerError {
     #[error("container not found: {0}")]
     NotFound(String),
@@ -124,6 +136,9 @@ pub enum ContainerError {
     #[error("invalid configuration: {0}")]
     InvalidConfig(String),
 
+    #[error("executable `{0}` not found in container")]
+    ExecutableNotFound(String),
+
     #[error("runtime error: {0}")]
     Runtime(String),
 }
@@ -28,7 +28,7 @@ pub trait ExecOps: Sealed + Send + Sync {
 }
 
 /// Errors from exec operations.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum ExecError {
     #[error("container not found: {0}")]
     ContainerNotFound(String),
@@ -1,12 +1,13 @@
 // ABOUTME: Image operations trait for container runtimes.
-// ABOUTME: Pull, check existence, and remove container images.
+// ABOUTME: Pull, load, check existence, and remove container images.
 
 use super::sealed::Sealed;
 use super::shared_types::RegistryAuth;
 use crate::types::ImageRef;
 use async_trait::async_trait;
+use std::path::Path;
 
-/// Image operations: pull, check existence, remove.
+/// Image operations: pull, load, check existence, remove.
 #[async_trait]
 pub trait ImageOps: Sealed + Send + Sync {
     /// Pull an image from a registry.
@@ -16,15 +17,34 @@ pub trait ImageOps: Sealed + Send + Sync {
         auth: Option<&RegistryAuth>,
     ) -> Result<(), ImageError>;
 
+    /// Load an image from a local tarball, streaming it to the runtime.
+    async fn load_image(&self, archive: &Path) -> Result<(), ImageError>;
+
     /// Check if an image exists locally.
     async fn image_exists(&self, reference: &ImageRef) -> Result<bool, ImageError>;
 
+    /// Get the local image ID for a reference, or None if not present.
+    async fn image_id(&self, reference: &ImageRef) -> Result<Option<String>, ImageError>;
+
+    /// Get the registry manifest digests (`sha256:...`) recorded for a local
+    /// image under the reference's repository. Empty if the image is not
+    /// present or was never pulled from that repository.
+    async fn image_digests(&self, reference: &ImageRef) -> Result<Vec<String>, ImageError>;
+
+    /// Resolve the manifest digest a reference currently points at in its
+    /// registry, without pulling.
+    async fn registry_digest(
+        &self,
+        reference: &ImageRef,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<String, ImageError>;
+
     /// Remove an image.
     async fn remove_image(&self, reference: &ImageRef, force: bool) -> Result<(), ImageError>;
 }
 
 /// Errors from image operations.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum ImageError {
     #[error("image not found: {0}")]
     NotFound(String),
@@ -35,6 +55,9 @@ pub enum ImageError {
     #[error("pull failed: {0}")]
     PullFailed(String),
 
+    #[error("load failed: {0}")]
+    LoadFailed(String),
+
     #[error("image in use, cannot remove: {0}")]
     InUse(String),
 
@@ -35,7 +35,7 @@ pub trait NetworkOps: Sealed + Send + Sync {
 }
 
 /// Errors from network operations.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum NetworkError {
     #[error("network not found: {0}")]
     NotFound(String),
@@ -20,6 +20,10 @@ pub struct ContainerConfig {
     pub ports: Vec<PortMapping>,
     /// Volume mounts.
     pub volumes: Vec<VolumeMount>,
+    /// tmpfs mounts (path -> mount options).
+    pub tmpfs: HashMap<String, String>,
+    /// Mount the root filesystem read-only.
+    pub read_only: bool,
     /// Command to run (overrides image CMD).
     pub command: Option<Vec<String>>,
     /// Entrypoint (overrides image ENTRYPOINT).
@@ -75,7 +79,7 @@ pub struct VolumeMount {
 }
 
 /// Restart policy configuration.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum RestartPolicyConfig {
     /// Never restart.
     No,
@@ -88,6 +92,20 @@ pub enum RestartPolicyConfig {
     OnFailure { max_retries: Option<u32> },
 }
 
+impl std::fmt::Display for RestartPolicyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartPolicyConfig::No => write!(f, "no"),
+            RestartPolicyConfig::Always => write!(f, "always"),
+            RestartPolicyConfig::UnlessStopped => write!(f, "unless-stopped"),
+            RestartPolicyConfig::OnFailure { max_retries: None } => write!(f, "on-failure"),
+            RestartPolicyConfig::OnFailure {
+                max_retries: Some(n),
+            } => write!(f, "on-failure:{}", n),
+        }
+    }
+}
+
 /// Resource limits for a container.
 #[derive(Debug, Clone, Default)]
 pub struct ResourceLimits {
@@ -121,6 +139,8 @@ pub struct ContainerInfo {
     pub name: String,
     /// Image used.
     pub image: String,
+    /// ID of the image the container was created from.
+    pub image_id: String,
     /// Current state.
     pub state: ContainerState,
     /// Health status (if healthcheck configured).
@@ -131,6 +151,34 @@ pub struct ContainerInfo {
     pub labels: HashMap<String, String>,
     /// Network settings.
     pub network_settings: NetworkSettings,
+    /// Restart policy (None if the runtime didn't report one).
+    pub restart_policy: Option<RestartPolicyConfig>,
+    /// Times the runtime has restarted the container.
+    pub restart_count: u32,
+    /// Whether the last termination was caused by the OOM killer.
+    pub oom_killed: bool,
+    /// Exit code of the last termination, if the runtime reported one.
+    pub exit_code: Option<i64>,
+}
+
+impl ContainerInfo {
+    /// Whether the container has been restarted or OOM-killed since creation.
+    pub fn has_crashed(&self) -> bool {
+        self.restart_count > 0 || self.oom_killed
+    }
+
+    /// Short description of crash history, e.g.
+    /// `restarted 3 time(s), OOM killed, last exit code 137`.
+    pub fn crash_summary(&self) -> String {
+        let mut parts = vec![format!("restarted {} time(s)", self.restart_count)];
+        if self.oom_killed {
+            parts.push("OOM killed".to_string());
+        }
+        if let Some(code) = self.exit_code.filter(|c| *c != 0) {
+            parts.push(format!("last exit code {}", code));
+        }
+        parts.join(", ")
+    }
 }
 
 /// Container state.
@@ -265,6 +313,87 @@ pub struct ExecResult {
     pub stderr: Vec<u8>,
 }
 
+impl ExecResult {
+    /// Name of the executable that could not be found when running `cmd`.
+    ///
+    /// Runtimes and shells report a missing binary as exit code 127 (126 with
+    /// a "not found" message on some Docker versions). The name is taken from
+    /// the error output when present (`exec: "curl": executable file not
+    /// found`, `sh: curl: not found`); otherwise the script's first word
+    /// for `sh -c`, or the command itself.
+    pub fn missing_executable(&self, cmd: &[String]) -> Option<String> {
+        let output = format!(
+            "{}{}",
+            String::from_utf8_lossy(&self.stderr),
+            String::from_utf8_lossy(&self.stdout)
+        );
+        let reports_missing = output.contains("not found") || output.contains("no such file");
+        if !(self.exit_code == 127 || (self.exit_code == 126 && reports_missing)) {
+            return None;
+        }
+
+        if let Some(name) = missing_name_from_output(&output) {
+            return Some(name);
+        }
+        match cmd {
+            [shell, flag, script, ..] if flag == "-c" && !shell.is_empty() => script
+                .split_whitespace()
+                .next()
+                .map(str::to_string)
+                .or_else(|| Some(shell.clone())),
+            [first, ..] => Some(first.clone()),
+            [] => None,
+        }
+    }
+}
+
+/// Extract a command name from runtime or shell "not found" messages.
+fn missing_name_from_output(output: &str) -> Option<String> {
+    // Runtime: exec: "curl": executable file not found in $PATH
+    if let Some(rest) = output.split("exec: \"").nth(1)
+        && let Some((name, _)) = rest.split_once('"')
+    {
+        return Some(name.to_string());
+    }
+    // Shells: "sh: curl: not found", "sh: 1: curl: not found", "bash: curl: command not found"
+    output.lines().find_map(|line| {
+        let parts: Vec<&str> = line.split(": ").collect();
+        let pos = parts
+            .iter()
+            .position(|p| p.trim() == "not found" || p.trim() == "command not found")?;
+        (pos >= 2).then(|| parts[pos - 1].trim().to_string())
+    })
+}
+
+/// How a path differs from the image in a container's writable layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeKind {
+    /// Path exists in the image and was modified.
+    Changed,
+    /// Path was created in the container.
+    Added,
+    /// Path was deleted from the container.
+    Deleted,
+}
+
+impl ChangeKind {
+    /// One-letter marker used by `docker diff` / `podman diff` (`C`, `A`, `D`).
+    pub fn marker(&self) -> char {
+        match self {
+            ChangeKind::Changed => 'C',
+            ChangeKind::Added => 'A',
+            ChangeKind::Deleted => 'D',
+        }
+    }
+}
+
+/// A single entry from a container's filesystem diff.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FilesystemChange {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
 /// Exec instance information.
 #[derive(Debug, Clone)]
 pub struct ExecInfo {
@@ -277,3 +406,109 @@ pub struct ExecInfo {
     /// Container ID.
     pub container_id: ContainerId,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> ContainerInfo {
+        ContainerInfo {
+            id: ContainerId::new("abc123".to_string()),
+            name: "app-blue".to_string(),
+            image: "app:v1".to_string(),
+            image_id: "sha256:abc".to_string(),
+            state: ContainerState::Running,
+            health: None,
+            created: String::new(),
+            labels: HashMap::new(),
+            network_settings: NetworkSettings::default(),
+            restart_policy: None,
+            restart_count: 0,
+            oom_killed: false,
+            exit_code: Some(0),
+        }
+    }
+
+    #[test]
+    fn fresh_container_has_not_crashed() {
+        assert!(!info().has_crashed());
+    }
+
+    #[test]
+    fn crash_summary_includes_oom_and_exit_code() {
+        let mut info = info();
+        info.restart_count = 3;
+        info.oom_killed = true;
+        info.exit_code = Some(137);
+        assert!(info.has_crashed());
+        assert_eq!(
+            info.crash_summary(),
+            "restarted 3 time(s), OOM killed, last exit code 137"
+        );
+    }
+
+    #[test]
+    fn crash_summary_omits_zero_exit_code() {
+        let mut info = info();
+        info.restart_count = 1;
+        assert_eq!(info.crash_summary(), "restarted 1 time(s)");
+    }
+
+    fn exec_result(exit_code: i64, stderr: &str) -> ExecResult {
+        ExecResult {
+            exit_code,
+            stdout: Vec::new(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    fn cmd(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn missing_executable_from_runtime_message() {
+        let result = exec_result(
+            126,
+            "OCI runtime exec failed: exec: \"sh\": executable file not found in $PATH",
+        );
+        assert_eq!(
+            result.missing_executable(&cmd(&["sh", "-c", "curl -f localhost"])),
+            Some("sh".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_executable_from_shell_message() {
+        let result = exec_result(127, "sh: 1: curl: not found\n");
+        assert_eq!(
+            result.missing_executable(&cmd(&["sh", "-c", "wget -q localhost && curl localhost"])),
+            Some("curl".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_executable_without_output_uses_command() {
+        let result = exec_result(127, "");
+        assert_eq!(
+            result.missing_executable(&cmd(&["/app/healthcheck", "--quick"])),
+            Some("/app/healthcheck".to_string())
+        );
+        assert_eq!(
+            result.missing_executable(&cmd(&["sh", "-c", "curl -f localhost"])),
+            Some("curl".to_string())
+        );
+    }
+
+    #[test]
+    fn ordinary_failures_are_not_missing_executables() {
+        assert_eq!(
+            exec_result(1, "").missing_executable(&cmd(&["false"])),
+            None
+        );
+        assert_eq!(
+            exec_result(126, "permission denied").missing_executable(&cmd(&["/app/check"])),
+            None
+        );
+    }
+}
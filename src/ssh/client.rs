@@ -1,6 +1,7 @@
 // ABOUTME: SSH session management using russh.
 // ABOUTME: Handles connection, authentication, and command execution.
 
+use super::clock::ClockSkew;
 use super::error::{Error, Result};
 use parking_lot::Mutex;
 use russh::client::{self, Config, Handle};
@@ -12,8 +13,16 @@ use russh::keys::{PrivateKeyWithHashAlg, load_secret_key, ssh_key};
 use russh::{ChannelMsg, Disconnect};
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::net::UnixStream;
+use tokio::sync::Semaphore;
+
+/// Number of unanswered keepalives before the connection is considered dead.
+const KEEPALIVE_MAX: usize = 3;
+
+/// Default limit on concurrent command channels per session. OpenSSH allows
+/// 10 sessions per connection (`MaxSessions`); stay below it.
+const DEFAULT_MAX_CHANNELS: usize = 8;
 
 /// Configuration for establishing an SSH session.
 #[derive(Debug, Clone)]
@@ -35,6 +44,24 @@ pub struct SessionConfig {
     pub known_hosts_path: Option<PathBuf>,
     /// Timeout for command execution (default: 5 minutes).
     pub command_timeout: Duration,
+    /// Close the connection after this long without traffic (default: 30 seconds).
+    /// None disables the inactivity timeout.
+    pub inactivity_timeout: Option<Duration>,
+    /// Interval between SSH keepalive requests (default: 15 seconds).
+    /// Keeps the connection alive during long phases with little traffic,
+    /// such as waiting on a health check. None disables keepalives.
+    pub keepalive_interval: Option<Duration>,
+    /// Local directory for forwarded runtime sockets.
+    /// Defaults to `$PELEKA_RUNTIME_DIR`, falling back to `/tmp`.
+    pub runtime_dir: PathBuf,
+    /// How many times to try reconnecting a dropped session (default: 3).
+    pub reconnect_attempts: u32,
+    /// Delay before the first reconnection attempt, doubled after each
+    /// failure (default: 1 second).
+    pub reconnect_backoff: Duration,
+    /// Most commands run at once on this session (default: 8). Further
+    /// commands wait for a free channel. Socket forwards are not counted.
+    pub max_channels: usize,
 }
 
 impl SessionConfig {
@@ -47,6 +74,12 @@ impl SessionConfig {
             trust_on_first_use: false,
             known_hosts_path: None,
             command_timeout: Duration::from_secs(300), // 5 minutes
+            inactivity_timeout: Some(Duration::from_secs(30)),
+            keepalive_interval: Some(Duration::from_secs(15)),
+            runtime_dir: super::forward::default_runtime_dir(),
+            reconnect_attempts: 3,
+            reconnect_backoff: Duration::from_secs(1),
+            max_channels: DEFAULT_MAX_CHANNELS,
         }
     }
 
@@ -74,6 +107,36 @@ impl SessionConfig {
         self.command_timeout = timeout;
         self
     }
+
+    pub fn inactivity_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.inactivity_timeout = timeout;
+        self
+    }
+
+    pub fn keepalive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
+
+    pub fn runtime_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.runtime_dir = dir.into();
+        self
+    }
+
+    pub fn reconnect_attempts(mut self, attempts: u32) -> Self {
+        self.reconnect_attempts = attempts;
+        self
+    }
+
+    pub fn reconnect_backoff(mut self, backoff: Duration) -> Self {
+        self.reconnect_backoff = backoff;
+        self
+    }
+
+    pub fn max_channels(mut self, max: usize) -> Self {
+        self.max_channels = max.max(1);
+        self
+    }
 }
 
 /// Output from a remote command execution.
@@ -87,10 +150,30 @@ pub struct CommandOutput {
     pub stderr: String,
 }
 
+/// Exit code shells use when a command does not exist.
+const COMMAND_NOT_FOUND: u32 = 127;
+
 impl CommandOutput {
     pub fn success(&self) -> bool {
         self.exit_code == 0
     }
+
+    /// Whether the shell could not find the command that was run.
+    pub fn command_not_found(&self) -> bool {
+        self.exit_code == COMMAND_NOT_FOUND
+    }
+
+    /// Fail with [`Error::ToolNotFound`] naming `tool` if it was not found.
+    ///
+    /// Turns a missing binary on minimal hosts into an explicit error
+    /// instead of a confusing parse failure on empty output.
+    pub fn require_tool(self, tool: &str) -> Result<Self> {
+        if self.command_not_found() {
+            Err(Error::ToolNotFound(tool.to_string()))
+        } else {
+            Ok(self)
+        }
+    }
 }
 
 /// SSH client handler for russh.
@@ -172,14 +255,44 @@ enum AuthMethod {
     KeyFile(Arc<ssh_key::PrivateKey>),
 }
 
+/// Time spent in each step of establishing a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectTimings {
+    /// TCP connect plus SSH key exchange and host key verification.
+    pub handshake: Duration,
+    /// User authentication.
+    pub auth: Duration,
+}
+
 /// An established SSH session.
+///
+/// A session is cheap to clone and safe to use from many tasks at once.
+/// Clones share one connection: every `exec` opens its own channel, so
+/// concurrent commands never see each other's output, and at most
+/// `max_channels` commands run at a time while the rest wait their turn.
+/// A command's timeout starts once it has a channel. Forwarded sockets and
+/// the measured clock skew are shared by all clones, and `disconnect` on any
+/// clone closes the connection for all of them.
+#[derive(Clone)]
 pub struct Session {
     config: SessionConfig,
     handle: Arc<Handle<SshHandler>>,
     /// Active socket forwarders.
-    forwarders: Mutex<Vec<super::forward::ForwardHandle>>,
+    forwarders: Arc<Mutex<Vec<super::forward::ForwardHandle>>>,
+    /// Clock skew, measured on first use.
+    clock_skew: Arc<Mutex<Option<ClockSkew>>>,
+    /// Permits for command channels, limiting concurrent `exec` calls.
+    channels: Arc<Semaphore>,
+    /// How long the session took to establish.
+    timings: ConnectTimings,
 }
 
+// Sessions are shared between concurrent tasks; keep them shareable.
+const _: () = {
+    const fn assert_shareable<T: Clone + Send + Sync>() {}
+    assert_shareable::<Session>();
+};
+
 impl std::fmt::Debug for Session {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Session")
@@ -195,9 +308,13 @@ impl Session {
         // Resolve authentication method
         let auth_method = Self::resolve_auth_method(&config).await?;
 
-        // Configure client
+        // Configure client. Keepalives count as traffic, so a keepalive interval
+        // shorter than the inactivity timeout holds the connection open while
+        // a long-running phase (e.g. a 10 minute health check) is mostly idle.
         let russh_config = Config {
-            inactivity_timeout: Some(Duration::from_secs(30)),
+            inactivity_timeout: config.inactivity_timeout,
+            keepalive_interval: config.keepalive_interval,
+            keepalive_max: KEEPALIVE_MAX,
             ..Default::default()
         };
 
@@ -209,6 +326,7 @@ impl Session {
         );
 
         // Connect
+        let started = Instant::now();
         let mut session = client::connect(
             Arc::new(russh_config),
             (config.host.as_str(), config.port),
@@ -226,19 +344,69 @@ impl Session {
             }
         })?;
 
+        let handshake = started.elapsed();
+
         // Authenticate
+        let started = Instant::now();
         let auth_success = Self::authenticate(&mut session, &config, auth_method).await?;
         if !auth_success {
             return Err(Error::AuthenticationFailed);
         }
+        let auth = started.elapsed();
 
         Ok(Self {
+            channels: Arc::new(Semaphore::new(config.max_channels)),
             config,
             handle: Arc::new(session),
-            forwarders: Mutex::new(Vec::new()),
+            forwarders: Arc::new(Mutex::new(Vec::new())),
+            clock_skew: Arc::new(Mutex::new(None)),
+            timings: ConnectTimings { handshake, auth },
         })
     }
 
+    /// Open a fresh session with the same configuration after this one dropped.
+    ///
+    /// Tries up to `reconnect_attempts` times, doubling the delay between
+    /// attempts. Authentication and host key failures are returned at once
+    /// since retrying cannot fix them. Forwarded sockets are not carried over.
+    pub async fn reconnect(&self) -> Result<Self> {
+        let mut backoff = self.config.reconnect_backoff;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            tokio::time::sleep(backoff).await;
+            match Self::connect(self.config.clone()).await {
+                Ok(session) => return Ok(session),
+                Err(e) if e.is_transient() && attempt < self.config.reconnect_attempts => {
+                    tracing::debug!(
+                        "Reconnect attempt {} to {} failed: {}",
+                        attempt,
+                        self.config.host,
+                        e
+                    );
+                    backoff *= 2;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Whether the underlying connection has been closed, e.g. by a network
+    /// failure, an sshd restart or missed keepalives.
+    pub fn is_closed(&self) -> bool {
+        self.handle.is_closed()
+    }
+
+    /// Configuration this session was opened with.
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// Time spent on the handshake and authentication when connecting.
+    pub fn connect_timings(&self) -> ConnectTimings {
+        self.timings
+    }
+
     /// Resolve which authentication method to use.
     async fn resolve_auth_method(config: &SessionConfig) -> Result<AuthMethod> {
         // If key path specified, use that
@@ -329,6 +497,30 @@ impl Session {
         Ok(output.success() && output.stdout.trim() == "exists")
     }
 
+    /// Offset of the server's clock relative to the local clock.
+    ///
+    /// Measured once per session with a single `date` call and cached.
+    pub async fn clock_skew(&self) -> Result<ClockSkew> {
+        if let Some(skew) = *self.clock_skew.lock() {
+            return Ok(skew);
+        }
+
+        let before = chrono::Utc::now();
+        let output = self.exec("date -u +%s").await?.require_tool("date")?;
+        let after = chrono::Utc::now();
+
+        let server_secs: i64 = output.stdout.trim().parse().map_err(|_| {
+            Error::CommandFailed(format!(
+                "unexpected output from date: {:?}",
+                output.stdout.trim()
+            ))
+        })?;
+
+        let skew = ClockSkew::measure(before, server_secs, after);
+        *self.clock_skew.lock() = Some(skew);
+        Ok(skew)
+    }
+
     /// Execute a command on the remote host.
     pub async fn exec(&self, command: &str) -> Result<CommandOutput> {
         self.exec_with_timeout(command, self.config.command_timeout)
@@ -336,11 +528,18 @@ impl Session {
     }
 
     /// Execute a command with a custom timeout.
+    ///
+    /// Waits for a free channel first if `max_channels` commands are running.
     pub async fn exec_with_timeout(
         &self,
         command: &str,
         timeout: Duration,
     ) -> Result<CommandOutput> {
+        let _permit = self
+            .channels
+            .acquire()
+            .await
+            .map_err(|_| Error::ChannelClosed)?;
         match tokio::time::timeout(timeout, self.exec_inner(command)).await {
             Ok(result) => result,
             Err(_) => Err(Error::CommandTimeout(timeout)),
@@ -418,9 +617,12 @@ impl Session {
     /// Creates a local socket that tunnels all connections through SSH to
     /// the specified remote socket path. Returns the path to the local socket.
     pub async fn forward_socket(&self, remote_socket: &str) -> Result<String> {
-        let forward_handle =
-            super::forward::start_forward(Arc::clone(&self.handle), remote_socket.to_string())
-                .await?;
+        let forward_handle = super::forward::start_forward(
+            Arc::clone(&self.handle),
+            remote_socket.to_string(),
+            &self.config.runtime_dir,
+        )
+        .await?;
         let path = forward_handle
             .path()
             .ok_or_else(|| {
@@ -431,8 +633,21 @@ impl Session {
         Ok(path)
     }
 
-    /// Disconnect the session.
+    /// Path of the first local socket forwarded by this session, if any.
+    ///
+    /// This is the runtime socket once a runtime has been connected.
+    pub fn forwarded_socket(&self) -> Option<String> {
+        self.forwarders
+            .lock()
+            .first()
+            .and_then(|f| f.path().map(str::to_string))
+    }
+
+    /// Disconnect the session, closing the connection for every clone.
     pub async fn disconnect(self) -> Result<()> {
+        // Commands waiting for a channel fail instead of using a dead connection
+        self.channels.close();
+
         // Stop all forwarders first (drain to Vec to release lock before await)
         let forwarders: Vec<_> = self.forwarders.lock().drain(..).collect();
         for forwarder in forwarders {
@@ -0,0 +1,102 @@
+// ABOUTME: Clock skew measurement between the local machine and a server.
+// ABOUTME: Lets lock TTLs use the server clock and warns when clocks disagree.
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::time::Duration;
+
+/// Skew above which peleka warns that clocks disagree.
+pub const CLOCK_SKEW_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Offset of a server's clock relative to the local clock.
+///
+/// Positive when the server is ahead. Measured with one-second resolution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClockSkew {
+    offset_ms: i64,
+}
+
+impl ClockSkew {
+    /// Build from a known offset in milliseconds (server minus local).
+    pub fn from_millis(offset_ms: i64) -> Self {
+        Self { offset_ms }
+    }
+
+    /// Compute the skew from a server timestamp (Unix seconds) read between
+    /// two local instants. The midpoint of the round trip is taken as the
+    /// local time at which the server sampled its clock.
+    pub fn measure(before: DateTime<Utc>, server_secs: i64, after: DateTime<Utc>) -> Self {
+        let local_ms =
+            before.timestamp_millis() + (after.timestamp_millis() - before.timestamp_millis()) / 2;
+        // The server truncates to whole seconds; assume the middle of that second.
+        let server_ms = server_secs * 1000 + 500;
+        Self::from_millis(server_ms - local_ms)
+    }
+
+    /// Signed offset (server minus local).
+    pub fn offset(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.offset_ms)
+    }
+
+    /// Absolute size of the skew.
+    pub fn magnitude(&self) -> Duration {
+        Duration::from_millis(self.offset_ms.unsigned_abs())
+    }
+
+    /// Whether the skew is larger than `threshold`.
+    pub fn exceeds(&self, threshold: Duration) -> bool {
+        self.magnitude() > threshold
+    }
+
+    /// Translate a local timestamp to the server's clock.
+    pub fn to_server_time(&self, local: DateTime<Utc>) -> DateTime<Utc> {
+        local + self.offset()
+    }
+}
+
+impl fmt::Display for ClockSkew {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.magnitude().as_secs();
+        if self.offset_ms >= 0 {
+            write!(f, "{}s ahead of local clock", secs)
+        } else {
+            write!(f, "{}s behind local clock", secs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    #[test]
+    fn in_sync_clocks_have_small_skew() {
+        let skew = ClockSkew::measure(at(1000), 1000, at(1001));
+        assert!(!skew.exceeds(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn server_ahead_is_positive() {
+        let skew = ClockSkew::measure(at(1000), 1060, at(1000));
+        assert!(skew.offset() > chrono::Duration::zero());
+        assert!(skew.exceeds(CLOCK_SKEW_THRESHOLD));
+        assert_eq!(skew.to_string(), "60s ahead of local clock");
+    }
+
+    #[test]
+    fn server_behind_is_negative() {
+        let skew = ClockSkew::measure(at(1000), 900, at(1000));
+        assert!(skew.offset() < chrono::Duration::zero());
+        assert!(skew.to_string().contains("behind"));
+    }
+
+    #[test]
+    fn to_server_time_applies_offset() {
+        let skew = ClockSkew::from_millis(5_000);
+        assert_eq!(skew.to_server_time(at(1000)), at(1005));
+    }
+}
@@ -21,6 +21,9 @@ pub enum Error {
     #[error("command execution failed: {0}")]
     CommandFailed(String),
 
+    #[error("`{0}` not found on the server")]
+    ToolNotFound(String),
+
     #[error("command timed out after {0:?}")]
     CommandTimeout(std::time::Duration),
 
@@ -40,4 +43,15 @@ pub enum Error {
     Io(#[from] std::io::Error),
 }
 
+impl Error {
+    /// Whether the error comes from the connection itself, so that trying
+    /// again later may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Error::Connection(_) | Error::ChannelClosed | Error::Protocol(_) | Error::Io(_)
+        )
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -5,13 +5,71 @@ use super::client::SshHandler;
 use super::error::{Error, Result};
 use russh::ChannelMsg;
 use russh::client::Handle;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::Notify;
 
+/// Environment variable that overrides where local sockets are created.
+pub const RUNTIME_DIR_ENV: &str = "PELEKA_RUNTIME_DIR";
+
+/// Longest path a Unix socket can be bound to (`sun_path` minus the NUL).
+const MAX_SOCKET_PATH: usize = 103;
+
+/// Default directory for local forwarded sockets.
+///
+/// Uses `$PELEKA_RUNTIME_DIR` when set and non-empty, otherwise `/tmp`.
+pub fn default_runtime_dir() -> PathBuf {
+    match std::env::var_os(RUNTIME_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from("/tmp"),
+    }
+}
+
+/// Remove sockets left behind by peleka processes that are no longer running.
+///
+/// A crashed run skips the cleanup in [`ForwardHandle`]'s drop, leaving
+/// `peleka-{pid}-{n}.sock` files behind. A socket is stale when nothing
+/// accepts connections on it; sockets of the current process and of live
+/// runs are left alone. Returns how many sockets were removed.
+pub fn cleanup_stale_sockets(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let own_pid = std::process::id();
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name.to_str().and_then(socket_owner) else {
+            continue;
+        };
+        if pid == own_pid {
+            continue;
+        }
+        let path = entry.path();
+        let refused = matches!(
+            std::os::unix::net::UnixStream::connect(&path),
+            Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused
+        );
+        if refused && std::fs::remove_file(&path).is_ok() {
+            tracing::debug!("removed stale socket {}", path.display());
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Pid of the process that created a socket named `peleka-{pid}-{n}.sock`.
+fn socket_owner(file_name: &str) -> Option<u32> {
+    let rest = file_name.strip_prefix("peleka-")?.strip_suffix(".sock")?;
+    let (pid, count) = rest.split_once('-')?;
+    count.parse::<u64>().ok()?;
+    pid.parse().ok()
+}
+
 /// Handle for managing a forwarded socket.
 pub struct ForwardHandle {
     /// Path to the local socket.
@@ -51,14 +109,21 @@ impl Drop for ForwardHandle {
 
 /// Start forwarding a local Unix socket to a remote Unix socket.
 ///
-/// Creates a local socket at `/tmp/peleka-{pid}-{counter}.sock` and forwards
-/// all connections through SSH to the specified remote socket path.
+/// Creates a local socket at `{runtime_dir}/peleka-{pid}-{counter}.sock` and
+/// forwards all connections through SSH to the specified remote socket path.
 pub async fn start_forward(
     handle: Arc<Handle<SshHandler>>,
     remote_socket: String,
+    runtime_dir: &Path,
 ) -> Result<ForwardHandle> {
     // Generate unique local socket path
-    let local_path = generate_socket_path();
+    let local_path = generate_socket_path(runtime_dir);
+    if local_path.as_os_str().len() > MAX_SOCKET_PATH {
+        return Err(Error::SocketForwardFailed(format!(
+            "local socket path {:?} is longer than {} bytes; set {} to a shorter directory",
+            local_path, MAX_SOCKET_PATH, RUNTIME_DIR_ENV
+        )));
+    }
 
     // Ensure parent directory exists and remove any stale socket
     if let Some(parent) = local_path.parent() {
@@ -95,14 +160,14 @@ pub async fn start_forward(
     Ok(forward_handle)
 }
 
-/// Generate a unique local socket path.
-fn generate_socket_path() -> PathBuf {
+/// Generate a unique local socket path inside `dir`.
+fn generate_socket_path(dir: &Path) -> PathBuf {
     use std::sync::atomic::AtomicU64;
     static COUNTER: AtomicU64 = AtomicU64::new(0);
 
     let pid = std::process::id();
     let count = COUNTER.fetch_add(1, Ordering::SeqCst);
-    PathBuf::from(format!("/tmp/peleka-{}-{}.sock", pid, count))
+    dir.join(format!("peleka-{}-{}.sock", pid, count))
 }
 
 /// Run the forwarder, accepting connections and forwarding them.
@@ -221,3 +286,50 @@ async fn forward_connection(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_socket_owner() {
+        assert_eq!(socket_owner("peleka-4242-0.sock"), Some(4242));
+        assert_eq!(socket_owner("peleka-4242-17.sock"), Some(4242));
+        assert_eq!(socket_owner("peleka-4242.sock"), None);
+        assert_eq!(socket_owner("other-4242-0.sock"), None);
+        assert_eq!(socket_owner("peleka-abc-0.sock"), None);
+    }
+
+    #[test]
+    fn socket_path_uses_runtime_dir() {
+        let path = generate_socket_path(Path::new("/run/user/1000"));
+        assert!(path.starts_with("/run/user/1000"));
+        assert!(socket_owner(path.file_name().unwrap().to_str().unwrap()).is_some());
+    }
+
+    #[test]
+    fn runtime_dir_from_env() {
+        temp_env::with_var(RUNTIME_DIR_ENV, Some("/var/run/peleka"), || {
+            assert_eq!(default_runtime_dir(), PathBuf::from("/var/run/peleka"));
+        });
+        temp_env::with_var(RUNTIME_DIR_ENV, None::<&str>, || {
+            assert_eq!(default_runtime_dir(), PathBuf::from("/tmp"));
+        });
+    }
+
+    #[test]
+    fn removes_only_dead_sockets() {
+        let dir = tempfile::tempdir().unwrap();
+        let stale = dir.path().join("peleka-1-0.sock");
+        drop(std::os::unix::net::UnixListener::bind(&stale).unwrap());
+        let live = dir.path().join("peleka-2-0.sock");
+        let _listener = std::os::unix::net::UnixListener::bind(&live).unwrap();
+        let unrelated = dir.path().join("app.sock");
+        drop(std::os::unix::net::UnixListener::bind(&unrelated).unwrap());
+
+        assert_eq!(cleanup_stale_sockets(dir.path()), 1);
+        assert!(!stale.exists());
+        assert!(live.exists());
+        assert!(unrelated.exists());
+    }
+}
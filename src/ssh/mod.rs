@@ -2,9 +2,11 @@
 // ABOUTME: Supports SSH agent and key-based authentication with known_hosts verification.
 
 mod client;
+mod clock;
 mod error;
 mod forward;
 
-pub use client::{CommandOutput, Session, SessionConfig};
+pub use client::{CommandOutput, ConnectTimings, Session, SessionConfig};
+pub use clock::{CLOCK_SKEW_THRESHOLD, ClockSkew};
 pub use error::{Error, Result};
-pub use forward::ForwardHandle;
+pub use forward::{ForwardHandle, RUNTIME_DIR_ENV, cleanup_stale_sockets, default_runtime_dir};
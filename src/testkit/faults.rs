@@ -0,0 +1,1031 @@
+// ABOUTME: Failure injection and deterministic record/replay of runtime calls (feature `testkit`).
+// ABOUTME: Drives the deploy state machine down rollback and recovery paths without a flaky daemon.
+
+use crate::runtime::traits::sealed::Sealed;
+use crate::runtime::{
+    ContainerConfig, ContainerError, ContainerFilters, ContainerInfo, ContainerOps,
+    ContainerSummary, ExecConfig, ExecError, ExecOps, ExecResult, FilesystemChange, ImageError,
+    ImageOps, NetworkConfig, NetworkError, NetworkOps, RegistryAuth,
+};
+use crate::types::{ContainerId, ImageRef, NetworkAlias, NetworkId};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+
+/// A runtime operation, as seen by [`FaultRuntime`] and [`ReplayRuntime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    PullImage,
+    LoadImage,
+    ImageExists,
+    ImageId,
+    ImageDigests,
+    RegistryDigest,
+    RemoveImage,
+    CreateContainer,
+    StartContainer,
+    StopContainer,
+    RemoveContainer,
+    InspectContainer,
+    ListContainers,
+    RenameContainer,
+    ContainerChanges,
+    WaitContainer,
+    RunHealthcheck,
+    CreateNetwork,
+    RemoveNetwork,
+    ConnectToNetwork,
+    DisconnectFromNetwork,
+    NetworkExists,
+    Exec,
+    ExecCreate,
+    ExecStart,
+}
+
+/// Named points in the deploy state machine where a failure can be injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InjectionPoint {
+    /// Creating the new container, once the image is in place.
+    AfterPull,
+    /// Starting the new container.
+    ContainerStart,
+    /// The first healthcheck command run against the new container.
+    HealthCheck,
+    /// Attaching the service alias to the new container.
+    Cutover,
+    /// Stopping the old container after traffic moved.
+    Cleanup,
+}
+
+impl InjectionPoint {
+    /// Name used in injected error messages.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InjectionPoint::AfterPull => "after-pull",
+            InjectionPoint::ContainerStart => "container-start",
+            InjectionPoint::HealthCheck => "health-check",
+            InjectionPoint::Cutover => "cutover",
+            InjectionPoint::Cleanup => "cleanup",
+        }
+    }
+}
+
+/// A failure to inject: the `nth` call of `op`, counting only calls made
+/// after the first `after` call when set. Each fault fires once.
+#[derive(Debug, Clone)]
+pub struct Fault {
+    op: Op,
+    after: Option<Op>,
+    nth: usize,
+    name: String,
+}
+
+impl Fault {
+    /// Fail the first call of `op`.
+    pub fn on(op: Op) -> Self {
+        Self {
+            op,
+            after: None,
+            nth: 1,
+            name: format!("{:?}", op),
+        }
+    }
+
+    /// Fail the `n`th call (1-based) instead of the first.
+    pub fn nth(mut self, n: usize) -> Self {
+        self.nth = n.max(1);
+        self
+    }
+
+    /// Only count calls made after the first call of `op`.
+    pub fn after(mut self, op: Op) -> Self {
+        self.after = Some(op);
+        self
+    }
+}
+
+impl From<InjectionPoint> for Fault {
+    fn from(point: InjectionPoint) -> Self {
+        let fault = match point {
+            InjectionPoint::AfterPull => Fault::on(Op::CreateContainer),
+            InjectionPoint::ContainerStart => Fault::on(Op::StartContainer),
+            InjectionPoint::HealthCheck => Fault::on(Op::RunHealthcheck),
+            InjectionPoint::Cutover => Fault::on(Op::ConnectToNetwork),
+            InjectionPoint::Cleanup => Fault::on(Op::StopContainer).after(Op::ConnectToNetwork),
+        };
+        Fault {
+            name: point.as_str().to_string(),
+            ..fault
+        }
+    }
+}
+
+/// What a runtime call returned, kept so it can be replayed.
+#[derive(Debug, Clone)]
+pub enum Reply {
+    Image(Result<(), ImageError>),
+    ImageExists(Result<bool, ImageError>),
+    ImageId(Result<Option<String>, ImageError>),
+    ImageDigests(Result<Vec<String>, ImageError>),
+    RegistryDigest(Result<String, ImageError>),
+    Created(Result<ContainerId, ContainerError>),
+    Container(Result<(), ContainerError>),
+    Inspected(Result<ContainerInfo, ContainerError>),
+    Listed(Result<Vec<ContainerSummary>, ContainerError>),
+    Changes(Result<Vec<FilesystemChange>, ContainerError>),
+    Exited(Result<i64, ContainerError>),
+    Healthy(Result<bool, ContainerError>),
+    NetworkCreated(Result<NetworkId, NetworkError>),
+    Network(Result<(), NetworkError>),
+    NetworkExists(Result<bool, NetworkError>),
+    Exec(Result<ExecResult, ExecError>),
+    ExecCreated(Result<String, ExecError>),
+}
+
+/// One recorded runtime call.
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub op: Op,
+    /// Image, container, network or exec the call was about.
+    pub target: String,
+    pub reply: Reply,
+    /// Whether the reply came from an injected fault.
+    pub injected: bool,
+}
+
+/// Ordered runtime calls of one run.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    calls: Vec<Call>,
+}
+
+impl Recording {
+    /// Empty recording, for scripting replies by hand with [`Recording::push`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a scripted call.
+    pub fn push(&mut self, op: Op, target: impl Into<String>, reply: Reply) -> &mut Self {
+        self.calls.push(Call {
+            op,
+            target: target.into(),
+            reply,
+            injected: false,
+        });
+        self
+    }
+
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// The sequence of operations, for asserting on call order.
+    pub fn ops(&self) -> Vec<Op> {
+        self.calls.iter().map(|c| c.op).collect()
+    }
+}
+
+/// Error types that can carry an injected or replay failure.
+trait Injectable {
+    fn injected(message: String) -> Self;
+}
+
+impl Injectable for ImageError {
+    fn injected(message: String) -> Self {
+        ImageError::Runtime(message)
+    }
+}
+
+impl Injectable for ContainerError {
+    fn injected(message: String) -> Self {
+        ContainerError::Runtime(message)
+    }
+}
+
+impl Injectable for NetworkError {
+    fn injected(message: String) -> Self {
+        NetworkError::Runtime(message)
+    }
+}
+
+impl Injectable for ExecError {
+    fn injected(message: String) -> Self {
+        ExecError::Runtime(message)
+    }
+}
+
+struct FaultState {
+    faults: Vec<Fault>,
+    calls: Vec<Call>,
+}
+
+impl FaultState {
+    /// Remove and return the fault that fires for this call of `op`, if any.
+    fn take_fault(&mut self, op: Op) -> Option<Fault> {
+        let index = self.faults.iter().position(|fault| {
+            if fault.op != op {
+                return false;
+            }
+            let start = match fault.after {
+                Some(after) => match self.calls.iter().position(|c| c.op == after) {
+                    Some(i) => i + 1,
+                    None => return false,
+                },
+                None => 0,
+            };
+            let seen = self.calls[start..].iter().filter(|c| c.op == op).count();
+            seen + 1 == fault.nth
+        })?;
+        Some(self.faults.remove(index))
+    }
+}
+
+/// Runtime wrapper that records every call and fails the ones matching a
+/// [`Fault`].
+///
+/// Injected failures are the runtime error variant of the operation
+/// (`ContainerError::Runtime`, ...) with a message naming the fault, so the
+/// deploy code handles them like a daemon failure.
+pub struct FaultRuntime<R> {
+    inner: R,
+    state: Mutex<FaultState>,
+}
+
+impl<R> FaultRuntime<R> {
+    /// Wrap a runtime with no faults.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: Mutex::new(FaultState {
+                faults: Vec::new(),
+                calls: Vec::new(),
+            }),
+        }
+    }
+
+    /// Add a fault, e.g. `InjectionPoint::Cutover` or `Fault::on(Op::StopContainer).nth(2)`.
+    pub fn fail(self, fault: impl Into<Fault>) -> Self {
+        self.state.lock().faults.push(fault.into());
+        self
+    }
+
+    /// Access the wrapped runtime (calls through it are not recorded).
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Calls made so far.
+    pub fn recording(&self) -> Recording {
+        Recording {
+            calls: self.state.lock().calls.clone(),
+        }
+    }
+
+    /// Whether every fault has fired.
+    pub fn all_faults_fired(&self) -> bool {
+        self.state.lock().faults.is_empty()
+    }
+
+    async fn intercept<T, E, F>(
+        &self,
+        op: Op,
+        target: String,
+        wrap: fn(Result<T, E>) -> Reply,
+        call: F,
+    ) -> Result<T, E>
+    where
+        T: Clone,
+        E: Injectable + Clone,
+        F: Future<Output = Result<T, E>>,
+    {
+        let fault = self.state.lock().take_fault(op);
+        let (result, injected) = match fault {
+            Some(fault) => (
+                Err(E::injected(format!("injected failure at {}", fault.name))),
+                true,
+            ),
+            None => (call.await, false),
+        };
+        self.state.lock().calls.push(Call {
+            op,
+            target,
+            reply: wrap(result.clone()),
+            injected,
+        });
+        result
+    }
+}
+
+impl<R> Sealed for FaultRuntime<R> {}
+
+#[async_trait]
+impl<R: ImageOps> ImageOps for FaultRuntime<R> {
+    async fn pull_image(
+        &self,
+        reference: &ImageRef,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<(), ImageError> {
+        self.intercept(
+            Op::PullImage,
+            reference.to_string(),
+            Reply::Image,
+            self.inner.pull_image(reference, auth),
+        )
+        .await
+    }
+
+    async fn load_image(&self, archive: &Path) -> Result<(), ImageError> {
+        self.intercept(
+            Op::LoadImage,
+            archive.display().to_string(),
+            Reply::Image,
+            self.inner.load_image(archive),
+        )
+        .await
+    }
+
+    async fn image_exists(&self, reference: &ImageRef) -> Result<bool, ImageError> {
+        self.intercept(
+            Op::ImageExists,
+            reference.to_string(),
+            Reply::ImageExists,
+            self.inner.image_exists(reference),
+        )
+        .await
+    }
+
+    async fn image_id(&self, reference: &ImageRef) -> Result<Option<String>, ImageError> {
+        self.intercept(
+            Op::ImageId,
+            reference.to_string(),
+            Reply::ImageId,
+            self.inner.image_id(reference),
+        )
+        .await
+    }
+
+    async fn image_digests(&self, reference: &ImageRef) -> Result<Vec<String>, ImageError> {
+        self.intercept(
+            Op::ImageDigests,
+            reference.to_string(),
+            Reply::ImageDigests,
+            self.inner.image_digests(reference),
+        )
+        .await
+    }
+
+    async fn registry_digest(
+        &self,
+        reference: &ImageRef,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<String, ImageError> {
+        self.intercept(
+            Op::RegistryDigest,
+            reference.to_string(),
+            Reply::RegistryDigest,
+            self.inner.registry_digest(reference, auth),
+        )
+        .await
+    }
+
+    async fn remove_image(&self, reference: &ImageRef, force: bool) -> Result<(), ImageError> {
+        self.intercept(
+            Op::RemoveImage,
+            reference.to_string(),
+            Reply::Image,
+            self.inner.remove_image(reference, force),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<R: ContainerOps> ContainerOps for FaultRuntime<R> {
+    async fn create_container(
+        &self,
+        config: &ContainerConfig,
+    ) -> Result<ContainerId, ContainerError> {
+        self.intercept(
+            Op::CreateContainer,
+            config.name.clone(),
+            Reply::Created,
+            self.inner.create_container(config),
+        )
+        .await
+    }
+
+    async fn start_container(&self, id: &ContainerId) -> Result<(), ContainerError> {
+        self.intercept(
+            Op::StartContainer,
+            id.to_string(),
+            Reply::Container,
+            self.inner.start_container(id),
+        )
+        .await
+    }
+
+    async fn stop_container(
+        &self,
+        id: &ContainerId,
+        timeout: Duration,
+    ) -> Result<(), ContainerError> {
+        self.intercept(
+            Op::StopContainer,
+            id.to_string(),
+            Reply::Container,
+            self.inner.stop_container(id, timeout),
+        )
+        .await
+    }
+
+    async fn remove_container(&self, id: &ContainerId, force: bool) -> Result<(), ContainerError> {
+        self.intercept(
+            Op::RemoveContainer,
+            id.to_string(),
+            Reply::Container,
+            self.inner.remove_container(id, force),
+        )
+        .await
+    }
+
+    async fn inspect_container(&self, id: &ContainerId) -> Result<ContainerInfo, ContainerError> {
+        self.intercept(
+            Op::InspectContainer,
+            id.to_string(),
+            Reply::Inspected,
+            self.inner.inspect_container(id),
+        )
+        .await
+    }
+
+    async fn list_containers(
+        &self,
+        filters: &ContainerFilters,
+    ) -> Result<Vec<ContainerSummary>, ContainerError> {
+        self.intercept(
+            Op::ListContainers,
+            filters.name.clone().unwrap_or_default(),
+            Reply::Listed,
+            self.inner.list_containers(filters),
+        )
+        .await
+    }
+
+    async fn rename_container(
+        &self,
+        id: &ContainerId,
+        new_name: &str,
+    ) -> Result<(), ContainerError> {
+        self.intercept(
+            Op::RenameContainer,
+            id.to_string(),
+            Reply::Container,
+            self.inner.rename_container(id, new_name),
+        )
+        .await
+    }
+
+    async fn container_changes(
+        &self,
+        id: &ContainerId,
+    ) -> Result<Vec<FilesystemChange>, ContainerError> {
+        self.intercept(
+            Op::ContainerChanges,
+            id.to_string(),
+            Reply::Changes,
+            self.inner.container_changes(id),
+        )
+        .await
+    }
+
+    async fn wait_container(&self, id: &ContainerId) -> Result<i64, ContainerError> {
+        self.intercept(
+            Op::WaitContainer,
+            id.to_string(),
+            Reply::Exited,
+            self.inner.wait_container(id),
+        )
+        .await
+    }
+
+    async fn run_healthcheck(
+        &self,
+        id: &ContainerId,
+        cmd: &[String],
+    ) -> Result<bool, ContainerError> {
+        self.intercept(
+            Op::RunHealthcheck,
+            id.to_string(),
+            Reply::Healthy,
+            self.inner.run_healthcheck(id, cmd),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<R: NetworkOps> NetworkOps for FaultRuntime<R> {
+    async fn create_network(&self, config: &NetworkConfig) -> Result<NetworkId, NetworkError> {
+        self.intercept(
+            Op::CreateNetwork,
+            config.name.clone(),
+            Reply::NetworkCreated,
+            self.inner.create_network(config),
+        )
+        .await
+    }
+
+    async fn remove_network(&self, id: &NetworkId) -> Result<(), NetworkError> {
+        self.intercept(
+            Op::RemoveNetwork,
+            id.to_string(),
+            Reply::Network,
+            self.inner.remove_network(id),
+        )
+        .await
+    }
+
+    async fn connect_to_network(
+        &self,
+        container: &ContainerId,
+        network: &NetworkId,
+        aliases: &[NetworkAlias],
+    ) -> Result<(), NetworkError> {
+        self.intercept(
+            Op::ConnectToNetwork,
+            container.to_string(),
+            Reply::Network,
+            self.inner.connect_to_network(container, network, aliases),
+        )
+        .await
+    }
+
+    async fn disconnect_from_network(
+        &self,
+        container: &ContainerId,
+        network: &NetworkId,
+    ) -> Result<(), NetworkError> {
+        self.intercept(
+            Op::DisconnectFromNetwork,
+            container.to_string(),
+            Reply::Network,
+            self.inner.disconnect_from_network(container, network),
+        )
+        .await
+    }
+
+    async fn network_exists(&self, name: &str) -> Result<bool, NetworkError> {
+        self.intercept(
+            Op::NetworkExists,
+            name.to_string(),
+            Reply::NetworkExists,
+            self.inner.network_exists(name),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<R: ExecOps> ExecOps for FaultRuntime<R> {
+    async fn exec(
+        &self,
+        container: &ContainerId,
+        config: &ExecConfig,
+    ) -> Result<ExecResult, ExecError> {
+        self.intercept(
+            Op::Exec,
+            container.to_string(),
+            Reply::Exec,
+            self.inner.exec(container, config),
+        )
+        .await
+    }
+
+    async fn exec_create(
+        &self,
+        container: &ContainerId,
+        config: &ExecConfig,
+    ) -> Result<String, ExecError> {
+        self.intercept(
+            Op::ExecCreate,
+            container.to_string(),
+            Reply::ExecCreated,
+            self.inner.exec_create(container, config),
+        )
+        .await
+    }
+
+    async fn exec_start(&self, exec_id: &str) -> Result<ExecResult, ExecError> {
+        self.intercept(
+            Op::ExecStart,
+            exec_id.to_string(),
+            Reply::Exec,
+            self.inner.exec_start(exec_id),
+        )
+        .await
+    }
+}
+
+/// Runtime that answers each call with the next reply of a [`Recording`],
+/// without touching a daemon.
+///
+/// Calls must arrive in recorded order with the recorded targets. The first
+/// call that does not match fails with a runtime error and is reported by
+/// [`ReplayRuntime::divergence`]; every later call fails too.
+pub struct ReplayRuntime {
+    state: Mutex<ReplayState>,
+}
+
+struct ReplayState {
+    remaining: VecDeque<Call>,
+    divergence: Option<String>,
+}
+
+impl ReplayRuntime {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            state: Mutex::new(ReplayState {
+                remaining: recording.calls.into(),
+                divergence: None,
+            }),
+        }
+    }
+
+    /// Why replay stopped matching the recording, if it did.
+    pub fn divergence(&self) -> Option<String> {
+        self.state.lock().divergence.clone()
+    }
+
+    /// Whether every recorded call was replayed without divergence.
+    pub fn is_finished(&self) -> bool {
+        let state = self.state.lock();
+        state.remaining.is_empty() && state.divergence.is_none()
+    }
+
+    fn next<T, E: Injectable>(
+        &self,
+        op: Op,
+        target: String,
+        unwrap: fn(Reply) -> Option<Result<T, E>>,
+    ) -> Result<T, E> {
+        let mut state = self.state.lock();
+        if let Some(reason) = &state.divergence {
+            return Err(E::injected(format!("replay diverged: {}", reason)));
+        }
+
+        let reason = match state.remaining.pop_front() {
+            Some(call) if call.op == op && call.target == target => match unwrap(call.reply) {
+                Some(result) => return result,
+                None => format!("recorded reply for {:?} has the wrong type", op),
+            },
+            Some(call) => format!(
+                "expected {:?} on {}, got {:?} on {}",
+                call.op, call.target, op, target
+            ),
+            None => format!(
+                "unexpected {:?} on {} after the recording ended",
+                op, target
+            ),
+        };
+        state.divergence = Some(reason.clone());
+        Err(E::injected(format!("replay diverged: {}", reason)))
+    }
+}
+
+impl Sealed for ReplayRuntime {}
+
+#[async_trait]
+impl ImageOps for ReplayRuntime {
+    async fn pull_image(
+        &self,
+        reference: &ImageRef,
+        _auth: Option<&RegistryAuth>,
+    ) -> Result<(), ImageError> {
+        self.next(Op::PullImage, reference.to_string(), |r| match r {
+            Reply::Image(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn load_image(&self, archive: &Path) -> Result<(), ImageError> {
+        self.next(Op::LoadImage, archive.display().to_string(), |r| match r {
+            Reply::Image(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn image_exists(&self, reference: &ImageRef) -> Result<bool, ImageError> {
+        self.next(Op::ImageExists, reference.to_string(), |r| match r {
+            Reply::ImageExists(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn image_id(&self, reference: &ImageRef) -> Result<Option<String>, ImageError> {
+        self.next(Op::ImageId, reference.to_string(), |r| match r {
+            Reply::ImageId(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn image_digests(&self, reference: &ImageRef) -> Result<Vec<String>, ImageError> {
+        self.next(Op::ImageDigests, reference.to_string(), |r| match r {
+            Reply::ImageDigests(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn registry_digest(
+        &self,
+        reference: &ImageRef,
+        _auth: Option<&RegistryAuth>,
+    ) -> Result<String, ImageError> {
+        self.next(Op::RegistryDigest, reference.to_string(), |r| match r {
+            Reply::RegistryDigest(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn remove_image(&self, reference: &ImageRef, _force: bool) -> Result<(), ImageError> {
+        self.next(Op::RemoveImage, reference.to_string(), |r| match r {
+            Reply::Image(r) => Some(r),
+            _ => None,
+        })
+    }
+}
+
+#[async_trait]
+impl ContainerOps for ReplayRuntime {
+    async fn create_container(
+        &self,
+        config: &ContainerConfig,
+    ) -> Result<ContainerId, ContainerError> {
+        self.next(Op::CreateContainer, config.name.clone(), |r| match r {
+            Reply::Created(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn start_container(&self, id: &ContainerId) -> Result<(), ContainerError> {
+        self.next(Op::StartContainer, id.to_string(), |r| match r {
+            Reply::Container(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn stop_container(
+        &self,
+        id: &ContainerId,
+        _timeout: Duration,
+    ) -> Result<(), ContainerError> {
+        self.next(Op::StopContainer, id.to_string(), |r| match r {
+            Reply::Container(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn remove_container(&self, id: &ContainerId, _force: bool) -> Result<(), ContainerError> {
+        self.next(Op::RemoveContainer, id.to_string(), |r| match r {
+            Reply::Container(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn inspect_container(&self, id: &ContainerId) -> Result<ContainerInfo, ContainerError> {
+        self.next(Op::InspectContainer, id.to_string(), |r| match r {
+            Reply::Inspected(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn list_containers(
+        &self,
+        filters: &ContainerFilters,
+    ) -> Result<Vec<ContainerSummary>, ContainerError> {
+        let target = filters.name.clone().unwrap_or_default();
+        self.next(Op::ListContainers, target, |r| match r {
+            Reply::Listed(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn rename_container(
+        &self,
+        id: &ContainerId,
+        _new_name: &str,
+    ) -> Result<(), ContainerError> {
+        self.next(Op::RenameContainer, id.to_string(), |r| match r {
+            Reply::Container(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn container_changes(
+        &self,
+        id: &ContainerId,
+    ) -> Result<Vec<FilesystemChange>, ContainerError> {
+        self.next(Op::ContainerChanges, id.to_string(), |r| match r {
+            Reply::Changes(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn wait_container(&self, id: &ContainerId) -> Result<i64, ContainerError> {
+        self.next(Op::WaitContainer, id.to_string(), |r| match r {
+            Reply::Exited(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn run_healthcheck(
+        &self,
+        id: &ContainerId,
+        _cmd: &[String],
+    ) -> Result<bool, ContainerError> {
+        self.next(Op::RunHealthcheck, id.to_string(), |r| match r {
+            Reply::Healthy(r) => Some(r),
+            _ => None,
+        })
+    }
+}
+
+#[async_trait]
+impl NetworkOps for ReplayRuntime {
+    async fn create_network(&self, config: &NetworkConfig) -> Result<NetworkId, NetworkError> {
+        self.next(Op::CreateNetwork, config.name.clone(), |r| match r {
+            Reply::NetworkCreated(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn remove_network(&self, id: &NetworkId) -> Result<(), NetworkError> {
+        self.next(Op::RemoveNetwork, id.to_string(), |r| match r {
+            Reply::Network(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn connect_to_network(
+        &self,
+        container: &ContainerId,
+        _network: &NetworkId,
+        _aliases: &[NetworkAlias],
+    ) -> Result<(), NetworkError> {
+        self.next(Op::ConnectToNetwork, container.to_string(), |r| match r {
+            Reply::Network(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn disconnect_from_network(
+        &self,
+        container: &ContainerId,
+        _network: &NetworkId,
+    ) -> Result<(), NetworkError> {
+        self.next(
+            Op::DisconnectFromNetwork,
+            container.to_string(),
+            |r| match r {
+                Reply::Network(r) => Some(r),
+                _ => None,
+            },
+        )
+    }
+
+    async fn network_exists(&self, name: &str) -> Result<bool, NetworkError> {
+        self.next(Op::NetworkExists, name.to_string(), |r| match r {
+            Reply::NetworkExists(r) => Some(r),
+            _ => None,
+        })
+    }
+}
+
+#[async_trait]
+impl ExecOps for ReplayRuntime {
+    async fn exec(
+        &self,
+        container: &ContainerId,
+        _config: &ExecConfig,
+    ) -> Result<ExecResult, ExecError> {
+        self.next(Op::Exec, container.to_string(), |r| match r {
+            Reply::Exec(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn exec_create(
+        &self,
+        container: &ContainerId,
+        _config: &ExecConfig,
+    ) -> Result<String, ExecError> {
+        self.next(Op::ExecCreate, container.to_string(), |r| match r {
+            Reply::ExecCreated(r) => Some(r),
+            _ => None,
+        })
+    }
+
+    async fn exec_start(&self, exec_id: &str) -> Result<ExecResult, ExecError> {
+        self.next(Op::ExecStart, exec_id.to_string(), |r| match r {
+            Reply::Exec(r) => Some(r),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(s: &str) -> ContainerId {
+        ContainerId::new(s.to_string())
+    }
+
+    /// Replays a start that succeeds, then whatever else was scripted.
+    fn scripted() -> Recording {
+        let mut recording = Recording::new();
+        recording
+            .push(Op::StartContainer, "a", Reply::Container(Ok(())))
+            .push(Op::StartContainer, "b", Reply::Container(Ok(())))
+            .push(Op::StopContainer, "a", Reply::Container(Ok(())));
+        recording
+    }
+
+    #[tokio::test]
+    async fn fault_fires_once_on_nth_call() {
+        let runtime = FaultRuntime::new(ReplayRuntime::new(scripted()))
+            .fail(Fault::on(Op::StartContainer).nth(2));
+
+        assert!(runtime.start_container(&id("a")).await.is_ok());
+        let err = runtime.start_container(&id("b")).await.unwrap_err();
+        assert!(err.to_string().contains("injected failure"), "{}", err);
+        assert!(runtime.all_faults_fired());
+
+        let calls = runtime.recording();
+        assert_eq!(calls.ops(), vec![Op::StartContainer, Op::StartContainer]);
+        assert!(calls.calls()[1].injected);
+    }
+
+    #[tokio::test]
+    async fn fault_after_op_waits_for_that_op() {
+        let runtime = FaultRuntime::new(ReplayRuntime::new(scripted()))
+            .fail(Fault::on(Op::StopContainer).after(Op::ConnectToNetwork));
+
+        runtime.start_container(&id("a")).await.unwrap();
+        runtime.start_container(&id("b")).await.unwrap();
+        runtime
+            .stop_container(&id("a"), Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(!runtime.all_faults_fired());
+    }
+
+    #[tokio::test]
+    async fn injection_point_names_the_failure() {
+        let runtime =
+            FaultRuntime::new(ReplayRuntime::new(scripted())).fail(InjectionPoint::ContainerStart);
+
+        let err = runtime.start_container(&id("a")).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "runtime error: injected failure at container-start"
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_reports_divergence() {
+        let replay = ReplayRuntime::new(scripted());
+
+        replay.start_container(&id("a")).await.unwrap();
+        let err = replay
+            .stop_container(&id("a"), Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("replay diverged"), "{}", err);
+        assert!(replay.divergence().unwrap().contains("StartContainer on b"));
+        assert!(!replay.is_finished());
+    }
+
+    #[tokio::test]
+    async fn recording_replays_identically() {
+        let recorder = FaultRuntime::new(ReplayRuntime::new(scripted()));
+        recorder.start_container(&id("a")).await.unwrap();
+        recorder.start_container(&id("b")).await.unwrap();
+        recorder
+            .stop_container(&id("a"), Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        let replay = ReplayRuntime::new(recorder.recording());
+        replay.start_container(&id("a")).await.unwrap();
+        replay.start_container(&id("b")).await.unwrap();
+        replay
+            .stop_container(&id("a"), Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(replay.is_finished());
+    }
+}
@@ -1,6 +1,7 @@
 // ABOUTME: Podman container helper for integration tests.
 // ABOUTME: Provides SSH access to a container running Podman daemon.
 
+use crate::ssh::SessionConfig;
 use bollard::Docker;
 use bollard::models::ContainerCreateBody;
 use bollard::query_parameters::{
@@ -9,7 +10,6 @@ use bollard::query_parameters::{
 use bytes::Bytes;
 use futures::StreamExt;
 use http_body_util::{Either, Full};
-use peleka::ssh::SessionConfig;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 use tokio::sync::OnceCell;
@@ -1,6 +1,7 @@
 // ABOUTME: SSH container helper for integration tests.
 // ABOUTME: Builds from local Dockerfile using Gitea registry to avoid Docker Hub rate limits.
 
+use crate::ssh::SessionConfig;
 use bollard::Docker;
 use bollard::models::ContainerCreateBody;
 use bollard::query_parameters::{
@@ -9,7 +10,6 @@ use bollard::query_parameters::{
 use bytes::Bytes;
 use futures::StreamExt;
 use http_body_util::{Either, Full};
-use peleka::ssh::SessionConfig;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
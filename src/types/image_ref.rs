@@ -118,6 +118,20 @@ impl ImageRef {
     pub fn digest(&self) -> Option<&str> {
         self.digest.as_deref()
     }
+
+    /// Fully qualified repository without tag or digest, with Docker Hub
+    /// defaults filled in: `nginx` becomes `docker.io/library/nginx`.
+    pub fn repository(&self) -> String {
+        let registry = match self.registry.as_deref() {
+            None | Some("index.docker.io") | Some("registry-1.docker.io") => "docker.io",
+            Some(registry) => registry,
+        };
+        if registry == "docker.io" && !self.name.contains('/') {
+            format!("docker.io/library/{}", self.name)
+        } else {
+            format!("{}/{}", registry, self.name)
+        }
+    }
 }
 
 impl fmt::Display for ImageRef {
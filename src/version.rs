@@ -0,0 +1,113 @@
+// ABOUTME: Binary version, release lookups, and version comparison.
+// ABOUTME: Backs `peleka version --check` and the config's `min_peleka_version`.
+
+use tokio::process::Command;
+
+/// Version of this binary.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Page listing published releases.
+pub const RELEASES_URL: &str = "https://github.com/vitalratel/peleka/releases/latest";
+
+/// API endpoint describing the latest published release.
+const LATEST_RELEASE_API: &str = "https://api.github.com/repos/vitalratel/peleka/releases/latest";
+
+/// Errors from looking up the latest release.
+#[derive(Debug, thiserror::Error)]
+pub enum ReleaseCheckError {
+    #[error("failed to run curl: {0}")]
+    Curl(#[from] std::io::Error),
+
+    #[error("release lookup failed: {0}")]
+    Failed(String),
+
+    #[error("unexpected release response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Look up the latest published release version (without a leading `v`).
+///
+/// Uses the local `curl`, like the discovery providers do on servers, so no
+/// TLS stack is linked into the binary.
+pub async fn latest_release() -> Result<String, ReleaseCheckError> {
+    let output = Command::new("curl")
+        .args([
+            "-fsSL",
+            "--max-time",
+            "10",
+            "-H",
+            "Accept: application/vnd.github+json",
+            LATEST_RELEASE_API,
+        ])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(ReleaseCheckError::Failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let body: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| ReleaseCheckError::InvalidResponse(e.to_string()))?;
+    body["tag_name"]
+        .as_str()
+        .map(|tag| tag.trim_start_matches('v').to_string())
+        .ok_or_else(|| ReleaseCheckError::InvalidResponse("missing tag_name".to_string()))
+}
+
+/// Whether `candidate` is a newer version than `current`.
+///
+/// Compares `major.minor.patch` numerically; a leading `v` and any
+/// pre-release or build suffix are ignored. Unparseable versions are never
+/// considered newer.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse(candidate), parse(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+/// Parse `major[.minor[.patch]]`, treating missing parts as zero.
+fn parse(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_numerically() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("1.0.0", "0.99.99"));
+        assert!(!is_newer("0.1.2", "0.1.2"));
+        assert!(!is_newer("0.1.1", "0.1.2"));
+    }
+
+    #[test]
+    fn ignores_prefix_and_suffix() {
+        assert!(is_newer("v0.2.0", "0.1.9"));
+        assert!(!is_newer("0.1.2-rc.1", "0.1.2"));
+        assert!(is_newer("0.2", "0.1.5"));
+    }
+
+    #[test]
+    fn unparseable_is_never_newer() {
+        assert!(!is_newer("latest", "0.1.0"));
+        assert!(!is_newer("1.2.3.4", "0.1.0"));
+        assert!(!is_newer("1.0.0", "dev"));
+    }
+}
@@ -197,6 +197,55 @@ servers:
         .stderr(predicate::str::contains("unknown destination"));
 }
 
+#[test]
+fn deploy_help_shows_shadow_flag() {
+    peleka_cmd()
+        .args(["deploy", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--shadow"));
+}
+
+#[test]
+fn deploy_rejects_malformed_env_override() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_content = r#"
+service: myapp
+image: ghcr.io/example/myapp:latest
+servers:
+  - host: server1.example.com
+"#;
+    fs::write(temp_dir.path().join("peleka.yml"), config_content).unwrap();
+
+    peleka_cmd()
+        .current_dir(temp_dir.path())
+        .args(["deploy", "--env", "FEATURE_FLAG"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not KEY=VALUE"));
+}
+
+#[test]
+fn logs_help_shows_previous_flag() {
+    peleka_cmd()
+        .args(["logs", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--previous"));
+}
+
+#[test]
+fn inspect_requires_config_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    peleka_cmd()
+        .current_dir(temp_dir.path())
+        .args(["inspect", "--previous"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::is_match("(?i)configuration file not found").unwrap());
+}
+
 #[test]
 fn exec_command_in_help() {
     peleka_cmd()
@@ -236,3 +285,107 @@ servers:
         .failure()
         .stderr(predicate::str::contains("required"));
 }
+
+#[test]
+fn ping_command_in_help() {
+    peleka_cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ping"));
+}
+
+#[test]
+fn ping_requires_config_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    peleka_cmd()
+        .current_dir(temp_dir.path())
+        .arg("ping")
+        .assert()
+        .failure()
+        .stderr(predicate::str::is_match("(?i)configuration file not found").unwrap());
+}
+
+#[test]
+fn completions_call_back_for_destinations() {
+    for shell in ["bash", "zsh", "fish"] {
+        peleka_cmd()
+            .args(["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("peleka __complete destinations"))
+            .stdout(predicate::str::contains("deploy"));
+    }
+}
+
+#[test]
+fn complete_destinations_lists_config_destinations() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_content = r#"
+service: myapp
+image: ghcr.io/example/myapp:latest
+servers:
+  - host: server1.example.com
+destinations:
+  staging:
+    servers:
+      - host: staging.example.com
+  production:
+    servers:
+      - host: prod.example.com
+"#;
+    fs::write(temp_dir.path().join("peleka.yml"), config_content).unwrap();
+
+    peleka_cmd()
+        .current_dir(temp_dir.path())
+        .args(["__complete", "destinations"])
+        .assert()
+        .success()
+        .stdout("production\nstaging\n");
+}
+
+#[test]
+fn complete_destinations_without_config_prints_nothing() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    peleka_cmd()
+        .current_dir(temp_dir.path())
+        .args(["__complete", "destinations"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn version_prints_binary_version() {
+    peleka_cmd()
+        .arg("version")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "peleka {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+}
+
+#[test]
+fn diff_fs_command_in_help() {
+    peleka_cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("diff-fs"));
+}
+
+#[test]
+fn diff_fs_requires_config_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    peleka_cmd()
+        .current_dir(temp_dir.path())
+        .args(["diff-fs", "--ignore", "/tmp"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::is_match("(?i)configuration file not found").unwrap());
+}
@@ -416,6 +416,26 @@ healthcheck:
         assert_eq!(hc.timeout, Duration::from_secs(5));
         assert_eq!(hc.retries, 3);
         assert_eq!(hc.start_period, Duration::from_secs(30));
+        assert_eq!(hc.warmup_requests, 0);
+        assert_eq!(hc.warmup_interval, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn parse_healthcheck_with_warmup() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+healthcheck:
+  cmd: "curl -f http://localhost:8080/health"
+  warmup_requests: 5
+  warmup_interval: 200ms
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let hc = config.healthcheck.unwrap();
+        assert_eq!(hc.warmup_requests, 5);
+        assert_eq!(hc.warmup_interval, Duration::from_millis(200));
     }
 
     #[test]
@@ -440,6 +460,86 @@ healthcheck:
         assert_eq!(hc.retries, 5);
         assert_eq!(hc.start_period, Duration::from_secs(10));
     }
+
+    #[test]
+    fn parse_external_healthcheck_without_cmd() {
+        let yaml = r#"
+service: myapp
+image: ghcr.io/org/static-app:v1
+servers:
+  - host: example.com
+healthcheck:
+  external:
+    image: curlimages/curl:8.10.1
+    cmd: "curl -f http://{{host}}:8080/health"
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let hc = config.healthcheck.unwrap();
+        assert!(hc.cmd.is_empty());
+        let external = hc.external.unwrap();
+        assert_eq!(external.image.to_string(), "curlimages/curl:8.10.1");
+        assert_eq!(external.cmd, "curl -f http://{{host}}:8080/health");
+    }
+
+    #[test]
+    fn reject_healthcheck_without_cmd_or_external() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+healthcheck:
+  interval: 5s
+"#;
+        let err = Config::from_yaml(yaml).unwrap_err();
+        assert!(err.to_string().contains("`cmd` or `external`"));
+    }
+}
+
+mod stop {
+    use super::*;
+
+    #[test]
+    fn pre_stop_command_defaults_to_none() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+stop:
+  timeout: 20s
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let stop = config.stop.unwrap();
+        assert_eq!(stop.pre_stop_command, None);
+        assert_eq!(stop.pre_stop_timeout, Duration::from_secs(10));
+        assert_eq!(stop.pre_stop_args(), None);
+    }
+
+    #[test]
+    fn parse_pre_stop_command() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+stop:
+  pre_stop_command: "nginx -s quit"
+  pre_stop_timeout: 15s
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let stop = config.stop.unwrap();
+        assert_eq!(stop.pre_stop_timeout, Duration::from_secs(15));
+        assert_eq!(
+            stop.pre_stop_args(),
+            Some(vec![
+                "nginx".to_string(),
+                "-s".to_string(),
+                "quit".to_string()
+            ])
+        );
+        assert_eq!(stop.timeout, Duration::from_secs(30));
+    }
 }
 
 mod runtime_config {
@@ -521,6 +621,32 @@ pull_policy: never
         assert_eq!(config.pull_policy, PullPolicy::Never);
     }
 
+    #[test]
+    fn parse_if_not_present_policy() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+pull_policy: if-not-present
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.pull_policy, PullPolicy::IfNotPresent);
+    }
+
+    #[test]
+    fn parse_digest_changed_policy() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+pull_policy: digest-changed
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.pull_policy, PullPolicy::DigestChanged);
+    }
+
     #[test]
     fn default_policy_is_always() {
         let yaml = r#"
@@ -532,6 +658,27 @@ servers:
         let config = Config::from_yaml(yaml).unwrap();
         assert_eq!(config.pull_policy, PullPolicy::Always);
     }
+
+    #[test]
+    fn parse_podman_auto_update() {
+        let yaml = r#"
+service: myapp
+image: ghcr.io/org/app:stable
+servers:
+  - host: example.com
+podman_auto_update: registry
+labels:
+  PODMAN_SYSTEMD_UNIT: myapp.service
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.podman_auto_update, Some(AutoUpdatePolicy::Registry));
+        assert_eq!(
+            config.auto_update_policy(),
+            Some(AutoUpdatePolicy::Registry)
+        );
+        assert_eq!(config.effective_pull_policy(), PullPolicy::IfNotPresent);
+        assert!(config.auto_update_warnings().is_empty());
+    }
 }
 
 mod strategy_config {
@@ -563,6 +710,19 @@ strategy: blue-green
         assert_eq!(config.strategy, Some(StrategyConfig::BlueGreen));
     }
 
+    #[test]
+    fn parse_replace_strategy() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+strategy: replace
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.strategy, Some(StrategyConfig::Replace));
+    }
+
     #[test]
     fn default_strategy_is_none() {
         let yaml = r#"
@@ -575,3 +735,430 @@ servers:
         assert!(config.strategy.is_none());
     }
 }
+
+mod ssh_timing_config {
+    use super::*;
+
+    #[test]
+    fn parse_server_with_keepalive_settings() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+    inactivity_timeout: 2m
+    keepalive_interval: 10s
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let server = &config.servers[0];
+        assert_eq!(server.inactivity_timeout, Some(Duration::from_secs(120)));
+        assert_eq!(server.keepalive_interval, Some(Duration::from_secs(10)));
+
+        let session = server.ssh_session_config();
+        assert_eq!(session.inactivity_timeout, Some(Duration::from_secs(120)));
+        assert_eq!(session.keepalive_interval, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn parse_server_with_reconnect_attempts() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+    reconnect_attempts: 0
+  - host: other.example.com
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.servers[0].reconnect_attempts, Some(0));
+        assert_eq!(config.servers[0].ssh_session_config().reconnect_attempts, 0);
+        assert_eq!(config.servers[1].ssh_session_config().reconnect_attempts, 3);
+    }
+
+    #[test]
+    fn session_config_uses_defaults_when_unset() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let session = config.servers[0].ssh_session_config();
+        assert_eq!(session.inactivity_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(session.keepalive_interval, Some(Duration::from_secs(15)));
+    }
+}
+
+mod ssh_users {
+    use super::*;
+    use peleka::config::SshRole;
+
+    #[test]
+    fn deploy_and_admin_users_pick_session_identity() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+    user: legacy
+    deploy_user: deployer
+    admin_user: ops
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let server = &config.servers[0];
+        assert_eq!(server.ssh_user(), "deployer");
+        assert_eq!(server.ssh_session_config().user, "deployer");
+        assert_eq!(server.ssh_session_config_for(SshRole::Admin).user, "ops");
+        assert!(server.has_separate_admin());
+    }
+
+    #[test]
+    fn admin_falls_back_to_deploy_user() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+    user: deploy
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let server = &config.servers[0];
+        assert_eq!(server.ssh_user_for(SshRole::Deploy), "deploy");
+        assert_eq!(server.ssh_user_for(SshRole::Admin), "deploy");
+        assert!(!server.has_separate_admin());
+    }
+}
+
+mod config_fragments {
+    use super::*;
+
+    const BASE: &str = r#"
+service: myapp
+image: ghcr.io/org/app:v1
+servers:
+  - host: example.com
+env:
+  LOG_LEVEL: info
+"#;
+
+    #[test]
+    fn fragments_merge_in_lexical_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILENAME), BASE).unwrap();
+        let fragments = dir.path().join(CONFIG_FRAGMENT_DIR);
+        std::fs::create_dir_all(&fragments).unwrap();
+        std::fs::write(
+            fragments.join("10-ci.yml"),
+            "image: ghcr.io/org/app:v2
+",
+        )
+        .unwrap();
+        std::fs::write(
+            fragments.join("20-env.yaml"),
+            "image: ghcr.io/org/app:v3
+env:
+  FEATURE: \"on\"
+",
+        )
+        .unwrap();
+
+        let config = Config::discover(dir.path()).unwrap();
+        assert_eq!(config.image.to_string(), "ghcr.io/org/app:v3");
+        assert_eq!(
+            config.env.get("LOG_LEVEL"),
+            Some(&EnvValue::Literal("info".to_string()))
+        );
+        assert_eq!(
+            config.env.get("FEATURE"),
+            Some(&EnvValue::Literal("on".to_string()))
+        );
+    }
+
+    #[test]
+    fn base_config_alone_without_fragment_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILENAME), BASE).unwrap();
+
+        let config = Config::discover(dir.path()).unwrap();
+        assert_eq!(config.image.to_string(), "ghcr.io/org/app:v1");
+    }
+
+    #[test]
+    fn invalid_fragment_names_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILENAME), BASE).unwrap();
+        let fragments = dir.path().join(CONFIG_FRAGMENT_DIR);
+        std::fs::create_dir_all(&fragments).unwrap();
+        std::fs::write(
+            fragments.join("bad.yml"),
+            "image: [unclosed
+",
+        )
+        .unwrap();
+
+        let err = Config::discover(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("bad.yml"), "{err}");
+    }
+}
+
+mod command_config {
+    use super::*;
+
+    #[test]
+    fn parse_command_and_args() {
+        let yaml = r#"
+service: myapp
+image: ghcr.io/org/app:v2.0.1
+servers:
+  - host: example.com
+command: ["/app/server"]
+args: ["--release={{version}}", "--name={{service}}"]
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.command, Some(vec!["/app/server".to_string()]));
+
+        let vars = TemplateVars::for_config(&config);
+        let args = vars.render_all(config.args.as_ref().unwrap()).unwrap();
+        assert_eq!(args, vec!["--release=v2.0.1", "--name=myapp"]);
+    }
+
+    #[test]
+    fn args_default_to_none() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert!(config.args.is_none());
+    }
+}
+
+mod discovery_config {
+    use super::*;
+
+    #[test]
+    fn parse_discovery_providers() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+ports:
+  - "8080"
+discovery:
+  - type: consul
+    tags: [web]
+  - type: webhook
+    url: https://dns.example.com/records
+  - type: tailscale
+    tags: ["tag:web"]
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.discovery.len(), 3);
+        assert_eq!(
+            config.discovery[0],
+            DiscoveryConfig::Consul {
+                address: "http://127.0.0.1:8500".to_string(),
+                port: None,
+                tags: vec!["web".to_string()],
+            }
+        );
+        assert_eq!(config.discovery[1].kind(), "webhook");
+        assert_eq!(config.discovery[2].kind(), "tailscale");
+        assert_eq!(config.primary_container_port(), Some(8080));
+    }
+
+    #[test]
+    fn unknown_provider_type_is_error() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+discovery:
+  - type: zookeeper
+"#;
+        assert!(Config::from_yaml(yaml).is_err());
+    }
+
+    #[test]
+    fn primary_container_port_uses_container_side() {
+        let mut config = Config::template();
+        config.ports = vec!["80:3000/tcp".to_string()];
+        assert_eq!(config.primary_container_port(), Some(3000));
+
+        config.ports = vec![];
+        assert_eq!(config.primary_container_port(), None);
+    }
+}
+
+mod read_only_config {
+    use super::*;
+
+    #[test]
+    fn parse_read_only_with_tmpfs() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+read_only: true
+tmpfs:
+  - /tmp
+  - /run:size=16m
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert!(config.read_only);
+        assert_eq!(config.tmpfs, vec!["/tmp", "/run:size=16m"]);
+        assert!(config.read_only_warnings().is_empty());
+    }
+
+    #[test]
+    fn read_only_defaults_to_false() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert!(!config.read_only);
+        assert!(config.tmpfs.is_empty());
+    }
+}
+
+mod health_config {
+    use super::*;
+
+    #[test]
+    fn parse_auto_restart_with_defaults() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+health:
+  auto_restart: true
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let health = config.health.unwrap();
+        assert!(health.auto_restart);
+        assert_eq!(health.max_restarts, 3);
+        assert_eq!(health.restart_window, Duration::from_secs(600));
+        assert_eq!(health.poll_interval, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn parse_custom_rate_limit() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+health:
+  auto_restart: true
+  max_restarts: 5
+  restart_window: 1h
+  poll_interval: 30s
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let health = config.health.unwrap();
+        assert_eq!(health.max_restarts, 5);
+        assert_eq!(health.restart_window, Duration::from_secs(3600));
+        assert_eq!(health.poll_interval, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn zero_poll_interval_returns_error() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+health:
+  poll_interval: 0s
+"#;
+        let err = Config::from_yaml(yaml).unwrap_err();
+        assert!(err.to_string().contains("greater than zero"), "{}", err);
+    }
+}
+
+mod image_archive_config {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parse_image_archive() {
+        let yaml = r#"
+service: myapp
+image: myapp:ci-123
+servers:
+  - host: example.com
+image_archive: ./dist/app.oci.tar
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(
+            config.image_archive.as_deref(),
+            Some(Path::new("./dist/app.oci.tar"))
+        );
+    }
+
+    #[test]
+    fn image_archive_defaults_to_none() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert!(config.image_archive.is_none());
+    }
+}
+
+mod min_peleka_version {
+    use super::*;
+
+    fn config_requiring(version: &str) -> Config {
+        let yaml = format!(
+            r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+min_peleka_version: "{}"
+"#,
+            version
+        );
+        Config::from_yaml(&yaml).unwrap()
+    }
+
+    #[test]
+    fn newer_requirement_is_reported() {
+        let config = config_requiring("0.3.0");
+        assert_eq!(config.requires_newer_peleka("0.2.9"), Some("0.3.0"));
+    }
+
+    #[test]
+    fn satisfied_requirement_is_not_reported() {
+        let config = config_requiring("0.3.0");
+        assert_eq!(config.requires_newer_peleka("0.3.0"), None);
+        assert_eq!(config.requires_newer_peleka("1.0.0"), None);
+    }
+
+    #[test]
+    fn absent_by_default() {
+        let yaml = r#"
+service: myapp
+image: nginx
+servers:
+  - host: example.com
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.min_peleka_version, None);
+        assert_eq!(config.requires_newer_peleka("0.0.1"), None);
+    }
+}
@@ -150,3 +150,15 @@ fn deployment_with_old_container() {
 
     assert_eq!(deployment.old_container(), Some(&old_id));
 }
+
+/// Test: Shadow deployment never references an existing container.
+#[test]
+fn shadow_deployment_has_no_old_container() {
+    use peleka::config::Config;
+
+    let config = Config::template();
+    let deployment: Deployment<Initialized> = Deployment::new_shadow(config);
+
+    assert!(deployment.is_shadow());
+    assert!(deployment.old_container().is_none());
+}
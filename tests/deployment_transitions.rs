@@ -17,13 +17,13 @@ use std::time::Duration;
 #[test]
 fn transition_type_signatures_compile() {
     use peleka::deploy::DeployError;
-    use peleka::runtime::{ContainerOps, ImageOps, NetworkOps, RegistryAuth};
+    use peleka::runtime::{ContainerOps, ExecOps, ImageOps, NetworkOps, RegistryAuth};
     use peleka::types::NetworkId;
 
     // This function is never called, but it must compile.
     // If any type signature is wrong, this will fail to compile.
     #[allow(dead_code)]
-    async fn check_signatures<R: ImageOps + ContainerOps + NetworkOps>(
+    async fn check_signatures<R: ImageOps + ContainerOps + NetworkOps + ExecOps>(
         runtime: &R,
         network_id: &NetworkId,
     ) {
@@ -111,7 +111,8 @@ async fn full_deployment_chain() {
     // Create deployment config with network
     let mut deploy_config = support::test_config("test-deploy");
     deploy_config.network = Some(peleka::config::NetworkConfig {
-        name: "peleka-test-network".to_string(),
+        name: Some("peleka-test-network".to_string()),
+        shared: false,
         aliases: vec![],
     });
 
@@ -195,6 +196,292 @@ async fn container_start_failure_cleans_up() {
         .expect("disconnect should succeed");
 }
 
+/// Replayed from scripted recordings of runtime calls, so no daemon is needed.
+mod replay {
+    use super::*;
+
+    /// Test: An injected start failure removes the created container.
+    #[tokio::test]
+    async fn injected_start_failure_removes_created_container() {
+        use peleka::deploy::Deployment;
+        use peleka::testkit::faults::{
+            FaultRuntime, InjectionPoint, Op, Recording, ReplayRuntime, Reply,
+        };
+        use peleka::types::ContainerId;
+
+        let config = support::test_config("test-inject-start");
+        let image = config.image.to_string();
+
+        let mut recording = Recording::new();
+        recording
+            .push(Op::PullImage, image.as_str(), Reply::Image(Ok(())))
+            .push(
+                Op::ImageDigests,
+                image.as_str(),
+                Reply::ImageDigests(Ok(vec![])),
+            )
+            .push(
+                Op::CreateContainer,
+                "test-inject-start-blue",
+                Reply::Created(Ok(ContainerId::new("new".to_string()))),
+            )
+            .push(Op::RemoveContainer, "new", Reply::Container(Ok(())));
+        let runtime =
+            FaultRuntime::new(ReplayRuntime::new(recording)).fail(InjectionPoint::ContainerStart);
+
+        let pulled = Deployment::new(config)
+            .pull_image(&runtime, None)
+            .await
+            .expect("pull should replay");
+        let err = pulled
+            .start_container(&runtime)
+            .await
+            .expect_err("start should fail");
+
+        assert!(
+            err.to_string()
+                .contains("injected failure at container-start"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            runtime.recording().ops(),
+            vec![
+                Op::PullImage,
+                Op::ImageDigests,
+                Op::CreateContainer,
+                Op::StartContainer,
+                Op::RemoveContainer,
+            ]
+        );
+        assert!(runtime.inner().is_finished());
+    }
+
+    /// Test: An injected healthcheck failure fails the health check.
+    #[tokio::test]
+    async fn injected_health_check_failure_fails_health_check() {
+        use peleka::config::HealthcheckConfig;
+        use peleka::deploy::Deployment;
+        use peleka::testkit::faults::{
+            FaultRuntime, InjectionPoint, Op, Recording, ReplayRuntime, Reply,
+        };
+        use peleka::types::ContainerId;
+
+        let mut config = support::test_config("test-inject-health");
+        config.healthcheck = Some(HealthcheckConfig {
+            cmd: "true".to_string(),
+            interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(1),
+            retries: 0,
+            start_period: Duration::ZERO,
+            external: None,
+            warmup_requests: 0,
+            warmup_interval: Duration::from_millis(10),
+        });
+        let image = config.image.to_string();
+
+        let mut recording = Recording::new();
+        recording
+            .push(Op::PullImage, image.as_str(), Reply::Image(Ok(())))
+            .push(
+                Op::ImageDigests,
+                image.as_str(),
+                Reply::ImageDigests(Ok(vec![])),
+            )
+            .push(
+                Op::CreateContainer,
+                "test-inject-health-blue",
+                Reply::Created(Ok(ContainerId::new("new".to_string()))),
+            )
+            .push(Op::StartContainer, "new", Reply::Container(Ok(())));
+        let runtime =
+            FaultRuntime::new(ReplayRuntime::new(recording)).fail(InjectionPoint::HealthCheck);
+
+        let started = Deployment::new(config)
+            .pull_image(&runtime, None)
+            .await
+            .expect("pull should replay")
+            .start_container(&runtime)
+            .await
+            .expect("start should replay");
+        let (_, err) = started
+            .health_check(&runtime, Duration::from_secs(5))
+            .await
+            .expect_err("health check should fail");
+
+        assert!(
+            err.to_string().contains("injected failure at health-check"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            runtime.recording().ops(),
+            vec![
+                Op::PullImage,
+                Op::ImageDigests,
+                Op::CreateContainer,
+                Op::StartContainer,
+                Op::RunHealthcheck,
+            ]
+        );
+        assert!(runtime.inner().is_finished());
+    }
+
+    /// Test: The replace strategy runs the pre-stop command before stopping.
+    #[tokio::test]
+    async fn replace_strategy_runs_pre_stop_command() {
+        use peleka::deploy::Deployment;
+        use peleka::runtime::ExecResult;
+        use peleka::testkit::faults::{Op, Recording, ReplayRuntime, Reply};
+        use peleka::types::ContainerId;
+
+        let mut config = support::test_config("test-replace-pre-stop");
+        if let Some(stop) = config.stop.as_mut() {
+            stop.pre_stop_command = Some("nginx -s quit".to_string());
+        }
+        let image = config.image.to_string();
+
+        let mut recording = Recording::new();
+        recording
+            .push(Op::PullImage, image.as_str(), Reply::Image(Ok(())))
+            .push(
+                Op::ImageDigests,
+                image.as_str(),
+                Reply::ImageDigests(Ok(vec![])),
+            )
+            .push(
+                Op::Exec,
+                "old",
+                Reply::Exec(Ok(ExecResult {
+                    exit_code: 0,
+                    stdout: Vec::new(),
+                    stderr: Vec::new(),
+                })),
+            )
+            .push(Op::StopContainer, "old", Reply::Container(Ok(())));
+        let runtime = ReplayRuntime::new(recording);
+
+        Deployment::new_update(config, ContainerId::new("old".to_string()))
+            .pull_image(&runtime, None)
+            .await
+            .expect("pull should replay")
+            .stop_old_container(&runtime)
+            .await
+            .expect("stop should replay");
+
+        assert!(runtime.is_finished());
+    }
+
+    /// Build a container summary for scripted `ListContainers` replies.
+    fn listed(
+        id: &str,
+        name: &str,
+        state: &str,
+        labels: &[(&str, &str)],
+    ) -> peleka::runtime::ContainerSummary {
+        peleka::runtime::ContainerSummary {
+            id: peleka::types::ContainerId::new(id.to_string()),
+            name: name.to_string(),
+            image: "app:v1".to_string(),
+            state: state.to_string(),
+            status: String::new(),
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Test: Only stopped containers from another deploy run are leftovers.
+    #[tokio::test]
+    async fn remove_leftovers_only_removes_other_runs_containers() {
+        use peleka::deploy::{DeployMetadata, Deployment};
+        use peleka::testkit::faults::{Op, Recording, ReplayRuntime, Reply};
+        use peleka::types::ContainerId;
+
+        let mut metadata = DeployMetadata::new();
+        metadata.id = "run-2".to_string();
+        let deployment =
+            Deployment::new(support::test_config("test-leftovers")).with_metadata(metadata);
+
+        let mut recording = Recording::new();
+        recording
+            .push(
+                Op::ListContainers,
+                "",
+                Reply::Listed(Ok(vec![
+                    listed(
+                        "crashed",
+                        "test-leftovers-blue",
+                        "created",
+                        &[("peleka.deploy-id", "run-1")],
+                    ),
+                    listed("unlabeled", "test-leftovers-blue", "exited", &[]),
+                    listed(
+                        "ours",
+                        "test-leftovers-blue",
+                        "created",
+                        &[("peleka.deploy-id", "run-2")],
+                    ),
+                ])),
+            )
+            .push(Op::RemoveContainer, "crashed", Reply::Container(Ok(())));
+        let runtime = ReplayRuntime::new(recording);
+
+        let removed = deployment
+            .remove_leftovers(&runtime)
+            .await
+            .expect("leftovers should be removed");
+
+        assert_eq!(removed, vec![ContainerId::new("crashed".to_string())]);
+        assert!(runtime.is_finished());
+    }
+
+    /// Test: Ephemeral containers of other runs are removed, the current run's kept.
+    #[tokio::test]
+    async fn cleanup_ephemeral_keeps_current_run() {
+        use peleka::deploy::{EPHEMERAL_LABEL, RUN_ID_LABEL, cleanup_ephemeral};
+        use peleka::testkit::faults::{Op, Recording, ReplayRuntime, Reply};
+        use peleka::types::ContainerId;
+
+        let config = support::test_config("test-ephemeral");
+
+        let mut recording = Recording::new();
+        recording
+            .push(
+                Op::ListContainers,
+                "",
+                Reply::Listed(Ok(vec![
+                    listed(
+                        "stale-checker",
+                        "test-ephemeral-check-1",
+                        "exited",
+                        &[(EPHEMERAL_LABEL, "true"), (RUN_ID_LABEL, "run-1")],
+                    ),
+                    listed(
+                        "current-checker",
+                        "test-ephemeral-check-2",
+                        "running",
+                        &[(EPHEMERAL_LABEL, "true"), (RUN_ID_LABEL, "run-2")],
+                    ),
+                ])),
+            )
+            .push(
+                Op::RemoveContainer,
+                "stale-checker",
+                Reply::Container(Ok(())),
+            );
+        let runtime = ReplayRuntime::new(recording);
+
+        let removed = cleanup_ephemeral(&runtime, &config.service, "run-2")
+            .await
+            .expect("listing should replay");
+
+        assert_eq!(removed, vec![ContainerId::new("stale-checker".to_string())]);
+        assert!(runtime.is_finished());
+    }
+}
+
 /// Test: Rollback from ContainerStarted removes new container.
 #[tokio::test]
 async fn rollback_from_container_started_removes_container() {
@@ -30,6 +30,9 @@ async fn health_check_passes() {
         timeout: Duration::from_secs(5),
         retries: 2,
         start_period: Duration::from_secs(1),
+        external: None,
+        warmup_requests: 0,
+        warmup_interval: Duration::from_millis(500),
     });
 
     let d1 = Deployment::new(deploy_config);
@@ -81,6 +84,9 @@ async fn health_check_fails_and_rollback() {
         timeout: Duration::from_secs(2),
         retries: 2,
         start_period: Duration::from_secs(1),
+        external: None,
+        warmup_requests: 0,
+        warmup_interval: Duration::from_millis(500),
     });
 
     let d1 = Deployment::new(deploy_config);
@@ -1,7 +1,7 @@
 // ABOUTME: Integration tests for hooks system.
 // ABOUTME: Tests hook discovery, execution, and environment variable passing.
 
-use peleka::hooks::{HookContext, HookPoint, HookRunner};
+use peleka::hooks::{ConfigSummary, HookContext, HookPoint, HookRunner};
 use peleka::types::ServiceName;
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
@@ -31,6 +31,10 @@ fn test_context() -> HookContext {
         server: "test.example.com".to_string(),
         runtime: "docker".to_string(),
         previous_version: Some("v0.9.0".to_string()),
+        config: ConfigSummary::default(),
+        phases: vec![],
+        elapsed: None,
+        docker_host: None,
     }
 }
 
@@ -147,6 +151,52 @@ exit 0
     assert!(result.stdout.contains("PREVIOUS=v0.9.0"));
 }
 
+/// Test: Hook receives the JSON context on stdin.
+#[tokio::test]
+async fn hook_receives_json_on_stdin() {
+    let temp_dir = TempDir::new().unwrap();
+    create_hook(
+        &temp_dir,
+        "post-deploy",
+        "#!/bin/sh
+cat
+",
+    );
+
+    let runner = HookRunner::new(temp_dir.path());
+    let result = runner
+        .run(HookPoint::PostDeploy, &test_context())
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    let json: serde_json::Value = serde_json::from_str(&result.stdout).unwrap();
+    assert_eq!(json["hook"], "post-deploy");
+    assert_eq!(json["service"], "testapp");
+    assert_eq!(json["server"], "test.example.com");
+    assert_eq!(json["previous_version"], "v0.9.0");
+}
+
+/// Test: Hooks that ignore stdin still succeed.
+#[tokio::test]
+async fn hook_ignoring_stdin_succeeds() {
+    let temp_dir = TempDir::new().unwrap();
+    create_hook(
+        &temp_dir,
+        "pre-deploy",
+        "#!/bin/sh
+exit 0
+",
+    );
+
+    let runner = HookRunner::new(temp_dir.path());
+    let result = runner
+        .run(HookPoint::PreDeploy, &test_context())
+        .await
+        .unwrap();
+    assert!(result.success);
+}
+
 /// Test: Missing hook returns None.
 #[tokio::test]
 async fn missing_hook_returns_none() {
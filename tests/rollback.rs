@@ -61,12 +61,14 @@ async fn manual_rollback_swaps_containers() {
         grace_period: Duration::from_secs(0),
     });
     deploy_config.network = Some(peleka::config::NetworkConfig {
-        name: "peleka-test-rollback-swap".to_string(),
+        name: Some("peleka-test-rollback-swap".to_string()),
+        shared: false,
         aliases: vec![],
     });
     deploy_config.stop = Some(peleka::config::StopConfig {
         timeout: Duration::from_secs(5),
         signal: "SIGTERM".to_string(),
+        ..Default::default()
     });
 
     // First deployment - creates "active" container
@@ -181,12 +183,14 @@ async fn rollback_fails_without_previous() {
     // Create first deployment only (no previous)
     let mut deploy_config = support::test_config("test-rollback-no-prev");
     deploy_config.network = Some(peleka::config::NetworkConfig {
-        name: "peleka-test-rollback-no-prev".to_string(),
+        name: Some("peleka-test-rollback-no-prev".to_string()),
+        shared: false,
         aliases: vec![],
     });
     deploy_config.stop = Some(peleka::config::StopConfig {
         timeout: Duration::from_secs(5),
         signal: "SIGTERM".to_string(),
+        ..Default::default()
     });
 
     let d1 = Deployment::new(deploy_config.clone());
@@ -261,12 +265,14 @@ async fn double_rollback_swaps_back() {
         grace_period: Duration::from_secs(0),
     });
     deploy_config.network = Some(peleka::config::NetworkConfig {
-        name: "peleka-test-rollback-pingpong".to_string(),
+        name: Some("peleka-test-rollback-pingpong".to_string()),
+        shared: false,
         aliases: vec![],
     });
     deploy_config.stop = Some(peleka::config::StopConfig {
         timeout: Duration::from_secs(5),
         signal: "SIGTERM".to_string(),
+        ..Default::default()
     });
 
     // First deployment
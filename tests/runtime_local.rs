@@ -124,6 +124,8 @@ async fn container_lifecycle() {
         },
         ports: vec![],
         volumes: vec![],
+        tmpfs: HashMap::new(),
+        read_only: false,
         command: Some(vec!["sleep".to_string(), "30".to_string()]),
         entrypoint: None,
         working_dir: None,
@@ -217,6 +219,8 @@ async fn rename_container() {
         labels: HashMap::new(),
         ports: vec![],
         volumes: vec![],
+        tmpfs: HashMap::new(),
+        read_only: false,
         command: Some(vec!["sleep".to_string(), "30".to_string()]),
         entrypoint: None,
         working_dir: None,
@@ -296,6 +300,8 @@ async fn network_operations() {
         labels: HashMap::new(),
         ports: vec![],
         volumes: vec![],
+        tmpfs: HashMap::new(),
+        read_only: false,
         command: Some(vec!["sleep".to_string(), "30".to_string()]),
         entrypoint: None,
         working_dir: None,
@@ -385,6 +391,8 @@ async fn exec_command() {
         labels: HashMap::new(),
         ports: vec![],
         volumes: vec![],
+        tmpfs: HashMap::new(),
+        read_only: false,
         command: Some(vec!["sleep".to_string(), "60".to_string()]),
         entrypoint: None,
         working_dir: None,
@@ -513,6 +521,8 @@ async fn log_streaming() {
         labels: HashMap::new(),
         ports: vec![],
         volumes: vec![],
+        tmpfs: HashMap::new(),
+        read_only: false,
         command: Some(vec![
             "sh".to_string(),
             "-c".to_string(),
@@ -196,3 +196,34 @@ async fn command_timeout_returns_error() {
         .await
         .expect("disconnect should succeed");
 }
+
+/// Test: Many concurrent commands on clones of one session.
+/// Expected: More commands than channels all succeed, each with its own output.
+#[tokio::test]
+async fn concurrent_exec_on_cloned_sessions() {
+    let container = shared_container().await;
+    let config = container.session_config().max_channels(2);
+
+    let session = Session::connect(config)
+        .await
+        .expect("connection should succeed");
+
+    let tasks: Vec<_> = (0..12)
+        .map(|i| {
+            let session = session.clone();
+            tokio::spawn(async move { (i, session.exec(&format!("echo {}", i)).await) })
+        })
+        .collect();
+
+    for task in futures::future::join_all(tasks).await {
+        let (i, output) = task.expect("task should not panic");
+        let output = output.expect("command should succeed");
+        assert!(output.success());
+        assert_eq!(output.stdout.trim(), i.to_string());
+    }
+
+    session
+        .disconnect()
+        .await
+        .expect("disconnect should succeed");
+}
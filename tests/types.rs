@@ -71,6 +71,19 @@ mod image_ref_tests {
         let img = ImageRef::parse("ghcr.io/org/repo:v1").unwrap();
         assert_eq!(img.to_string(), "ghcr.io/org/repo:v1");
     }
+
+    #[test]
+    fn repository_fills_in_docker_hub_defaults() {
+        let repository = |s: &str| ImageRef::parse(s).unwrap().repository();
+        assert_eq!(repository("nginx:1.27"), "docker.io/library/nginx");
+        assert_eq!(
+            repository("docker.io/library/nginx"),
+            "docker.io/library/nginx"
+        );
+        assert_eq!(repository("index.docker.io/org/app"), "docker.io/org/app");
+        assert_eq!(repository("org/app@sha256:abc"), "docker.io/org/app");
+        assert_eq!(repository("ghcr.io/org/app:v1"), "ghcr.io/org/app");
+    }
 }
 
 mod network_alias_tests {
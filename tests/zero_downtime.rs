@@ -38,7 +38,8 @@ async fn ensure_network_creates_if_not_exists() {
     // Create deployment config with specific network
     let mut deploy_config = support::test_config("test-ensure-net");
     deploy_config.network = Some(peleka::config::NetworkConfig {
-        name: test_network_name.to_string(),
+        name: Some(test_network_name.to_string()),
+        shared: false,
         aliases: vec![],
     });
 